@@ -37,6 +37,8 @@ export const CONFIG_ARRAY_START =
   1 + // retain authority
   4; // max number of lines;
 export const CONFIG_LINE_SIZE = 4 + 32 + 4 + 200;
+export const CONFIG_MACHINE_COUNT_SIZE = 8; // candy machines using the config
+export const CONFIG_WATERMARKS_SIZE = 8 + 8; // items available + lines minted
 
 export const CACHE_PATH = './.cache';
 
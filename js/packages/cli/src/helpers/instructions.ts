@@ -8,6 +8,8 @@ import {
   CANDY_MACHINE_PROGRAM_ID,
   CONFIG_ARRAY_START,
   CONFIG_LINE_SIZE,
+  CONFIG_MACHINE_COUNT_SIZE,
+  CONFIG_WATERMARKS_SIZE,
   SPL_ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
   TOKEN_METADATA_PROGRAM_ID,
@@ -216,7 +218,9 @@ export async function createConfigAccount(
     4 +
     configData.maxNumberOfLines.toNumber() * CONFIG_LINE_SIZE +
     4 +
-    Math.ceil(configData.maxNumberOfLines.toNumber() / 8);
+    Math.ceil(configData.maxNumberOfLines.toNumber() / 8) +
+    CONFIG_MACHINE_COUNT_SIZE +
+    CONFIG_WATERMARKS_SIZE;
 
   return anchor.web3.SystemProgram.createAccount({
     fromPubkey: payerWallet,
@@ -14,17 +14,23 @@ import { findProgramAddress, StringPublicKey, toPublicKey } from '../utils';
 export const AUCTION_PREFIX = 'auction';
 export const METADATA = 'metadata';
 export const EXTENDED = 'extended';
-export const MAX_AUCTION_DATA_EXTENDED_SIZE = 8 + 9 + 2 + 9 + 33 + 158;
+export const MAX_AUCTION_DATA_EXTENDED_SIZE =
+  8 + 9 + 2 + 9 + 33 + 10 + 35 + 35 + 33 + 1 + 34 + 17 + 1 + 9 + 1 + 275;
+// Extended accounts created before the bidder gate and deposit were added are smaller, but the
+// fields read here sit at the same offsets in both.
+export const LEGACY_AUCTION_DATA_EXTENDED_SIZE = 8 + 9 + 2 + 9 + 33 + 158;
 
 export enum AuctionState {
   Created = 0,
   Started,
   Ended,
+  Cancelled,
 }
 
 export enum BidStateType {
   EnglishAuction = 0,
   OpenEdition = 1,
+  DutchAuction = 2,
 }
 
 export class Bid {
@@ -106,7 +112,12 @@ export const BidderPotParser: AccountParser = (
 });
 
 export const decodeBidderPot = (buffer: Buffer) => {
-  return deserializeUnchecked(AUCTION_SCHEMA, BidderPot, buffer) as BidderPot;
+  // Legacy pots lack the depositSettled flag.
+  const data =
+    buffer.length === LEGACY_BIDDER_POT_LEN
+      ? Buffer.concat([buffer, Buffer.alloc(BIDDER_POT_LEN - buffer.length)])
+      : buffer;
+  return deserializeUnchecked(AUCTION_SCHEMA, BidderPot, data) as BidderPot;
 };
 
 export const AuctionDataExtendedParser: AccountParser = (
@@ -224,7 +235,7 @@ export class AuctionData {
   auctionGap: BN | null;
   /// Minimum price for any bid to meet.
   priceFloor: PriceFloor;
-  /// The state the auction is in, whether it has started or ended.
+  /// The state the auction is in, whether it has started, ended or been cancelled.
   state: AuctionState;
   /// Auction Bids, each user may have one bid open at a time.
   bidState: BidState;
@@ -264,6 +275,8 @@ export class AuctionData {
   }
 
   public ended() {
+    if (this.state === AuctionState.Cancelled) return true;
+
     const now = moment().unix();
     if (!this.endedAt) return false;
 
@@ -329,23 +342,27 @@ export class BidderMetadata {
   }
 }
 
-export const BIDDER_POT_LEN = 32 + 32 + 32 + 1;
+export const BIDDER_POT_LEN = 32 + 32 + 32 + 1 + 1;
+export const LEGACY_BIDDER_POT_LEN = 32 + 32 + 32 + 1;
 export class BidderPot {
   /// Points at actual pot that is a token account
   bidderPot: StringPublicKey;
   bidderAct: StringPublicKey;
   auctionAct: StringPublicKey;
   emptied: boolean;
+  depositSettled: boolean;
   constructor(args: {
     bidderPot: StringPublicKey;
     bidderAct: StringPublicKey;
     auctionAct: StringPublicKey;
     emptied: boolean;
+    depositSettled: boolean;
   }) {
     this.bidderPot = args.bidderPot;
     this.bidderAct = args.bidderAct;
     this.auctionAct = args.auctionAct;
     this.emptied = args.emptied;
+    this.depositSettled = args.depositSettled;
   }
 }
 
@@ -622,6 +639,7 @@ export const AUCTION_SCHEMA = new Map<any, any>([
         ['bidderAct', 'pubkeyAsString'],
         ['auctionAct', 'pubkeyAsString'],
         ['emptied', 'u8'],
+        ['depositSettled', 'u8'],
       ],
     },
   ],
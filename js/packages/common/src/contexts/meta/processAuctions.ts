@@ -9,6 +9,8 @@ import {
   BidderPotParser,
   BIDDER_METADATA_LEN,
   BIDDER_POT_LEN,
+  LEGACY_AUCTION_DATA_EXTENDED_SIZE,
+  LEGACY_BIDDER_POT_LEN,
   MAX_AUCTION_DATA_EXTENDED_SIZE,
 } from '../../actions';
 import { AUCTION_ID, pubkeyToString } from '../../utils';
@@ -95,10 +97,12 @@ const isAuctionAccount: CheckAccountFunc = account =>
   account && pubkeyToString(account.owner) === AUCTION_ID;
 
 const isExtendedAuctionAccount: CheckAccountFunc = account =>
-  account.data.length === MAX_AUCTION_DATA_EXTENDED_SIZE;
+  account.data.length === MAX_AUCTION_DATA_EXTENDED_SIZE ||
+  account.data.length === LEGACY_AUCTION_DATA_EXTENDED_SIZE;
 
 const isBidderMetadataAccount: CheckAccountFunc = account =>
   account.data.length === BIDDER_METADATA_LEN;
 
 const isBidderPotAccount: CheckAccountFunc = account =>
-  account.data.length === BIDDER_POT_LEN;
+  account.data.length === BIDDER_POT_LEN ||
+  account.data.length === LEGACY_BIDDER_POT_LEN;
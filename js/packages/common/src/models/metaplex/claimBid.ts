@@ -12,6 +12,8 @@ export async function claimBid(
   vault: StringPublicKey,
   tokenMint: StringPublicKey,
   instructions: TransactionInstruction[],
+  // Needed when a Vickrey or uniform price winner is refunded part of their bid.
+  bidderToken?: StringPublicKey,
 ) {
   const PROGRAM_IDS = programIds();
   const store = PROGRAM_IDS.store;
@@ -104,6 +106,14 @@ export async function claimBid(
     },
   ];
 
+  if (bidderToken) {
+    keys.push({
+      pubkey: toPublicKey(bidderToken),
+      isSigner: false,
+      isWritable: true,
+    });
+  }
+
   instructions.push(
     new TransactionInstruction({
       keys,
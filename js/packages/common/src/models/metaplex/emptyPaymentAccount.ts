@@ -12,6 +12,7 @@ import {
   getSafetyDepositConfig,
   SCHEMA,
 } from '.';
+import { getAuctionExtended } from '../../actions';
 import { programIds, StringPublicKey, toPublicKey } from '../../utils';
 
 export async function emptyPaymentAccount(
@@ -43,6 +44,13 @@ export async function emptyPaymentAccount(
 
   const tokenTracker = await getAuctionWinnerTokenTypeTracker(auctionManager);
 
+  // Vickrey and uniform price winners are paid out at the price they were charged, which the
+  // extended settings decide.
+  const auctionExtended = await getAuctionExtended({
+    auctionProgramId: PROGRAM_IDS.auction,
+    resource: vault,
+  });
+
   const value = new EmptyPaymentAccountArgs({
     winningConfigIndex,
     winningConfigItemIndex,
@@ -143,6 +151,11 @@ export async function emptyPaymentAccount(
       isSigner: false,
       isWritable: false,
     },
+    {
+      pubkey: toPublicKey(auctionExtended),
+      isSigner: false,
+      isWritable: false,
+    },
   ];
 
   instructions.push(
@@ -41,7 +41,7 @@ export async function sendCancelBid(
 
   if (
     auctionView.auction.info.ended() &&
-    auctionView.auction.info.state !== AuctionState.Ended
+    auctionView.auction.info.state === AuctionState.Started
   ) {
     await setupPlaceBid(
       connection,
@@ -96,7 +96,7 @@ export async function sendRedeemBid(
 
   if (
     auctionView.auction.info.ended() &&
-    auctionView.auction.info.state !== AuctionState.Ended
+    auctionView.auction.info.state === AuctionState.Started
   ) {
     await setupPlaceBid(
       connection,
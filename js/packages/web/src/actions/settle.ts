@@ -35,7 +35,7 @@ export async function settle(
 ) {
   if (
     auctionView.auction.info.ended() &&
-    auctionView.auction.info.state !== AuctionState.Ended
+    auctionView.auction.info.state === AuctionState.Started
   ) {
     const signers: Keypair[][] = [];
     const instructions: TransactionInstruction[][] = [];
@@ -0,0 +1,28 @@
+use core::fmt;
+
+/// Reasons a bid is refused or a calculation can't be completed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MathError {
+    /// Bid is not a multiple of the tick size
+    BidMustBeMultipleOfTickSize,
+    /// Bid is below the minimum price
+    BidTooSmall,
+    /// Bid does not beat the bid below it by the gap tick size
+    GapBetweenBidsTooSmall,
+    /// Every lot of a Dutch auction has been sold
+    SoldOut,
+    /// Numerical overflow
+    NumericalOverflow,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            MathError::BidMustBeMultipleOfTickSize => "Bid must be multiple of tick size",
+            MathError::BidTooSmall => "Bid is too small",
+            MathError::GapBetweenBidsTooSmall => "Gap between bids is too small",
+            MathError::SoldOut => "Every lot has been sold",
+            MathError::NumericalOverflow => "Numerical overflow",
+        })
+    }
+}
@@ -0,0 +1,41 @@
+//! Pure auction math for the Metaplex Auction Program: bid ranking, gap and tick validation,
+//! instant sale endings, winner lookup and end-of-auction timing.
+//!
+//! The program applies these rules on chain, and anything that needs to predict what it will
+//! do, an indexer for instance, can run the very same code off chain. Nothing in here depends
+//! on Solana, and only `alloc` is needed from the standard library.
+//!
+//! Bids are kept in a vec sorted from lowest to highest, so the number one winner is always the
+//! last element. Winner indexes count down from there, winner 0 being the highest bid.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod error;
+pub mod ranking;
+pub mod timing;
+
+pub use error::MathError;
+pub use ranking::*;
+pub use timing::*;
+
+/// A bid as the ranking functions see it, so callers can keep bids in their own type.
+pub trait RankedBid {
+    type Key: PartialEq;
+
+    fn key(&self) -> &Self::Key;
+    fn amount(&self) -> u64;
+}
+
+impl<K: PartialEq> RankedBid for (K, u64) {
+    type Key = K;
+
+    fn key(&self) -> &K {
+        &self.0
+    }
+
+    fn amount(&self) -> u64 {
+        self.1
+    }
+}
@@ -0,0 +1,175 @@
+//! Ranking bids. Every function here takes the bid vec sorted from lowest to highest, along with
+//! `max`, the number of winning spots, which are always the last `max` bids.
+
+use {
+    crate::{MathError, RankedBid},
+    alloc::vec::Vec,
+    core::cmp,
+};
+
+/// How many bids an English auction keeps for `max` winners. The extra room holds bids bumped
+/// out of the winning spots, so they can move back up when a winner cancels.
+pub fn max_array_size_for(max: usize) -> usize {
+    if max < 8 {
+        8
+    } else {
+        2 * max
+    }
+}
+
+/// Bids must be a multiple of the tick size, when the auction has one.
+pub fn assert_valid_tick_size(amount: u64, tick_size: Option<u64>) -> Result<(), MathError> {
+    if let Some(tick) = tick_size {
+        if amount.checked_rem(tick) != Some(0) {
+            return Err(MathError::BidMustBeMultipleOfTickSize);
+        }
+    }
+    Ok(())
+}
+
+/// A bid must beat the one it is ranked above by at least `gap_tick` percent.
+pub fn assert_valid_gap_insertion(
+    gap_tick: u8,
+    beaten_amount: u64,
+    beating_amount: u64,
+) -> Result<(), MathError> {
+    // Use u128 to avoid potential overflow due to temporary mult of 100x since
+    // we haven't divided yet.
+    let minimum_bid_amount = (beaten_amount as u128)
+        .checked_mul(100 + gap_tick as u128)
+        .ok_or(MathError::NumericalOverflow)?
+        / 100;
+
+    if minimum_bid_amount > beating_amount as u128 {
+        return Err(MathError::GapBetweenBidsTooSmall);
+    }
+    Ok(())
+}
+
+/// Ranks a new English auction bid. Bids of the same amount rank below the ones placed before
+/// them. Returns the lowest bid when it had to be pruned to keep the vec at its maximum size.
+pub fn place_english_bid<B: RankedBid>(
+    bids: &mut Vec<B>,
+    bid: B,
+    max: usize,
+    tick_size: Option<u64>,
+    gap_tick_size_percentage: Option<u8>,
+    minimum: u64,
+) -> Result<Option<B>, MathError> {
+    assert_valid_tick_size(bid.amount(), tick_size)?;
+    if bid.amount() < minimum {
+        return Err(MathError::BidTooSmall);
+    }
+
+    if bids.is_empty() {
+        bids.push(bid);
+        return Ok(None);
+    }
+
+    for i in (0..bids.len()).rev() {
+        if bids[i].amount() < bid.amount() {
+            if let Some(gap_tick) = gap_tick_size_percentage {
+                assert_valid_gap_insertion(gap_tick, bids[i].amount(), bid.amount())?;
+            }
+            bids.insert(i + 1, bid);
+            break;
+        } else if bids[i].amount() == bid.amount() {
+            if let Some(gap_tick) = gap_tick_size_percentage {
+                if gap_tick > 0 {
+                    return Err(MathError::GapBetweenBidsTooSmall);
+                }
+            }
+            // Go below the last of the equal bids.
+            if i == 0 || bids[i - 1].amount() != bids[i].amount() {
+                bids.insert(i, bid);
+                break;
+            }
+        } else if i == 0 {
+            bids.insert(0, bid);
+            break;
+        }
+    }
+
+    if bids.len() > max_array_size_for(max) {
+        return Ok(Some(bids.remove(0)));
+    }
+    Ok(None)
+}
+
+/// Records a Dutch auction purchase. The price only falls, so each purchase ranks below the
+/// earlier ones. Returns whether that sold the last lot.
+pub fn place_dutch_purchase<B: RankedBid>(
+    bids: &mut Vec<B>,
+    bid: B,
+    max: usize,
+) -> Result<bool, MathError> {
+    if bids.len() >= max {
+        return Err(MathError::SoldOut);
+    }
+    bids.insert(0, bid);
+    Ok(bids.len() >= max)
+}
+
+/// Takes every bid of `key` out of the ranking.
+pub fn cancel_bid<B: RankedBid>(bids: &mut Vec<B>, key: &B::Key) {
+    bids.retain(|b| b.key() != key);
+}
+
+/// Amount of the bid ranked at `index`, winner 0 being the highest, or 0 past the end.
+pub fn amount_at<B: RankedBid>(bids: &[B], index: usize) -> u64 {
+    if index < bids.len() {
+        bids[bids.len() - index - 1].amount()
+    } else {
+        0
+    }
+}
+
+/// Winner index of `key`, if its bid is in a winning spot and at least `minimum`.
+pub fn winner_index<B: RankedBid>(
+    bids: &[B],
+    max: usize,
+    key: &B::Key,
+    minimum: u64,
+) -> Option<usize> {
+    let position = bids
+        .iter()
+        .position(|bid| bid.key() == key && bid.amount() >= minimum)?;
+    let index = bids.len() - position - 1;
+    if index < max {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+/// The bid holding winning spot `index`, winner 0 being the highest.
+pub fn winner_at<B: RankedBid>(bids: &[B], max: usize, index: usize) -> Option<&B> {
+    if index < max && index < bids.len() {
+        Some(&bids[bids.len() - index - 1])
+    } else {
+        None
+    }
+}
+
+pub fn num_winners(bids_len: usize, max: usize) -> usize {
+    cmp::min(bids_len, max)
+}
+
+/// Best bid that is still tracked but falls outside the winning spots.
+pub fn highest_losing_bid<B: RankedBid>(bids: &[B], max: usize) -> Option<u64> {
+    if bids.len() > max {
+        Some(bids[bids.len() - max - 1].amount())
+    } else {
+        None
+    }
+}
+
+/// Whether every winning spot is taken at the instant sale price, which ends the auction.
+pub fn sold_out_at_instant_sale_price<B: RankedBid>(
+    bids: &[B],
+    max: usize,
+    instant_sale_price: u64,
+) -> bool {
+    // bids.len() - max = index of the last winner bid
+    max > 0 && bids.len() >= max && bids[bids.len() - max].amount() >= instant_sale_price
+}
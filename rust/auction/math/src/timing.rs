@@ -0,0 +1,40 @@
+//! When an auction ends, and when the gap tick size applies.
+
+use crate::MathError;
+
+/// Seconds since the Unix epoch, as the Solana clock reports them.
+pub type UnixTimestamp = i64;
+
+/// Whether an auction is over at `now`. Without a gap the auction ends right after `ended_at`.
+/// With a gap, bids keep coming in past the end for as long as each lands within `gap` seconds of
+/// the one before it.
+pub fn ended(
+    ended_at: Option<UnixTimestamp>,
+    end_auction_gap: Option<UnixTimestamp>,
+    last_bid: Option<UnixTimestamp>,
+    now: UnixTimestamp,
+) -> Result<bool, MathError> {
+    match (ended_at, end_auction_gap) {
+        (Some(end), Some(gap)) => match last_bid {
+            Some(last) => {
+                let next_bid_time = last.checked_add(gap).ok_or(MathError::NumericalOverflow)?;
+                Ok(now > end && now > next_bid_time)
+            }
+            None => Ok(now > end),
+        },
+        (Some(end), None) => Ok(now > end),
+        _ => Ok(false),
+    }
+}
+
+/// The gap tick size only applies to bids placed in the gap window after the end.
+pub fn gap_tick_in_effect(
+    ended_at: Option<UnixTimestamp>,
+    now: UnixTimestamp,
+    gap_tick_size_percentage: Option<u8>,
+) -> Option<u8> {
+    match ended_at {
+        Some(end) if now > end => gap_tick_size_percentage,
+        _ => None,
+    }
+}
@@ -0,0 +1,190 @@
+use {
+    metaplex_auction_math::{
+        amount_at, cancel_bid, ended, highest_losing_bid, max_array_size_for, num_winners,
+        place_dutch_purchase, place_english_bid, winner_at, winner_index, MathError,
+    },
+    proptest::prelude::*,
+};
+
+type Bid = (u8, u64);
+
+/// Runs a sequence of bids through an English auction, skipping the ones that get refused.
+fn english_auction(
+    attempts: &[Bid],
+    max: usize,
+    tick_size: Option<u64>,
+    gap: Option<u8>,
+    minimum: u64,
+) -> Vec<Bid> {
+    let mut bids = vec![];
+    for bid in attempts {
+        let _ = place_english_bid(&mut bids, *bid, max, tick_size, gap, minimum);
+    }
+    bids
+}
+
+fn is_sorted(bids: &[Bid]) -> bool {
+    bids.windows(2).all(|pair| pair[0].1 <= pair[1].1)
+}
+
+proptest! {
+    #[test]
+    fn english_bids_stay_sorted_and_bounded(
+        attempts in prop::collection::vec((any::<u8>(), 0..10_000u64), 0..64),
+        max in 0..20usize,
+    ) {
+        let mut bids = vec![];
+        for bid in attempts {
+            let before = bids.len();
+            let pruned = place_english_bid(&mut bids, bid, max, None, None, 0).unwrap();
+            prop_assert!(is_sorted(&bids));
+            prop_assert!(bids.len() <= max_array_size_for(max));
+            // Pruning only ever drops the lowest bid.
+            if let Some(pruned) = pruned {
+                prop_assert_eq!(bids.len(), before);
+                prop_assert!(bids.iter().all(|b| b.1 >= pruned.1));
+            }
+        }
+    }
+
+    #[test]
+    fn equal_bids_rank_below_earlier_ones(
+        amount in 1..1_000u64,
+        count in 2..8usize,
+    ) {
+        let attempts: Vec<Bid> = (0..count as u8).map(|key| (key, amount)).collect();
+        let bids = english_auction(&attempts, 8, None, None, 0);
+        prop_assert_eq!(winner_at(&bids, 8, 0), Some(&(0, amount)));
+        prop_assert_eq!(winner_index(&bids, 8, &(count as u8 - 1), 0), Some(count - 1));
+    }
+
+    #[test]
+    fn refused_bids_leave_the_ranking_alone(
+        attempts in prop::collection::vec((any::<u8>(), 0..10_000u64), 1..32),
+        tick_size in 1..50u64,
+        gap in 0..50u8,
+        minimum in 0..500u64,
+    ) {
+        let mut bids = vec![];
+        for bid in attempts {
+            let before = bids.clone();
+            match place_english_bid(&mut bids, bid, 4, Some(tick_size), Some(gap), minimum) {
+                Ok(_) => {
+                    prop_assert_eq!(bid.1 % tick_size, 0);
+                    prop_assert!(bid.1 >= minimum);
+                }
+                Err(MathError::BidMustBeMultipleOfTickSize) => {
+                    prop_assert_ne!(bid.1 % tick_size, 0);
+                    prop_assert_eq!(&bids, &before);
+                }
+                Err(MathError::BidTooSmall) => {
+                    prop_assert!(bid.1 < minimum);
+                    prop_assert_eq!(&bids, &before);
+                }
+                Err(MathError::GapBetweenBidsTooSmall) => prop_assert_eq!(&bids, &before),
+                Err(e) => prop_assert!(false, "unexpected error {:?}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn accepted_bids_beat_the_bid_below_by_the_gap(
+        attempts in prop::collection::vec(1..10_000u64, 1..32),
+        gap in 1..50u8,
+    ) {
+        let mut bids: Vec<Bid> = vec![];
+        for (key, amount) in attempts.into_iter().enumerate() {
+            let bid = (key as u8, amount);
+            if place_english_bid(&mut bids, bid, 4, None, Some(gap), 0).is_err() {
+                continue;
+            }
+            // The new bid may have been pruned straight away.
+            if let Some(position) = bids.iter().position(|b| *b == bid) {
+                if position > 0 {
+                    let beaten = bids[position - 1].1 as u128;
+                    prop_assert!(beaten * (100 + gap as u128) / 100 <= bid.1 as u128);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn winner_lookups_agree(
+        amounts in prop::collection::vec(0..10_000u64, 0..64),
+        max in 0..12usize,
+    ) {
+        // The program holds one bid per bidder, so every key is unique.
+        let attempts: Vec<Bid> = amounts
+            .into_iter()
+            .enumerate()
+            .map(|(key, amount)| (key as u8, amount))
+            .collect();
+        let bids = english_auction(&attempts, max, None, None, 0);
+        let winners = num_winners(bids.len(), max);
+        prop_assert!(winners <= max);
+
+        for index in 0..winners {
+            let winner = winner_at(&bids, max, index).unwrap();
+            prop_assert_eq!(amount_at(&bids, index), winner.1);
+            prop_assert_eq!(winner_index(&bids, max, &winner.0, 0), Some(index));
+            if index > 0 {
+                prop_assert!(amount_at(&bids, index) <= amount_at(&bids, index - 1));
+            }
+        }
+        prop_assert_eq!(winner_at(&bids, max, winners), None);
+
+        match highest_losing_bid(&bids, max) {
+            Some(amount) => {
+                prop_assert!(bids.len() > max);
+                if winners > 0 {
+                    prop_assert!(amount <= amount_at(&bids, winners - 1));
+                }
+            }
+            None => prop_assert!(bids.len() <= max),
+        }
+    }
+
+    #[test]
+    fn cancelled_keys_leave_no_trace(
+        attempts in prop::collection::vec((0..8u8, 0..10_000u64), 0..64),
+        key in 0..8u8,
+    ) {
+        let mut bids = english_auction(&attempts, 4, None, None, 0);
+        cancel_bid(&mut bids, &key);
+        prop_assert!(is_sorted(&bids));
+        prop_assert!(bids.iter().all(|b| b.0 != key));
+        prop_assert_eq!(winner_index(&bids, 4, &key, 0), None);
+    }
+
+    #[test]
+    fn dutch_purchases_sell_out_exactly_once(max in 1..16usize) {
+        let mut bids: Vec<Bid> = vec![];
+        for i in 0..max {
+            let sold_out = place_dutch_purchase(&mut bids, (i as u8, 100), max).unwrap();
+            prop_assert_eq!(sold_out, i + 1 == max);
+        }
+        prop_assert_eq!(
+            place_dutch_purchase(&mut bids, (0, 100), max),
+            Err(MathError::SoldOut)
+        );
+        prop_assert_eq!(bids.len(), max);
+        // The first buyer keeps the top spot.
+        prop_assert_eq!(winner_index(&bids, max, &0, 0), Some(0));
+    }
+
+    #[test]
+    fn ended_never_reopens(
+        ended_at in prop::option::of(0..1_000i64),
+        gap in prop::option::of(0..100i64),
+        last_bid in prop::option::of(0..1_000i64),
+        now in 0..1_000i64,
+        later in 0..1_000i64,
+    ) {
+        if ended(ended_at, gap, last_bid, now).unwrap() {
+            prop_assert!(ended(ended_at, gap, last_bid, now + later).unwrap());
+        }
+        if ended_at.is_none() {
+            prop_assert!(!ended(ended_at, gap, last_bid, now).unwrap());
+        }
+    }
+}
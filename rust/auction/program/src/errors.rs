@@ -1,4 +1,5 @@
 use {
+    metaplex_auction_math::MathError,
     num_derive::FromPrimitive,
     solana_program::{
         decode_error::DecodeError,
@@ -134,6 +135,114 @@ pub enum AuctionError {
     /// Gap tick size percentage must be between 0 and 100
     #[error("Gap tick size percentage must be between 0 and 100")]
     InvalidGapTickSizePercentage,
+
+    /// Sealed-bid auctions need capped winners, a fixed end longer than the reveal period, and no gap or instant sale
+    #[error("Sealed-bid auctions need capped winners, a fixed end longer than the reveal period, and no gap or instant sale")]
+    InvalidSealedBidSettings,
+
+    /// Bids on a sealed-bid auction must be committed and revealed
+    #[error("Bids on a sealed-bid auction must be committed and revealed")]
+    BidsAreSealed,
+
+    /// This auction does not take sealed bids
+    #[error("This auction does not take sealed bids")]
+    NotSealedBidAuction,
+
+    /// The commit phase of this auction is over
+    #[error("The commit phase of this auction is over")]
+    CommitPhaseOver,
+
+    /// Bids can only be revealed during the reveal phase
+    #[error("Bids can only be revealed during the reveal phase")]
+    NotInRevealPhase,
+
+    /// This bid has already been revealed
+    #[error("This bid has already been revealed")]
+    BidAlreadyRevealed,
+
+    /// Revealed bid is larger than the deposit
+    #[error("Revealed bid is larger than the deposit")]
+    RevealExceedsDeposit,
+
+    /// Bids cannot be cancelled while sealed bids are being revealed
+    #[error("Bids cannot be cancelled while sealed bids are being revealed")]
+    CannotCancelDuringReveal,
+
+    /// Dutch auctions need capped winners, a fixed end, a falling price schedule, and no ticks, gap, instant sale or sealed bids
+    #[error("Dutch auctions need capped winners, a fixed end, a falling price schedule, and no ticks, gap, instant sale or sealed bids")]
+    InvalidDutchAuctionSettings,
+
+    /// Reserve prices need capped English bidding, a price, no instant sale, and an unblinded price floor if blinded themselves
+    #[error("Reserve prices need capped English bidding, a price, no instant sale, and an unblinded price floor if blinded themselves")]
+    InvalidReservePrice,
+
+    /// Anti-snipe extensions need a fixed end, no end gap, positive times, and cannot be used with sealed bids or Dutch auctions
+    #[error("Anti-snipe extensions need a fixed end, no end gap, positive times, and cannot be used with sealed bids or Dutch auctions")]
+    InvalidAntiSnipeSettings,
+
+    /// Uniform price auctions need capped English bidding with no instant sale or Vickrey settlement
+    #[error("Uniform price auctions need capped English bidding with no instant sale or Vickrey settlement")]
+    InvalidUniformPrice,
+
+    /// Refund account must be a token account of the bidder in the auction mint
+    #[error("Refund account must be a token account of the bidder in the auction mint")]
+    InvalidRefundAccount,
+
+    /// Bidder is not on the allowlist or does not hold the gating token
+    #[error("Bidder is not on the allowlist or does not hold the gating token")]
+    BidderNotAllowed,
+
+    /// Bid deposits need a positive amount and redeem period, and cannot be used with sealed bids or Dutch auctions
+    #[error("Bid deposits need a positive amount and redeem period, and cannot be used with sealed bids or Dutch auctions")]
+    InvalidBidDeposit,
+
+    /// This bid has already been claimed
+    #[error("This bid has already been claimed")]
+    BidAlreadyClaimed,
+
+    /// The deposit of this bid was already returned or forfeited
+    #[error("The deposit of this bid was already returned or forfeited")]
+    DepositAlreadySettled,
+
+    /// Bidder accounts can only be closed once the auction has ended and the bid is settled
+    #[error("Bidder accounts can only be closed once the auction has ended and the bid is settled")]
+    BidderAccountsInUse,
+
+    /// The bidder pot still holds tokens
+    #[error("The bidder pot still holds tokens")]
+    BidderPotNotEmpty,
+
+    /// Bid books need an English auction that has not started, no sealed bids, and a capacity of 1 to 250
+    #[error("Bid books need an English auction that has not started, no sealed bids, and a capacity of 1 to 250")]
+    InvalidBidBookSettings,
+
+    /// Bid book is missing or does not belong to this auction
+    #[error("Bid book is missing or does not belong to this auction")]
+    InvalidBidBook,
+
+    /// Scheduled start time must be positive
+    #[error("Scheduled start time must be positive")]
+    InvalidStartAt,
+
+    /// Auction cannot start before its scheduled start time
+    #[error("Auction cannot start before its scheduled start time")]
+    AuctionStartTooEarly,
+
+    /// Native SOL auctions must use the native mint and cannot be sealed
+    #[error("Native SOL auctions must use the native mint and cannot be sealed")]
+    InvalidNativeSolSettings,
+
+    /// Native SOL pots pay out to wallets, not token accounts
+    #[error("Native SOL pots pay out to wallets, not token accounts")]
+    InvalidNativeDestination,
+
+    /// Auction already has purchases that cannot be refunded, so it cannot be cancelled
+    #[error("Auction already has purchases that cannot be refunded, so it cannot be cancelled")]
+    CannotCancelAuctionWithPurchases,
+
+    /// Offers need an English auction that ended with no bids and has prizes left
+    #[error("Offers need an English auction that ended with no bids and has prizes left")]
+    OffersNotOpen,
 }
 
 impl PrintProgramError for AuctionError {
@@ -148,6 +257,18 @@ impl From<AuctionError> for ProgramError {
     }
 }
 
+impl From<MathError> for AuctionError {
+    fn from(e: MathError) -> Self {
+        match e {
+            MathError::BidMustBeMultipleOfTickSize => AuctionError::BidMustBeMultipleOfTickSize,
+            MathError::BidTooSmall => AuctionError::BidTooSmall,
+            MathError::GapBetweenBidsTooSmall => AuctionError::GapBetweenBidsTooSmall,
+            MathError::SoldOut => AuctionError::InvalidState,
+            MathError::NumericalOverflow => AuctionError::NumericalOverflowError,
+        }
+    }
+}
+
 impl<T> DecodeError<T> for AuctionError {
     fn type_of() -> &'static str {
         "Vault Error"
@@ -1,4 +1,4 @@
-use crate::{EXTENDED, PREFIX};
+use crate::{BID_BOOK, EXTENDED, PREFIX, SEALED};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -7,9 +7,13 @@ use solana_program::{
 };
 
 pub use crate::processor::{
-    cancel_bid::CancelBidArgs, claim_bid::ClaimBidArgs, create_auction::CreateAuctionArgs,
-    create_auction_v2::CreateAuctionArgsV2, end_auction::EndAuctionArgs, place_bid::PlaceBidArgs,
-    start_auction::StartAuctionArgs,
+    accept_offer::AcceptOfferArgs, cancel_auction::CancelAuctionArgs, cancel_bid::CancelBidArgs,
+    claim_bid::ClaimBidArgs, close_bidder_accounts::CloseBidderAccountsArgs,
+    commit_bid::CommitBidArgs, create_auction::CreateAuctionArgs,
+    create_auction_v2::CreateAuctionArgsV2, create_auction_v3::CreateAuctionArgsV3,
+    create_bid_book::CreateBidBookArgs, end_auction::EndAuctionArgs, place_bid::PlaceBidArgs,
+    place_bid_v2::PlaceBidArgsV2, place_offer::PlaceOfferArgs, return_deposit::ReturnDepositArgs,
+    reveal_bid::RevealBidArgs, start_auction::StartAuctionArgs,
 };
 
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
@@ -26,6 +30,7 @@ pub enum AuctionInstruction {
     ///   8. `[]` Rent sysvar
     ///   9. `[]` System program
     ///   10. `[]` SPL Token Program
+    ///   11. `[writable]` Bid book (pda of ['auction', program id, auction key, 'bidbook']), only needed when the auction has one
     CancelBid(CancelBidArgs),
 
     /// Create a new auction account bound to a resource, initially in a pending state.
@@ -47,19 +52,25 @@ pub enum AuctionInstruction {
     ///   7. `[]` Clock sysvar
     ///   8. `[]` Token program
     ///   9. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   10. `[writable]` The bidder's token account, refunded whatever the winner bid over their price
+    ///           Only needed when the winner pays less than they bid, the claim fails without it then.
     ClaimBid(ClaimBidArgs),
 
     /// Ends an auction, regardless of end timing conditions
     ///
     ///   0. `[writable, signer]` Auction authority
     ///   1. `[writable]` Auction
-    ///   6. `[]` Clock sysvar
+    ///   2. `[]` Clock sysvar
+    ///   3. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///           Optional, but the reserve price is only checked when it is passed.
     EndAuction(EndAuctionArgs),
 
     /// Start an inactive auction.
     ///   0. `[signer]` The creator/authorised account.
     ///   1. `[writable]` Initialized auction account.
     ///   2. `[]` Clock sysvar
+    ///   3. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///           Optional, but the scheduled start time is only checked when it is passed.
     StartAuction(StartAuctionArgs),
 
     /// Update the authority for an auction account.
@@ -75,13 +86,17 @@ pub enum AuctionInstruction {
     ///   3. `[writable]` The pot SPL account, where the tokens will be deposited.
     ///   4. `[writable]` The metadata account, storing information about the bidders actions.
     ///   5. `[writable]` Auction account, containing data about the auction and item being bid on.
-    ///   6. `[writable]` Token mint, for transfer instructions and verification.
-    ///   7. `[signer]` Transfer authority, for moving tokens into the bid pot.
-    ///   8. `[signer]` Payer
-    ///   9. `[]` Clock sysvar
-    ///   10. `[]` Rent sysvar
-    ///   11. `[]` System program
-    ///   12. `[]` SPL Token Program
+    ///   6. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   7. `[writable]` Token mint, for transfer instructions and verification.
+    ///   8. `[signer]` Transfer authority, for moving tokens into the bid pot.
+    ///   9. `[signer]` Payer
+    ///   10. `[]` Clock sysvar
+    ///   11. `[]` Rent sysvar
+    ///   12. `[]` System program
+    ///   13. `[]` SPL Token Program
+    ///   14. `[]` The bidders token account of the gating mint, only needed when bidding is gated on holding it
+    ///   15. `[writable]` Bid book (pda of ['auction', program id, auction key, 'bidbook']), only needed when the auction has one.
+    ///       Pass the program id for account 14 when the auction has a bid book but no gate.
     PlaceBid(PlaceBidArgs),
 
     /// Create a new auction account bound to a resource, initially in a pending state.
@@ -92,6 +107,104 @@ pub enum AuctionInstruction {
     ///   3. `[]` Rent sysvar
     ///   4. `[]` System account
     CreateAuctionV2(CreateAuctionArgsV2),
+
+    /// Create a new auction account bound to a resource, initially in a pending state.
+    /// Same as CreateAuctionV2, with optional sealed-bid settings in CreateAuctionArgsV3
+    ///   0. `[signer]` The account creating the auction, which is authorised to make changes.
+    ///   1. `[writable]` Uninitialized auction account.
+    ///   2. `[writable]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///   3. `[]` Rent sysvar
+    ///   4. `[]` System account
+    CreateAuctionV3(CreateAuctionArgsV3),
+
+    /// Commit a hidden bid on a running sealed-bid auction, depositing up to the amount bid.
+    ///   0. `[signer]` The bidders primary account, for PDA calculation/transit auth.
+    ///   1. `[writable]` The bidders token account they'll pay the deposit with
+    ///   2. `[writable]` The pot, containing a reference to the stored SPL token account.
+    ///   3. `[writable]` The pot SPL account, where the tokens will be deposited.
+    ///   4. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   5. `[writable]` The bid commitment (pda of ['auction', program id, auction key, bidder key, 'sealed'])
+    ///   6. `[]` Auction account, containing data about the auction and item being bid on.
+    ///   7. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   8. `[]` Token mint, for transfer instructions and verification.
+    ///   9. `[signer]` Transfer authority, for moving tokens into the bid pot.
+    ///   10. `[signer]` Payer
+    ///   11. `[]` Clock sysvar
+    ///   12. `[]` Rent sysvar
+    ///   13. `[]` System program
+    ///   14. `[]` SPL Token Program
+    ///   15. `[]` The bidders token account of the gating mint, only needed when bidding is gated on holding it
+    CommitBid(CommitBidArgs),
+
+    /// Reveal a committed bid during the reveal window, placing it and refunding the rest of the deposit.
+    ///   0. `[signer]` The bidders primary account, for PDA calculation/transit auth.
+    ///   1. `[writable]` The bidders token account the excess deposit is returned to
+    ///   2. `[]` The pot, containing a reference to the stored SPL token account.
+    ///   3. `[writable]` The pot SPL account, holding the deposit.
+    ///   4. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   5. `[writable]` The bid commitment (pda of ['auction', program id, auction key, bidder key, 'sealed'])
+    ///   6. `[writable]` Auction account, containing data about the auction and item being bid on.
+    ///   7. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   8. `[]` Token mint, for transfer instructions and verification.
+    ///   9. `[]` Clock sysvar
+    ///   10. `[]` SPL Token Program
+    RevealBid(RevealBidArgs),
+
+    /// Place a bid on a running auction, with a Merkle proof for auctions gated on an allowlist.
+    /// Takes the same accounts as PlaceBid.
+    PlaceBidV2(PlaceBidArgsV2),
+
+    /// Return the deposit left in the pot of a winner whose bid was claimed. Can only happen once,
+    /// and not after the deposit was forfeited with ClaimBid.
+    ///   0. `[writable]` The bidder's token account the deposit is returned to (their wallet for native SOL auctions)
+    ///   1. `[writable]` The bidder pot token account
+    ///   2. `[writable]` The bidder pot pda account [seed of ['auction', program_id, auction key, bidder key]]
+    ///   3. `[signer]` The authority on the auction
+    ///   4. `[]` The auction
+    ///   5. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   6. `[]` The bidder wallet
+    ///   7. `[]` Token program
+    ReturnDeposit(ReturnDepositArgs),
+
+    /// Close the metadata, pot and pot SPL account of a settled bid, returning their rent to the bidder.
+    /// The auction must have ended, the bid must be claimed or cancelled, and the pot SPL account empty.
+    ///   0. `[signer, writable]` The bidders primary account, receiving the rent.
+    ///   1. `[writable]` The pot, containing a reference to the stored SPL token account.
+    ///   2. `[writable]` The pot SPL account.
+    ///   3. `[writable]` The metadata account, storing information about the bidders actions.
+    ///   4. `[]` Auction account, containing data about the auction and item being bid on.
+    ///   5. `[]` Clock sysvar
+    ///   6. `[]` SPL Token Program
+    CloseBidderAccounts(CloseBidderAccountsArgs),
+
+    /// Create the bid book of an English auction that has not started, keeping bids pruned from the bid state.
+    ///   0. `[signer]` The auction authority.
+    ///   1. `[signer]` Payer
+    ///   2. `[]` Auction account.
+    ///   3. `[writable]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   4. `[writable]` Uninitialized bid book (pda of ['auction', program id, auction key, 'bidbook'])
+    ///   5. `[]` Rent sysvar
+    ///   6. `[]` System program
+    CreateBidBook(CreateBidBookArgs),
+
+    /// Cancel an auction that has not ended yet and has no Dutch or instant sale purchases.
+    /// Nobody wins, so every bid becomes cancellable.
+    ///   0. `[signer]` The auction authority.
+    ///   1. `[writable]` Auction account.
+    ///   2. `[]` Clock sysvar
+    ///   3. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    CancelAuction(CancelAuctionArgs),
+
+    /// Make an offer on an English auction that ended without a bid, escrowed in the bidder pot.
+    /// Takes the same accounts as PlaceBid, with the gate token account as an optional account 14.
+    PlaceOffer(PlaceOfferArgs),
+
+    /// Accept an offer, making it the lowest winning bid of the auction.
+    ///   0. `[signer]` The auction authority.
+    ///   1. `[writable]` Auction account.
+    ///   2. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   3. `[]` The metadata of the offer (pda of ['auction', program id, auction key, bidder key, 'metadata'])
+    AcceptOffer(AcceptOfferArgs),
 }
 
 /// Creates an CreateAuction instruction.
@@ -166,6 +279,42 @@ pub fn create_auction_instruction_v2(
     }
 }
 
+/// Creates an CreateAuctionV3 instruction.
+pub fn create_auction_instruction_v3(
+    program_id: Pubkey,
+    creator_pubkey: Pubkey,
+    args: CreateAuctionArgsV3,
+) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: AuctionInstruction::CreateAuctionV3(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 /// Creates an SetAuthority instruction.
 pub fn set_authority_instruction(
     program_id: Pubkey,
@@ -190,6 +339,7 @@ pub fn set_authority_instruction(
 pub fn start_auction_instruction(
     program_id: Pubkey,
     authority_pubkey: Pubkey,
+    auction_extended_pubkey: Option<Pubkey>,
     args: StartAuctionArgs,
 ) -> Instruction {
     // Derive Auction Key
@@ -200,13 +350,18 @@ pub fn start_auction_instruction(
     ];
     let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
 
+    let mut accounts = vec![
+        AccountMeta::new(authority_pubkey, true),
+        AccountMeta::new(auction_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    if let Some(auction_extended) = auction_extended_pubkey {
+        accounts.push(AccountMeta::new_readonly(auction_extended, false));
+    }
+
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(authority_pubkey, true),
-            AccountMeta::new(auction_pubkey, false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-        ],
+        accounts,
         data: AuctionInstruction::StartAuction(args).try_to_vec().unwrap(),
     }
 }
@@ -279,6 +434,53 @@ pub fn place_bid_instruction(
     }
 }
 
+/// Creates an PlaceBidV2 instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn place_bid_instruction_v2(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    transfer_authority: Pubkey,
+    payer: Pubkey,
+    gate_token_pubkey: Option<Pubkey>,
+    bid_book: bool,
+    args: PlaceBidArgsV2,
+) -> Instruction {
+    let mut instruction = place_bid_instruction(
+        program_id,
+        bidder_pubkey,
+        bidder_token_pubkey,
+        bidder_pot_token_pubkey,
+        token_mint_pubkey,
+        transfer_authority,
+        payer,
+        PlaceBidArgs {
+            amount: args.amount,
+            resource: args.resource,
+        },
+    );
+    if let Some(gate_token) = gate_token_pubkey {
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(gate_token, false));
+    } else if bid_book {
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(program_id, false));
+    }
+    if bid_book {
+        let auction_pubkey = instruction.accounts[5].pubkey;
+        instruction.accounts.push(AccountMeta::new(
+            bid_book_pubkey(program_id, auction_pubkey),
+            false,
+        ));
+    }
+    instruction.data = AuctionInstruction::PlaceBidV2(args).try_to_vec().unwrap();
+    instruction
+}
+
 /// Creates an CancelBidinstruction.
 pub fn cancel_bid_instruction(
     program_id: Pubkey,
@@ -286,6 +488,7 @@ pub fn cancel_bid_instruction(
     bidder_token_pubkey: Pubkey,
     bidder_pot_token_pubkey: Pubkey,
     token_mint_pubkey: Pubkey,
+    bid_book: bool,
     args: CancelBidArgs,
 ) -> Instruction {
     // Derive Auction Key
@@ -323,22 +526,30 @@ pub fn cancel_bid_instruction(
     ];
     let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
 
+    let mut accounts = vec![
+        AccountMeta::new(bidder_pubkey, true),
+        AccountMeta::new(bidder_token_pubkey, false),
+        AccountMeta::new(bidder_pot_pubkey, false),
+        AccountMeta::new(bidder_pot_token_pubkey, false),
+        AccountMeta::new(bidder_meta_pubkey, false),
+        AccountMeta::new(auction_pubkey, false),
+        AccountMeta::new(auction_extended_pubkey, false),
+        AccountMeta::new(token_mint_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    if bid_book {
+        accounts.push(AccountMeta::new(
+            bid_book_pubkey(program_id, auction_pubkey),
+            false,
+        ));
+    }
+
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(bidder_pubkey, true),
-            AccountMeta::new(bidder_token_pubkey, false),
-            AccountMeta::new(bidder_pot_pubkey, false),
-            AccountMeta::new(bidder_pot_token_pubkey, false),
-            AccountMeta::new(bidder_meta_pubkey, false),
-            AccountMeta::new(auction_pubkey, false),
-            AccountMeta::new(auction_extended_pubkey, false),
-            AccountMeta::new(token_mint_pubkey, false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: AuctionInstruction::CancelBid(args).try_to_vec().unwrap(),
     }
 }
@@ -356,12 +567,22 @@ pub fn end_auction_instruction(
     ];
     let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
 
+    // Derive Auction Extended Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
     Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(authority_pubkey, true),
             AccountMeta::new(auction_pubkey, false),
             AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new(auction_extended_pubkey, false),
         ],
         data: AuctionInstruction::EndAuction(args).try_to_vec().unwrap(),
     }
@@ -374,7 +595,8 @@ pub fn claim_bid_instruction(
     bidder_pubkey: Pubkey,
     bidder_pot_token_pubkey: Pubkey,
     token_mint_pubkey: Pubkey,
-    auction_extended_pubkey: Option<Pubkey>,
+    auction_extended_pubkey: Pubkey,
+    bidder_token_pubkey: Option<Pubkey>,
     args: ClaimBidArgs,
 ) -> Instruction {
     // Derive Auction Key
@@ -403,11 +625,12 @@ pub fn claim_bid_instruction(
         AccountMeta::new_readonly(bidder_pubkey, false),
         AccountMeta::new_readonly(token_mint_pubkey, false),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
-        AccountMeta::new_readonly(spl_token::id(), false),        
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(auction_extended_pubkey, false),
     ];
 
-    if let Some(auction_extended) = auction_extended_pubkey {
-        accounts.push(AccountMeta::new_readonly(auction_extended, false));
+    if let Some(bidder_token) = bidder_token_pubkey {
+        accounts.push(AccountMeta::new(bidder_token, false));
     }
 
     Instruction {
@@ -416,3 +639,449 @@ pub fn claim_bid_instruction(
         data: AuctionInstruction::ClaimBid(args).try_to_vec().unwrap(),
     }
 }
+
+/// Creates an CommitBid instruction.
+pub fn commit_bid_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    transfer_authority: Pubkey,
+    payer: Pubkey,
+    gate_token_pubkey: Option<Pubkey>,
+    args: CommitBidArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Meta
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bid Commitment
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        SEALED.as_bytes(),
+    ];
+    let (bid_commitment_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(bidder_pubkey, true),
+        AccountMeta::new(bidder_token_pubkey, false),
+        AccountMeta::new(bidder_pot_pubkey, false),
+        AccountMeta::new(bidder_pot_token_pubkey, false),
+        AccountMeta::new(bidder_meta_pubkey, false),
+        AccountMeta::new(bid_commitment_pubkey, false),
+        AccountMeta::new_readonly(auction_pubkey, false),
+        AccountMeta::new(auction_extended_pubkey, false),
+        AccountMeta::new_readonly(token_mint_pubkey, false),
+        AccountMeta::new_readonly(transfer_authority, true),
+        AccountMeta::new_readonly(payer, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    if let Some(gate_token) = gate_token_pubkey {
+        accounts.push(AccountMeta::new_readonly(gate_token, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: AuctionInstruction::CommitBid(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an RevealBid instruction.
+pub fn reveal_bid_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    args: RevealBidArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Meta
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bid Commitment
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        SEALED.as_bytes(),
+    ];
+    let (bid_commitment_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(bidder_pubkey, true),
+            AccountMeta::new(bidder_token_pubkey, false),
+            AccountMeta::new_readonly(bidder_pot_pubkey, false),
+            AccountMeta::new(bidder_pot_token_pubkey, false),
+            AccountMeta::new(bidder_meta_pubkey, false),
+            AccountMeta::new(bid_commitment_pubkey, false),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(token_mint_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: AuctionInstruction::RevealBid(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an ReturnDeposit instruction.
+pub fn return_deposit_instruction(
+    program_id: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    authority_pubkey: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    args: ReturnDepositArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Auction Extended Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(bidder_token_pubkey, false),
+            AccountMeta::new(bidder_pot_token_pubkey, false),
+            AccountMeta::new(bidder_pot_pubkey, false),
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new_readonly(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(bidder_pubkey, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: AuctionInstruction::ReturnDeposit(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an CloseBidderAccounts instruction.
+pub fn close_bidder_accounts_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    args: CloseBidderAccountsArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Pot
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    let (bidder_pot_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Meta
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(bidder_pubkey, true),
+            AccountMeta::new(bidder_pot_pubkey, false),
+            AccountMeta::new(bidder_pot_token_pubkey, false),
+            AccountMeta::new(bidder_meta_pubkey, false),
+            AccountMeta::new_readonly(auction_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: AuctionInstruction::CloseBidderAccounts(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Derives a bidder's pot. Native SOL auctions pass it as the pot token account as well.
+pub fn bidder_pot_pubkey(
+    program_id: Pubkey,
+    auction_pubkey: Pubkey,
+    bidder_pubkey: Pubkey,
+) -> Pubkey {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        bidder_pubkey.as_ref(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+/// Derives the bid book of an auction.
+pub fn bid_book_pubkey(program_id: Pubkey, auction_pubkey: Pubkey) -> Pubkey {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        BID_BOOK.as_bytes(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+/// Creates an CreateBidBook instruction.
+pub fn create_bid_book_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    payer_pubkey: Pubkey,
+    args: CreateBidBookArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new_readonly(payer_pubkey, true),
+            AccountMeta::new_readonly(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new(bid_book_pubkey(program_id, auction_pubkey), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: AuctionInstruction::CreateBidBook(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an CancelAuction instruction.
+pub fn cancel_auction_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: CancelAuctionArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Auction Extended Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+        ],
+        data: AuctionInstruction::CancelAuction(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an PlaceOffer instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn place_offer_instruction(
+    program_id: Pubkey,
+    bidder_pubkey: Pubkey,
+    bidder_token_pubkey: Pubkey,
+    bidder_pot_token_pubkey: Pubkey,
+    token_mint_pubkey: Pubkey,
+    transfer_authority: Pubkey,
+    payer: Pubkey,
+    gate_token_pubkey: Option<Pubkey>,
+    args: PlaceOfferArgs,
+) -> Instruction {
+    let mut instruction = place_bid_instruction(
+        program_id,
+        bidder_pubkey,
+        bidder_token_pubkey,
+        bidder_pot_token_pubkey,
+        token_mint_pubkey,
+        transfer_authority,
+        payer,
+        PlaceBidArgs {
+            amount: args.amount,
+            resource: args.resource,
+        },
+    );
+    if let Some(gate_token) = gate_token_pubkey {
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(gate_token, false));
+    }
+    instruction.data = AuctionInstruction::PlaceOffer(args).try_to_vec().unwrap();
+    instruction
+}
+
+/// Creates an AcceptOffer instruction.
+pub fn accept_offer_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: AcceptOfferArgs,
+) -> Instruction {
+    // Derive Auction Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Auction Extended Key
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Derive Bidder Meta
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        auction_pubkey.as_ref(),
+        args.bidder.as_ref(),
+        "metadata".as_bytes(),
+    ];
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(authority_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(bidder_meta_pubkey, false),
+        ],
+        data: AuctionInstruction::AcceptOffer(args).try_to_vec().unwrap(),
+    }
+}
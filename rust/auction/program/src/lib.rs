@@ -11,4 +11,11 @@ pub mod processor;
 pub const PREFIX: &str = "auction";
 
 pub const EXTENDED: &str = "extended";
+
+/// Seed for the PDA holding a bidder's commitment in a sealed-bid auction.
+pub const SEALED: &str = "sealed";
+
+/// Seed for the PDA holding the bids pruned from an English auction.
+pub const BID_BOOK: &str = "bidbook";
+
 solana_program::declare_id!("auctxRXPeJoc4817jDhf4HbjnhEcr1cCXenosMhK5R8");
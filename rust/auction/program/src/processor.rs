@@ -1,29 +1,62 @@
-use crate::errors::AuctionError;
+use crate::{
+    errors::AuctionError,
+    utils::{assert_initialized, assert_owned_by, verify_merkle_proof},
+};
 use arrayref::array_ref;
 use borsh::{BorshDeserialize, BorshSerialize};
+use metaplex_auction_math::{self as math, RankedBid};
 use solana_program::{
-    account_info::AccountInfo, borsh::try_from_slice_unchecked, clock::UnixTimestamp,
-    entrypoint::ProgramResult, hash::Hash, msg, program_error::ProgramError, pubkey::Pubkey,
+    account_info::AccountInfo,
+    borsh::try_from_slice_unchecked,
+    clock::UnixTimestamp,
+    entrypoint::ProgramResult,
+    hash::{self, Hash},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
 };
+use spl_token::state::Account;
 use std::{cell::Ref, cmp, mem};
 
 // Declare submodules, each contains a single handler for each instruction variant in the program.
+pub mod accept_offer;
+pub mod cancel_auction;
 pub mod cancel_bid;
 pub mod claim_bid;
+pub mod close_bidder_accounts;
+pub mod commit_bid;
 pub mod create_auction;
 pub mod create_auction_v2;
+pub mod create_auction_v3;
+pub mod create_bid_book;
 pub mod end_auction;
 pub mod place_bid;
+pub mod place_bid_v2;
+pub mod place_offer;
+pub mod return_deposit;
+pub mod reveal_bid;
 pub mod set_authority;
 pub mod start_auction;
 
 // Re-export submodules handlers + associated types for other programs to consume.
+pub use accept_offer::*;
+pub use cancel_auction::*;
 pub use cancel_bid::*;
 pub use claim_bid::*;
+pub use close_bidder_accounts::*;
+pub use commit_bid::*;
 pub use create_auction::*;
 pub use create_auction_v2::*;
+pub use create_auction_v3::*;
+pub use create_bid_book::*;
 pub use end_auction::*;
 pub use place_bid::*;
+pub use place_bid_v2::*;
+pub use place_offer::*;
+pub use return_deposit::*;
+pub use reveal_bid::*;
 pub use set_authority::*;
 pub use start_auction::*;
 
@@ -36,14 +69,29 @@ pub fn process_instruction(
     match AuctionInstruction::try_from_slice(input)? {
         AuctionInstruction::CancelBid(args) => cancel_bid(program_id, accounts, args),
         AuctionInstruction::ClaimBid(args) => claim_bid(program_id, accounts, args),
-        AuctionInstruction::CreateAuction(args) => {
-            create_auction(program_id, accounts, args, None, None)
-        }
+        AuctionInstruction::CreateAuction(args) => create_auction(
+            program_id,
+            accounts,
+            args,
+            ExtendedAuctionSettings::default(),
+        ),
         AuctionInstruction::CreateAuctionV2(args) => create_auction_v2(program_id, accounts, args),
+        AuctionInstruction::CreateAuctionV3(args) => create_auction_v3(program_id, accounts, args),
+        AuctionInstruction::CommitBid(args) => commit_bid(program_id, accounts, args),
+        AuctionInstruction::RevealBid(args) => reveal_bid(program_id, accounts, args),
         AuctionInstruction::EndAuction(args) => end_auction(program_id, accounts, args),
-        AuctionInstruction::PlaceBid(args) => place_bid(program_id, accounts, args),
+        AuctionInstruction::PlaceBid(args) => place_bid(program_id, accounts, args, &[]),
+        AuctionInstruction::PlaceBidV2(args) => place_bid_v2(program_id, accounts, args),
         AuctionInstruction::SetAuthority => set_authority(program_id, accounts),
         AuctionInstruction::StartAuction(args) => start_auction(program_id, accounts, args),
+        AuctionInstruction::ReturnDeposit(args) => return_deposit(program_id, accounts, args),
+        AuctionInstruction::CreateBidBook(args) => create_bid_book(program_id, accounts, args),
+        AuctionInstruction::CloseBidderAccounts(args) => {
+            close_bidder_accounts(program_id, accounts, args)
+        }
+        AuctionInstruction::CancelAuction(args) => cancel_auction(program_id, accounts, args),
+        AuctionInstruction::PlaceOffer(args) => place_offer(program_id, accounts, args),
+        AuctionInstruction::AcceptOffer(args) => accept_offer(program_id, accounts, args),
     }
 }
 
@@ -98,7 +146,11 @@ pub struct AuctionData {
 // Alias for auction name.
 pub type AuctionName = [u8; 32];
 
-pub const MAX_AUCTION_DATA_EXTENDED_SIZE: usize = 8 + 9 + 2 + 9 + 33 + 158;
+pub const MAX_AUCTION_DATA_EXTENDED_SIZE: usize =
+    8 + 9 + 2 + 9 + 33 + 10 + 35 + 35 + 33 + 1 + 34 + 17 + 1 + 9 + 1 + 275;
+/// Size of extended accounts created before the bidder gate and deposit were added. These are
+/// still read, with every field past the end treated as unset.
+pub const LEGACY_AUCTION_DATA_EXTENDED_SIZE: usize = 219;
 // Further storage for more fields. Would like to store more on the main data but due
 // to a borsh issue that causes more added fields to inflict "Access violation" errors
 // during redemption in main Metaplex app for no reason, we had to add this nasty PDA.
@@ -116,15 +168,223 @@ pub struct AuctionDataExtended {
     pub instant_sale_price: Option<u64>,
     /// Auction name
     pub name: Option<AuctionName>,
+    /// Present when bids are committed as hashes and revealed after bidding closes
+    pub sealed_bid: Option<SealedBidSettings>,
+    /// Price schedule for auctions running as a DutchAuction
+    pub dutch_auction: Option<DutchAuctionSettings>,
+    /// Price the top bid must reach at the end for anything to sell
+    pub reserve_price: Option<ReservePrice>,
+    /// Pushes the end back when bids arrive just before it
+    pub anti_snipe: Option<AntiSnipeSettings>,
+    /// Every winner pays the clearing price, the lowest winning bid
+    pub uniform_price: bool,
+    /// Restricts who may bid
+    pub bidder_gate: Option<BidderGate>,
+    /// Deposit every bidder pays on top of their first bid
+    pub bid_deposit: Option<BidDeposit>,
+    /// Bids pushed out of the bid state are kept in the auction's BidBook
+    pub bid_book: bool,
+    /// Earliest time the auction may start
+    pub start_at: Option<UnixTimestamp>,
+    /// Bids are paid in lamports held by the BidderPot itself rather than wrapped SOL
+    pub native_sol: bool,
 }
 
-impl AuctionDataExtended {
-    pub fn from_account_info(a: &AccountInfo) -> Result<AuctionDataExtended, ProgramError> {
-        if a.data_len() != MAX_AUCTION_DATA_EXTENDED_SIZE {
-            return Err(AuctionError::DataTypeMismatch.into());
+/// Sealed-bid auctions split the time before `ended_at` in two. Bidders commit a hash of their
+/// bid alongside a deposit until `reveal_period` seconds before the end, then reveal it in the
+/// remaining time. Only revealed bids enter the bid state.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct SealedBidSettings {
+    /// Length of the reveal window at the end of the auction, in seconds.
+    pub reveal_period: UnixTimestamp,
+    /// Winners pay the highest losing bid (or the price floor) instead of their own bid.
+    pub vickrey: bool,
+}
+
+/// How the price of a Dutch auction falls at each step.
+/// Both variants carry a u64 so borsh-js can read them, see PriceFloor.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum DutchDecay {
+    /// Price drops by a fixed amount.
+    Linear(u64),
+    /// Price drops by a share of the current price, in basis points.
+    Exponential(u64),
+}
+
+/// Dutch auctions start at `start_price` and fall every `step_interval` seconds, never going
+/// under `floor_price`. Every bid buys one of the lots at the current price.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct DutchAuctionSettings {
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub decay: DutchDecay,
+    pub step_interval: UnixTimestamp,
+}
+
+impl DutchAuctionSettings {
+    pub fn is_valid(&self) -> bool {
+        let decays = match self.decay {
+            DutchDecay::Linear(amount) => amount > 0,
+            DutchDecay::Exponential(basis_points) => basis_points > 0 && basis_points <= 10000,
+        };
+        decays && self.start_price > self.floor_price && self.step_interval > 0
+    }
+
+    /// Price after `elapsed` seconds of the auction running.
+    pub fn price_at(&self, elapsed: UnixTimestamp) -> Result<u64, ProgramError> {
+        let steps = (cmp::max(elapsed, 0) / self.step_interval) as u64;
+        let price = match self.decay {
+            DutchDecay::Linear(amount) => self
+                .start_price
+                .saturating_sub(amount.saturating_mul(steps)),
+            DutchDecay::Exponential(basis_points) => {
+                // Raise the per-step factor to the number of steps by squaring, in 1e12 fixed point.
+                const ONE: u128 = 1_000_000_000_000;
+                let mut factor = (10000 - basis_points as u128) * (ONE / 10000);
+                let mut result = ONE;
+                let mut remaining = steps;
+                while remaining > 0 && result > 0 {
+                    if remaining & 1 == 1 {
+                        result = result * factor / ONE;
+                    }
+                    factor = factor * factor / ONE;
+                    remaining >>= 1;
+                }
+                (self.start_price as u128 * result / ONE) as u64
+            }
+        };
+        Ok(cmp::max(price, self.floor_price))
+    }
+}
+
+/// Unlike the price floor, a reserve does not stop lower bids coming in. If the top bid is still
+/// under it when the auction ends, the auction fails: nobody wins and every bid can be cancelled.
+/// The price may be blinded the same way as a PriceFloor and is revealed in end_auction.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct ReservePrice {
+    /// Either a MinimumPrice or a BlindedPrice.
+    pub price: PriceFloor,
+    /// Set by end_auction when the top bid fell short of the reserve.
+    pub failed: bool,
+}
+
+impl ReservePrice {
+    pub fn is_valid(&self) -> bool {
+        match self.price {
+            PriceFloor::MinimumPrice(min) => min[0] > 0,
+            PriceFloor::BlindedPrice(_) => true,
+            PriceFloor::None(_) => false,
+        }
+    }
+}
+
+/// A bid placed `window` seconds or less before the end moves the end to `extension` seconds
+/// after the bid, up to `max_extension` seconds past the original end in total. The new end is
+/// written to `AuctionData::ended_at`, so it is always the real end of the auction.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct AntiSnipeSettings {
+    pub window: UnixTimestamp,
+    pub extension: UnixTimestamp,
+    pub max_extension: UnixTimestamp,
+    /// How far the end has been pushed back so far, starts at zero.
+    pub extended_by: UnixTimestamp,
+}
+
+impl AntiSnipeSettings {
+    pub fn is_valid(&self) -> bool {
+        self.window > 0 && self.extension > 0 && self.max_extension > 0 && self.extended_by == 0
+    }
+
+    /// Returns the end of the auction after a bid at `now`, recording any extension.
+    pub fn extend(
+        &mut self,
+        end: UnixTimestamp,
+        now: UnixTimestamp,
+    ) -> Result<UnixTimestamp, ProgramError> {
+        if end.saturating_sub(now) > self.window {
+            return Ok(end);
+        }
+        let wanted = now
+            .checked_add(self.extension)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        let remaining = self.max_extension.saturating_sub(self.extended_by);
+        let new_end = cmp::min(wanted, end.saturating_add(remaining));
+        if new_end <= end {
+            return Ok(end);
+        }
+        self.extended_by += new_end - end;
+        Ok(new_end)
+    }
+}
+
+/// Who may bid on an auction. Both variants carry 32 bytes so borsh-js can read them.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub enum BidderGate {
+    /// Merkle root of the allowed bidder wallets. Leaves are the hash of the wallet key, and pairs
+    /// are hashed smallest first.
+    Allowlist([u8; 32]),
+    /// Only holders of a token of this mint may bid.
+    TokenHolder(Pubkey),
+}
+
+impl BidderGate {
+    /// Checks the bidder against the gate, using the Merkle proof for an allowlist or their token
+    /// account for a holder gate.
+    pub fn check(
+        &self,
+        bidder: &Pubkey,
+        proof: &[[u8; 32]],
+        token_account: Option<&AccountInfo>,
+    ) -> ProgramResult {
+        let allowed = match self {
+            BidderGate::Allowlist(root) => {
+                let leaf = hash::hashv(&[bidder.as_ref()]).to_bytes();
+                verify_merkle_proof(leaf, proof, root)
+            }
+            BidderGate::TokenHolder(mint) => match token_account {
+                Some(token_account) => {
+                    assert_owned_by(token_account, &spl_token::id())?;
+                    let account: Account = assert_initialized(token_account)?;
+                    account.mint == *mint && account.owner == *bidder && account.amount > 0
+                }
+                None => false,
+            },
+        };
+        if !allowed {
+            return Err(AuctionError::BidderNotAllowed.into());
         }
+        Ok(())
+    }
+}
 
-        let auction_extended: AuctionDataExtended = try_from_slice_unchecked(&a.data.borrow_mut())?;
+/// Each bidder pays `amount` into their pot with their first bid. Losing bidders get it back when
+/// they cancel. Winners get it back through ReturnDeposit once their bid is claimed and they have
+/// redeemed, but if that hasn't happened `redeem_period` seconds after the auction ends, the
+/// authority may claim it instead. Either way it is paid out once.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct BidDeposit {
+    pub amount: u64,
+    pub redeem_period: UnixTimestamp,
+}
+
+impl AuctionDataExtended {
+    pub fn from_account_info(a: &AccountInfo) -> Result<AuctionDataExtended, ProgramError> {
+        let auction_extended: AuctionDataExtended = match a.data_len() {
+            MAX_AUCTION_DATA_EXTENDED_SIZE => try_from_slice_unchecked(&a.data.borrow_mut())?,
+            LEGACY_AUCTION_DATA_EXTENDED_SIZE => {
+                let mut data = a.data.borrow().to_vec();
+                data.resize(MAX_AUCTION_DATA_EXTENDED_SIZE, 0);
+                try_from_slice_unchecked(&data)?
+            }
+            _ => return Err(AuctionError::DataTypeMismatch.into()),
+        };
 
         Ok(auction_extended)
     }
@@ -178,6 +438,7 @@ impl AuctionData {
             0 => Ok(AuctionState::Created),
             1 => Ok(AuctionState::Started),
             2 => Ok(AuctionState::Ended),
+            3 => Ok(AuctionState::Cancelled),
             _ => Err(ProgramError::InvalidAccountData),
         }
     }
@@ -296,6 +557,14 @@ impl AuctionData {
         }
     }
 
+    /// Lowest winning bid, which every winner pays in a uniform price auction.
+    pub fn get_clearing_price(a: &AccountInfo) -> Option<u64> {
+        match AuctionData::get_num_winners(a) {
+            0 => None,
+            n => AuctionData::get_winner_bid_amount_at(a, n - 1),
+        }
+    }
+
     fn get_winner_bid_amount_at_inner<'a>(
         data: &'a Ref<'a, &'a mut [u8]>,
         idx: usize,
@@ -324,30 +593,14 @@ impl AuctionData {
     }
 
     pub fn ended(&self, now: UnixTimestamp) -> Result<bool, ProgramError> {
-        // If there is an end time specified, handle conditions.
-        return match (self.ended_at, self.end_auction_gap) {
-            // NOTE if changing this, change in auction.ts on front end as well where logic duplicates.
-            // Both end and gap present, means a bid can still be placed post-auction if it is
-            // within the gap time.
-            (Some(end), Some(gap)) => {
-                // Check if the bid is within the gap between the last bidder.
-                if let Some(last) = self.last_bid {
-                    let next_bid_time = last
-                        .checked_add(gap)
-                        .ok_or(AuctionError::NumericalOverflowError)?;
-
-                    Ok(now > end && now > next_bid_time)
-                } else {
-                    Ok(now > end)
-                }
-            }
-
-            // Simply whether now has passed the end.
-            (Some(end), None) => Ok(now > end),
+        // A cancelled auction is over as soon as it is cancelled.
+        if self.state == AuctionState::Cancelled {
+            return Ok(true);
+        }
 
-            // No other end conditions.
-            _ => Ok(false),
-        };
+        // NOTE if changing this, change in auction.ts on front end as well where logic duplicates.
+        math::ended(self.ended_at, self.end_auction_gap, self.last_bid, now)
+            .map_err(|e| AuctionError::from(e).into())
     }
 
     pub fn is_winner(&self, key: &Pubkey) -> Option<usize> {
@@ -358,6 +611,72 @@ impl AuctionData {
         self.bid_state.is_winner(key, minimum)
     }
 
+    /// Current price of a Dutch auction. The schedule runs from the start of the auction, which
+    /// is recovered from the end time since Dutch auctions always have one.
+    pub fn dutch_price(
+        &self,
+        dutch_auction: &DutchAuctionSettings,
+        now: UnixTimestamp,
+    ) -> Result<u64, ProgramError> {
+        let started_at = match (self.ended_at, self.end_auction_at) {
+            (Some(end), Some(duration)) => end
+                .checked_sub(duration)
+                .ok_or(AuctionError::NumericalOverflowError)?,
+            _ => return Err(AuctionError::InvalidDutchAuctionSettings.into()),
+        };
+        let elapsed = now
+            .checked_sub(started_at)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+        dutch_auction.price_at(elapsed)
+    }
+
+    /// Last moment a sealed bid can be committed, after which the reveal window opens.
+    pub fn commit_deadline(
+        &self,
+        sealed_bid: &SealedBidSettings,
+    ) -> Result<UnixTimestamp, ProgramError> {
+        match self.ended_at {
+            Some(end) => end
+                .checked_sub(sealed_bid.reveal_period)
+                .ok_or(AuctionError::NumericalOverflowError.into()),
+            None => Err(AuctionError::InvalidSealedBidSettings.into()),
+        }
+    }
+
+    /// What a winner of a Vickrey auction pays: the highest losing bid, or the price floor when
+    /// every revealed bid won, but never more than the winner's own bid.
+    pub fn vickrey_price(&self, index: usize) -> u64 {
+        let minimum = match self.price_floor {
+            PriceFloor::MinimumPrice(min) => min[0],
+            _ => 0,
+        };
+        let price = match self.bid_state.highest_losing_bid() {
+            Some(amount) => cmp::max(amount, minimum),
+            None => minimum,
+        };
+        cmp::min(price, self.bid_state.amount(index))
+    }
+
+    /// Lowest winning bid, which every winner pays in a uniform price auction.
+    pub fn clearing_price(&self) -> u64 {
+        match self.num_winners() {
+            0 => 0,
+            n => self.bid_state.amount(n as usize - 1),
+        }
+    }
+
+    /// What the winner at `index` is charged when their bid is claimed, when it isn't their own
+    /// bid. Vickrey winners only pay the second price, uniform price winners the clearing price.
+    pub fn settlement_price(&self, extended: &AuctionDataExtended, index: usize) -> Option<u64> {
+        if extended.uniform_price {
+            return Some(self.clearing_price());
+        }
+        match &extended.sealed_bid {
+            Some(sealed_bid) if sealed_bid.vickrey => Some(self.vickrey_price(index)),
+            _ => None,
+        }
+    }
+
     pub fn num_winners(&self) -> u64 {
         self.bid_state.num_winners()
     }
@@ -370,6 +689,21 @@ impl AuctionData {
         self.bid_state.winner_at(idx)
     }
 
+    /// Offers can be made on, and accepted for, an English auction that has ended without a single
+    /// bid, for as long as winning spots are left.
+    pub fn assert_open_for_offers(&self, extended: &AuctionDataExtended) -> ProgramResult {
+        if self.state != AuctionState::Ended
+            || extended.total_uncancelled_bids > 0
+            || self.num_winners() >= self.num_possible_winners()
+        {
+            return Err(AuctionError::OffersNotOpen.into());
+        }
+        match self.bid_state {
+            BidState::EnglishAuction { .. } => Ok(()),
+            _ => Err(AuctionError::OffersNotOpen.into()),
+        }
+    }
+
     pub fn consider_instant_bid(&mut self, instant_sale_price: Option<u64>) {
         // Check if all the lots were sold with instant_sale_price
         if let Some(price) = instant_sale_price {
@@ -390,25 +724,16 @@ impl AuctionData {
         gap_tick_size_percentage: Option<u8>,
         now: UnixTimestamp,
         instant_sale_price: Option<u64>,
-    ) -> Result<(), ProgramError> {
-        let gap_val = match self.ended_at {
-            Some(end) => {
-                // We use the actual gap tick size perc if we're in gap window,
-                // otherwise we pass in none so the logic isnt used
-                if now > end {
-                    gap_tick_size_percentage
-                } else {
-                    None
-                }
-            }
-            None => None,
-        };
+    ) -> Result<Option<Bid>, ProgramError> {
+        // We use the actual gap tick size perc if we're in gap window,
+        // otherwise we pass in none so the logic isnt used
+        let gap_val = math::gap_tick_in_effect(self.ended_at, now, gap_tick_size_percentage);
         let minimum = match self.price_floor {
             PriceFloor::MinimumPrice(min) => min[0],
             _ => 0,
         };
 
-        self.bid_state.place_bid(
+        let pruned = self.bid_state.place_bid(
             bid,
             tick_size,
             gap_val,
@@ -419,7 +744,7 @@ impl AuctionData {
 
         self.consider_instant_bid(instant_sale_price);
 
-        Ok(())
+        Ok(pruned)
     }
 }
 
@@ -430,6 +755,8 @@ pub enum AuctionState {
     Created,
     Started,
     Ended,
+    /// Stopped by the authority before it ended, nobody wins.
+    Cancelled,
 }
 
 impl AuctionState {
@@ -453,6 +780,15 @@ impl AuctionState {
             _ => Err(AuctionError::AuctionTransitionInvalid.into()),
         }
     }
+
+    #[inline(always)]
+    pub fn cancel(self) -> Result<Self, ProgramError> {
+        match self {
+            AuctionState::Started => Ok(AuctionState::Cancelled),
+            AuctionState::Created => Ok(AuctionState::Cancelled),
+            _ => Err(AuctionError::AuctionTransitionInvalid.into()),
+        }
+    }
 }
 
 /// Bids associate a bidding key with an amount bid.
@@ -460,6 +796,18 @@ impl AuctionState {
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
 pub struct Bid(pub Pubkey, pub u64);
 
+impl RankedBid for Bid {
+    type Key = Pubkey;
+
+    fn key(&self) -> &Pubkey {
+        &self.0
+    }
+
+    fn amount(&self) -> u64 {
+        self.1
+    }
+}
+
 /// BidState tracks the running state of an auction, each variant represents a different kind of
 /// auction being run.
 #[repr(C)]
@@ -467,6 +815,7 @@ pub struct Bid(pub Pubkey, pub u64);
 pub enum BidState {
     EnglishAuction { bids: Vec<Bid>, max: usize },
     OpenEdition { bids: Vec<Bid>, max: usize },
+    DutchAuction { bids: Vec<Bid>, max: usize },
 }
 
 /// Bidding Implementations.
@@ -476,6 +825,9 @@ pub enum BidState {
 ///
 /// Open Edition: All bids are accepted, cancellations return money to the bidder and always
 /// succeed.
+///
+/// Dutch Auction: bids are purchases at the current price, so every bid wins until all lots are
+/// taken and the auction ends. Purchases cannot be cancelled.
 impl BidState {
     pub fn new_english(n: usize) -> Self {
         BidState::EnglishAuction {
@@ -484,6 +836,13 @@ impl BidState {
         }
     }
 
+    pub fn new_dutch(n: usize) -> Self {
+        BidState::DutchAuction {
+            bids: vec![],
+            max: n,
+        }
+    }
+
     pub fn new_open_edition() -> Self {
         BidState::OpenEdition {
             bids: vec![],
@@ -492,56 +851,12 @@ impl BidState {
     }
 
     pub fn max_array_size_for(n: usize) -> usize {
-        let mut real_max = n;
-        if real_max < 8 {
-            real_max = 8;
-        } else {
-            real_max = 2 * real_max
-        }
-        real_max
-    }
-
-    fn assert_valid_tick_size_bid(bid: &Bid, tick_size: Option<u64>) -> ProgramResult {
-        if let Some(tick) = tick_size {
-            if bid.1.checked_rem(tick) != Some(0) {
-                msg!(
-                    "This bid {:?} is not a multiple of tick size {:?}, throw it out.",
-                    bid.1,
-                    tick_size
-                );
-                return Err(AuctionError::BidMustBeMultipleOfTickSize.into());
-            }
-        } else {
-            msg!("No tick size on this auction")
-        }
-
-        Ok(())
-    }
-
-    fn assert_valid_gap_insertion(
-        gap_tick: u8,
-        beaten_bid: &Bid,
-        beating_bid: &Bid,
-    ) -> ProgramResult {
-        // Use u128 to avoid potential overflow due to temporary mult of 100x since
-        // we haven't divided yet.
-        let mut minimum_bid_amount: u128 = (beaten_bid.1 as u128)
-            .checked_mul((100 + gap_tick) as u128)
-            .ok_or(AuctionError::NumericalOverflowError)?;
-        minimum_bid_amount = minimum_bid_amount
-            .checked_div(100u128)
-            .ok_or(AuctionError::NumericalOverflowError)?;
-
-        if minimum_bid_amount > beating_bid.1 as u128 {
-            msg!("Rejecting inserting this bid due to gap tick size of {:?} which causes min bid of {:?} from {:?} which is the bid it is trying to beat", gap_tick, minimum_bid_amount.to_string(), beaten_bid.1);
-            return Err(AuctionError::GapBetweenBidsTooSmall.into());
-        }
-
-        Ok(())
+        math::max_array_size_for(n)
     }
 
     /// Push a new bid into the state, this succeeds only if the bid is larger than the current top
-    /// winner stored. Crappy list information to start with.
+    /// winner stored. Crappy list information to start with. Returns the bid that was pruned to
+    /// make room, if any.
     pub fn place_bid(
         &mut self,
         bid: Bid,
@@ -550,81 +865,37 @@ impl BidState {
         minimum: u64,
         instant_sale_price: Option<u64>,
         auction_state: &mut AuctionState,
-    ) -> Result<(), ProgramError> {
+    ) -> Result<Option<Bid>, ProgramError> {
         msg!("Placing bid {:?}", &bid.1.to_string());
-        BidState::assert_valid_tick_size_bid(&bid, tick_size)?;
+        math::assert_valid_tick_size(bid.1, tick_size).map_err(AuctionError::from)?;
         if bid.1 < minimum {
             return Err(AuctionError::BidTooSmall.into());
         }
 
         match self {
             // In a capped auction, track the limited number of winners.
-            BidState::EnglishAuction { ref mut bids, max } => {
-                match bids.last() {
-                    Some(top) => {
-                        msg!("Looking to go over the loop, but check tick size first");
-
-                        for i in (0..bids.len()).rev() {
-                            msg!("Comparison of {:?} and {:?} for {:?}", bids[i].1, bid.1, i);
-                            if bids[i].1 < bid.1 {
-                                if let Some(gap_tick) = gap_tick_size_percentage {
-                                    BidState::assert_valid_gap_insertion(gap_tick, &bids[i], &bid)?
-                                }
-
-                                msg!("Ok we can do an insert");
-                                if i + 1 < bids.len() {
-                                    msg!("Doing a normal insert");
-                                    bids.insert(i + 1, bid);
-                                } else {
-                                    msg!("Doing an on the end insert");
-                                    bids.push(bid)
-                                }
-                                break;
-                            } else if bids[i].1 == bid.1 {
-                                if let Some(gap_tick) = gap_tick_size_percentage {
-                                    if gap_tick > 0 {
-                                        msg!("Rejecting same-bid insert due to gap tick size of {:?}", gap_tick);
-                                        return Err(AuctionError::GapBetweenBidsTooSmall.into());
-                                    }
-                                }
-
-                                msg!("Ok we can do an equivalent insert");
-                                if i == 0 {
-                                    msg!("Doing a normal insert");
-                                    bids.insert(0, bid);
-                                    break;
-                                } else {
-                                    if bids[i - 1].1 != bids[i].1 {
-                                        msg!("Doing an insert just before");
-                                        bids.insert(i, bid);
-                                        break;
-                                    }
-                                    msg!("More duplicates ahead...")
-                                }
-                            } else if i == 0 {
-                                msg!("Inserting at 0");
-                                bids.insert(0, bid);
-                                break;
-                            }
-                        }
-
-                        let max_size = BidState::max_array_size_for(*max);
-
-                        if bids.len() > max_size {
-                            bids.remove(0);
-                        }
-                        Ok(())
-                    }
-                    _ => {
-                        msg!("Pushing bid onto stack");
-                        bids.push(bid);
-                        Ok(())
-                    }
+            BidState::EnglishAuction { ref mut bids, max } => math::place_english_bid(
+                bids,
+                bid,
+                *max,
+                tick_size,
+                gap_tick_size_percentage,
+                minimum,
+            )
+            .map_err(|e| AuctionError::from(e).into()),
+
+            // In a Dutch auction the price only falls, so each purchase ranks below the earlier
+            // ones. The processor has already settled the bid at the current price.
+            BidState::DutchAuction { ref mut bids, max } => {
+                if math::place_dutch_purchase(bids, bid, *max).map_err(AuctionError::from)? {
+                    msg!("All the lots were sold, auction is ended");
+                    *auction_state = AuctionState::Ended;
                 }
+                Ok(None)
             }
 
             // In an open auction, bidding simply succeeds.
-            BidState::OpenEdition { bids, max } => Ok(()),
+            BidState::OpenEdition { bids, max } => Ok(None),
         }
     }
 
@@ -633,24 +904,65 @@ impl BidState {
     pub fn cancel_bid(&mut self, key: Pubkey) -> Result<(), ProgramError> {
         match self {
             BidState::EnglishAuction { ref mut bids, max } => {
-                bids.retain(|b| b.0 != key);
+                math::cancel_bid(bids, &key);
                 Ok(())
             }
 
             // In an open auction, cancelling simply succeeds. It's up to the manager of an auction
             // to decide what to do with open edition bids.
             BidState::OpenEdition { bids, max } => Ok(()),
+
+            // Dutch auction purchases are final, the processor refuses to cancel them.
+            BidState::DutchAuction { bids, max } => Ok(()),
         }
     }
 
-    pub fn amount(&self, index: usize) -> u64 {
+    /// Refills an English auction's bids from its bid book after a cancellation made room, so
+    /// the best pruned bids move back up and can still win.
+    pub fn backfill(&mut self, bid_book: &mut BidBook) {
+        if let BidState::EnglishAuction { bids, max } = self {
+            while bids.len() < BidState::max_array_size_for(*max) {
+                match bid_book.bids.pop() {
+                    Some(bid) => bids.insert(0, bid),
+                    None => break,
+                }
+            }
+        }
+    }
+
+    /// Leaves every bid in place but stops any of them from winning, used when an auction fails
+    /// to meet its reserve.
+    pub fn clear_winners(&mut self) {
+        match self {
+            BidState::EnglishAuction { bids, max } | BidState::DutchAuction { bids, max } => {
+                *max = 0
+            }
+            BidState::OpenEdition { bids, max } => {}
+        }
+    }
+
+    /// Makes an accepted offer the lowest winner. Earlier winners keep their places, so prizes
+    /// already handed out by winning index stay with the right bidder.
+    pub fn accept_offer(&mut self, bid: Bid) -> ProgramResult {
         match self {
             BidState::EnglishAuction { bids, max } => {
-                if index >= 0 as usize && index < bids.len() {
-                    return bids[bids.len() - index - 1].1;
-                } else {
-                    return 0;
+                if bids.len() >= *max {
+                    return Err(AuctionError::OffersNotOpen.into());
+                }
+                if bids.iter().any(|existing| existing.0 == bid.0) {
+                    return Err(AuctionError::BidAlreadyActive.into());
                 }
+                bids.insert(0, bid);
+                Ok(())
+            }
+            _ => Err(AuctionError::OffersNotOpen.into()),
+        }
+    }
+
+    pub fn amount(&self, index: usize) -> u64 {
+        match self {
+            BidState::EnglishAuction { bids, max } | BidState::DutchAuction { bids, max } => {
+                math::amount_at(bids, index)
             }
             BidState::OpenEdition { bids, max } => 0,
         }
@@ -662,18 +974,8 @@ impl BidState {
 
         match self {
             // Presense in the winner list is enough to check win state.
-            BidState::EnglishAuction { bids, max } => {
-                match bids.iter().position(|bid| &bid.0 == key && bid.1 >= min) {
-                    Some(val) => {
-                        let zero_based_index = bids.len() - val - 1;
-                        if zero_based_index < *max {
-                            Some(zero_based_index)
-                        } else {
-                            None
-                        }
-                    }
-                    None => None,
-                }
+            BidState::EnglishAuction { bids, max } | BidState::DutchAuction { bids, max } => {
+                math::winner_index(bids, *max, key, min)
             }
             // There are no winners in an open edition, it is up to the auction manager to decide
             // what to do with open edition bids.
@@ -683,14 +985,18 @@ impl BidState {
 
     pub fn num_winners(&self) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => cmp::min(bids.len(), *max) as u64,
+            BidState::EnglishAuction { bids, max } | BidState::DutchAuction { bids, max } => {
+                math::num_winners(bids.len(), *max) as u64
+            }
             BidState::OpenEdition { bids, max } => 0,
         }
     }
 
     pub fn num_possible_winners(&self) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => *max as u64,
+            BidState::EnglishAuction { bids, max } | BidState::DutchAuction { bids, max } => {
+                *max as u64
+            }
             BidState::OpenEdition { bids, max } => 0,
         }
     }
@@ -698,24 +1004,26 @@ impl BidState {
     /// Idea is to present #1 winner as index 0 to outside world with this method
     pub fn winner_at(&self, index: usize) -> Option<Pubkey> {
         match self {
-            BidState::EnglishAuction { bids, max } => {
-                if index < *max && index < bids.len() {
-                    let bid = &bids[bids.len() - index - 1];
-                    Some(bids[bids.len() - index - 1].0)
-                } else {
-                    None
-                }
+            BidState::EnglishAuction { bids, max } | BidState::DutchAuction { bids, max } => {
+                math::winner_at(bids, *max, index).map(|bid| bid.0)
             }
             BidState::OpenEdition { bids, max } => None,
         }
     }
 
+    /// Best bid that is still tracked but falls outside the winning positions.
+    pub fn highest_losing_bid(&self) -> Option<u64> {
+        match self {
+            BidState::EnglishAuction { bids, max } => math::highest_losing_bid(bids, *max),
+            _ => None,
+        }
+    }
+
     pub fn lowest_winning_bid_is_instant_bid_price(&self, instant_sale_amount: u64) -> bool {
         match self {
             // In a capped auction, track the limited number of winners.
             BidState::EnglishAuction { bids, max } => {
-                // bids.len() - max = index of the last winner bid
-                bids.len() >= *max && bids[bids.len() - *max].1 >= instant_sale_amount
+                math::sold_out_at_instant_sale_price(bids, *max, instant_sale_amount)
             }
             _ => false,
         }
@@ -760,6 +1068,97 @@ impl BidderMetadata {
     }
 }
 
+pub const BID_COMMITMENT_LEN: usize = 32 + 32 + 32 + 1;
+/// Hash a bidder commits to in a sealed-bid auction, stored in a PDA next to their metadata.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct BidCommitment {
+    /// Bidder the commitment belongs to.
+    pub bidder_pubkey: Pubkey,
+    /// Auction the commitment was made on.
+    pub auction_pubkey: Pubkey,
+    /// hashv of the bidder key, then the amount and salt as big-endian u64s.
+    pub commitment: Hash,
+    /// Whether the committed bid has been revealed and placed.
+    pub revealed: bool,
+}
+
+impl BidCommitment {
+    pub fn from_account_info(a: &AccountInfo) -> Result<BidCommitment, ProgramError> {
+        if a.data_len() != BID_COMMITMENT_LEN {
+            return Err(AuctionError::DataTypeMismatch.into());
+        }
+
+        let commitment: BidCommitment = try_from_slice_unchecked(&a.data.borrow_mut())?;
+
+        Ok(commitment)
+    }
+}
+
+/// Size of a BidBook holding up to `capacity` bids.
+pub fn bid_book_len(capacity: usize) -> usize {
+    32 + 8 + 4 + capacity * mem::size_of::<Bid>()
+}
+
+/// Largest BidBook that can be allocated from inside the program.
+pub const MAX_BID_BOOK_CAPACITY: usize = 250;
+
+/// Overflow for an English auction's bids, stored in a PDA of ['auction', program id, auction
+/// key, 'bidbook']. Bids pruned from the bid state land here, lowest first, and come back when
+/// cancellations make room. Like the bid state it only holds bids whose BidderMetadata is not
+/// cancelled: place_bid and cancel_bid keep both in step.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+pub struct BidBook {
+    /// Auction the book belongs to.
+    pub auction: Pubkey,
+    /// Most bids the book holds, once full the lowest bid is dropped.
+    pub capacity: u64,
+    pub bids: Vec<Bid>,
+}
+
+impl BidBook {
+    pub fn from_account_info(a: &AccountInfo) -> Result<BidBook, ProgramError> {
+        let bid_book: BidBook = try_from_slice_unchecked(&a.data.borrow_mut())?;
+        if a.data_len() != bid_book_len(bid_book.capacity as usize) {
+            return Err(AuctionError::DataTypeMismatch.into());
+        }
+
+        Ok(bid_book)
+    }
+
+    /// Loads the book of an auction that uses one, from the optional account passed for it.
+    pub fn for_auction(
+        a: Option<&AccountInfo>,
+        program_id: &Pubkey,
+        auction: &Pubkey,
+    ) -> Result<BidBook, ProgramError> {
+        let a = a.ok_or(AuctionError::InvalidBidBook)?;
+        assert_owned_by(a, program_id)?;
+        let bid_book = BidBook::from_account_info(a)?;
+        if bid_book.auction != *auction {
+            return Err(AuctionError::InvalidBidBook.into());
+        }
+
+        Ok(bid_book)
+    }
+
+    /// Adds a bid pruned from the bid state. It ranks below bids of the same amount already in
+    /// the book, just as it did in the bid state.
+    pub fn insert(&mut self, bid: Bid) {
+        let position = self.bids.iter().position(|b| b.1 >= bid.1);
+        self.bids.insert(position.unwrap_or(self.bids.len()), bid);
+        if self.bids.len() as u64 > self.capacity {
+            msg!("Bid book is full, dropping its lowest bid");
+            self.bids.remove(0);
+        }
+    }
+
+    pub fn remove(&mut self, key: &Pubkey) {
+        self.bids.retain(|b| b.0 != *key);
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
 pub struct BidderPot {
@@ -771,16 +1170,41 @@ pub struct BidderPot {
     pub auction_act: Pubkey,
     /// emptied or not
     pub emptied: bool,
+    /// The winner's deposit was returned or forfeited
+    pub deposit_settled: bool,
 }
 
+pub const BIDDER_POT_LEN: usize = 32 + 32 + 32 + 1 + 1;
+// Pots created before deposits were settled lack the last flag, their auctions take no deposit.
+pub const LEGACY_BIDDER_POT_LEN: usize = 32 + 32 + 32 + 1;
+
 impl BidderPot {
     pub fn from_account_info(a: &AccountInfo) -> Result<BidderPot, ProgramError> {
-        if a.data_len() != mem::size_of::<BidderPot>() {
-            return Err(AuctionError::DataTypeMismatch.into());
-        }
-
-        let bidder_pot: BidderPot = try_from_slice_unchecked(&a.data.borrow_mut())?;
+        let bidder_pot: BidderPot = match a.data_len() {
+            BIDDER_POT_LEN => try_from_slice_unchecked(&a.data.borrow_mut())?,
+            LEGACY_BIDDER_POT_LEN => {
+                let mut data = a.data.borrow().to_vec();
+                data.resize(BIDDER_POT_LEN, 0);
+                try_from_slice_unchecked(&data)?
+            }
+            _ => return Err(AuctionError::DataTypeMismatch.into()),
+        };
 
         Ok(bidder_pot)
     }
+
+    /// Writes the pot back, leaving out the fields a legacy pot has no room for.
+    pub fn save(&self, a: &AccountInfo) -> ProgramResult {
+        let data = self.try_to_vec()?;
+        let len = a.data_len();
+        a.data.borrow_mut().copy_from_slice(&data[..len]);
+        Ok(())
+    }
+
+    /// Lamports bid into a native SOL pot, everything above what keeps the pot rent exempt.
+    pub fn native_balance(a: &AccountInfo) -> Result<u64, ProgramError> {
+        let rent = Rent::get()?;
+        Ok(a.lamports()
+            .saturating_sub(rent.minimum_balance(a.data_len())))
+    }
 }
@@ -0,0 +1,124 @@
+//! Cancels an auction that has not ended yet, which only its authority can do. Nobody wins a
+//! cancelled auction, so every bid can be cancelled and refunded, including those that were
+//! winning at the time. Auctions holding Dutch or instant sale purchases cannot be cancelled, as
+//! those purchases may already have been redeemed.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, BidState},
+    utils::{assert_derivation, assert_owned_by, assert_signer},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CancelAuctionArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_signer(accounts.authority)?;
+    Ok(accounts)
+}
+
+pub fn cancel_auction<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: CancelAuctionArgs,
+) -> ProgramResult {
+    msg!("+ Processing CancelAuction");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &args.resource.as_ref(),
+        ],
+    )?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Check authority is correct.
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    // An auction past its end already has winners, it has to be ended instead.
+    if auction.ended(clock.unix_timestamp)? {
+        return Err(AuctionError::AuctionTransitionInvalid.into());
+    }
+
+    // Dutch purchases and instant sales may already have been redeemed, and cancel_bid refuses to
+    // refund them, so they must not lose their winning spot.
+    for index in 0..auction.bid_state.num_winners() as usize {
+        let purchased = match auction.bid_state {
+            BidState::DutchAuction { .. } => true,
+            _ => auction_extended
+                .instant_sale_price
+                .map_or(false, |price| auction.bid_state.amount(index) >= price),
+        };
+        if purchased {
+            return Err(AuctionError::CannotCancelAuctionWithPurchases.into());
+        }
+    }
+
+    auction.bid_state.clear_winners();
+
+    AuctionData {
+        ended_at: Some(clock.unix_timestamp),
+        state: auction.state.cancel()?,
+        ..auction
+    }
+    .serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -3,19 +3,28 @@
 //! 1) The auction is still going on, in which case it is possible to cancel a bid at any time.
 //! 2) The auction has finished, but the bid did not win. This allows users to claim back their
 //!    funds from bid accounts.
+//!
+//! Cancelling a bid in an auction with a bid book promotes the best bid in the book into its
+//! place.
+//!
+//! Winners can also use it to empty their pot once their bid was claimed. A deposit in it is only
+//! returned through ReturnDeposit, so this waits until the deposit is settled. Sealed-bid auctions
+//! refuse cancellations during the reveal window.
+//!
+//! Native SOL pots refund their lamports to the bidder's wallet, leaving the pot rent exempt.
 
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionDataExtended, BidderMetadata, BidderPot},
+    processor::{AuctionData, AuctionDataExtended, BidBook, BidderMetadata, BidderPot},
     utils::{
         assert_derivation, assert_initialized, assert_owned_by, assert_signer,
         assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
-        TokenTransferParams,
+        transfer_program_lamports, TokenTransferParams,
     },
     EXTENDED, PREFIX,
 };
 
-use super::AuctionState;
+use super::{AuctionState, BidState};
 
 use {
     borsh::{BorshDeserialize, BorshSerialize},
@@ -52,6 +61,7 @@ struct Accounts<'a, 'b: 'a> {
     rent: &'a AccountInfo<'b>,
     system: &'a AccountInfo<'b>,
     token_program: &'a AccountInfo<'b>,
+    bid_book: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -72,6 +82,7 @@ fn parse_accounts<'a, 'b: 'a>(
         rent: next_account_info(account_iter)?,
         system: next_account_info(account_iter)?,
         token_program: next_account_info(account_iter)?,
+        bid_book: next_account_info(account_iter).ok(),
     };
 
     assert_owned_by(accounts.auction, program_id)?;
@@ -79,7 +90,6 @@ fn parse_accounts<'a, 'b: 'a>(
     assert_owned_by(accounts.bidder_meta, program_id)?;
     assert_owned_by(accounts.mint, &spl_token::id())?;
     assert_owned_by(accounts.bidder_pot, program_id)?;
-    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
     assert_signer(accounts.bidder)?;
     assert_token_program_matches_package(accounts.token_program)?;
 
@@ -98,12 +108,6 @@ pub fn cancel_bid(
     msg!("+ Processing Cancelbid");
     let accounts = parse_accounts(program_id, accounts)?;
 
-    // The account within the pot must be owned by us.
-    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
-    if actual_account.owner != *accounts.auction.key {
-        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
-    }
-
     // Derive and load Auction.
     let auction_bump = assert_derivation(
         program_id,
@@ -131,8 +135,31 @@ pub fn cancel_bid(
 
     // Load auction extended account to check instant_sale_price
     // and update cancelled bids if auction still active
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
     let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
 
+    // The account within the pot must be owned by us, native pots hold the bid themselves.
+    if auction_extended.native_sol {
+        if accounts.bidder_pot_token.key != accounts.bidder_pot.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+    } else {
+        assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+        let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
+        if actual_account.owner != *accounts.auction.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+    }
+
     // Load the clock, used for various auction timing.
     let clock = Clock::from_account_info(accounts.clock_sysvar)?;
 
@@ -178,9 +205,38 @@ pub fn cancel_bid(
         return Err(AuctionError::BidderPotDoesNotExist.into());
     }
 
-    // Refuse to cancel if the auction ended and this person is a winning account.
+    // Confirm we're looking at the real SPL account for this bidder.
+    let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    // Refuse to cancel if the auction ended and this person is a winning account, unless the bid
+    // was already claimed and only leftovers remain in the pot.
     let winner_bid_index = auction.is_winner(accounts.bidder.key);
-    if auction.ended(clock.unix_timestamp)? && winner_bid_index.is_some() {
+    let claimed_winner = winner_bid_index.is_some() && bidder_pot.emptied;
+    let ended = auction.state == AuctionState::Ended || auction.ended(clock.unix_timestamp)?;
+    if ended && winner_bid_index.is_some() && !claimed_winner {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    // A claimed winner's deposit comes back when they redeem, or goes to the seller.
+    if claimed_winner && auction_extended.bid_deposit.is_some() && !bidder_pot.deposit_settled {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    // Revealed bids must stay put until every sealed bid had its chance to be revealed.
+    if let Some(sealed_bid) = &auction_extended.sealed_bid {
+        if auction.state == AuctionState::Started
+            && clock.unix_timestamp > auction.commit_deadline(sealed_bid)?
+            && !auction.ended(clock.unix_timestamp)?
+        {
+            return Err(AuctionError::CannotCancelDuringReveal.into());
+        }
+    }
+
+    // Refuse to cancel a Dutch auction purchase.
+    if let (Some(_), BidState::DutchAuction { .. }) = (winner_bid_index, &auction.bid_state) {
         return Err(AuctionError::InvalidState.into());
     }
 
@@ -193,22 +249,28 @@ pub fn cancel_bid(
         }
     }
 
-    // Confirm we're looking at the real SPL account for this bidder.
-    let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
-    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
-        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    if auction_extended.native_sol {
+        // Transfer the lamports bid back to the user's wallet.
+        let amount = BidderPot::native_balance(accounts.bidder_pot)?;
+        transfer_program_lamports(accounts.bidder_pot, accounts.bidder, amount)?;
+    } else {
+        // Transfer SPL bid balance back to the user.
+        let account: Account =
+            Account::unpack_from_slice(&accounts.bidder_pot_token.data.borrow())?;
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_pot_token.clone(),
+            destination: accounts.bidder_token.clone(),
+            authority: accounts.auction.clone(),
+            authority_signer_seeds: auction_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: account.amount,
+        })?;
     }
 
-    // Transfer SPL bid balance back to the user.
-    let account: Account = Account::unpack_from_slice(&accounts.bidder_pot_token.data.borrow())?;
-    spl_token_transfer(TokenTransferParams {
-        source: accounts.bidder_pot_token.clone(),
-        destination: accounts.bidder_token.clone(),
-        authority: accounts.auction.clone(),
-        authority_signer_seeds: auction_seeds,
-        token_program: accounts.token_program.clone(),
-        amount: account.amount,
-    })?;
+    // The winning bid itself was settled by the claim, so neither it nor the metadata change.
+    if claimed_winner {
+        return Ok(());
+    }
 
     // Update Metadata
     let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
@@ -221,19 +283,8 @@ pub fn cancel_bid(
 
     // Update Auction
 
-    if auction.state != AuctionState::Ended {
+    if !matches!(auction.state, AuctionState::Ended | AuctionState::Cancelled) {
         // Once ended we want uncancelled bids to retain it's pre-ending count
-        assert_derivation(
-            program_id,
-            accounts.auction_extended,
-            &[
-                PREFIX.as_bytes(),
-                program_id.as_ref(),
-                args.resource.as_ref(),
-                EXTENDED.as_bytes(),
-            ],
-        )?;
-
         msg!("Already cancelled is {:?}", already_cancelled);
 
         if !already_cancelled && auction_extended.total_uncancelled_bids > 0 {
@@ -246,6 +297,15 @@ pub fn cancel_bid(
 
         // Only cancel the bid if the auction has not ended yet
         auction.bid_state.cancel_bid(*accounts.bidder.key);
+
+        // Take the bid out of the book too, and promote the best one left into the room made.
+        if auction_extended.bid_book {
+            let mut bid_book =
+                BidBook::for_auction(accounts.bid_book, program_id, accounts.auction.key)?;
+            bid_book.remove(accounts.bidder.key);
+            auction.bid_state.backfill(&mut bid_book);
+            bid_book.serialize(&mut *accounts.bid_book.unwrap().data.borrow_mut())?;
+        }
         auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
     }
 
@@ -1,13 +1,16 @@
 //! Claim bid winnings into a target SPL account, only the authorised key can do this, though the
 //! target can be any SPL account.
+//!
+//! Native SOL pots pay lamports instead, so the target and any refund account must be wallets
+//! rather than SPL accounts.
 
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionDataExtended, BidderMetadata, BidderPot},
+    processor::{AuctionData, AuctionDataExtended, AuctionState, BidderMetadata, BidderPot},
     utils::{
         assert_derivation, assert_initialized, assert_owned_by, assert_signer,
         assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
-        TokenTransferParams,
+        transfer_program_lamports, TokenTransferParams,
     },
     EXTENDED, PREFIX,
 };
@@ -26,6 +29,7 @@ use {
         sysvar::{clock::Clock, Sysvar},
     },
     spl_token::state::Account,
+    std::cmp,
 };
 
 #[repr(C)]
@@ -44,7 +48,8 @@ struct Accounts<'a, 'b: 'a> {
     mint: &'a AccountInfo<'b>,
     clock_sysvar: &'a AccountInfo<'b>,
     token_program: &'a AccountInfo<'b>,
-    auction_extended: Option<&'a AccountInfo<'b>>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder_token: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -62,21 +67,17 @@ fn parse_accounts<'a, 'b: 'a>(
         mint: next_account_info(account_iter)?,
         clock_sysvar: next_account_info(account_iter)?,
         token_program: next_account_info(account_iter)?,
-        auction_extended: next_account_info(account_iter).ok(),
+        auction_extended: next_account_info(account_iter)?,
+        bidder_token: next_account_info(account_iter).ok(),
     };
 
     assert_owned_by(accounts.auction, program_id)?;
     assert_owned_by(accounts.mint, &spl_token::id())?;
-    assert_owned_by(accounts.destination, &spl_token::id())?;
-    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
     assert_owned_by(accounts.bidder_pot, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
     assert_signer(accounts.authority)?;
     assert_token_program_matches_package(accounts.token_program)?;
 
-    if let Some(auction_extended) = accounts.auction_extended {
-        assert_owned_by(auction_extended, program_id)?;
-    }
-
     if *accounts.token_program.key != spl_token::id() {
         return Err(AuctionError::InvalidTokenProgram.into());
     }
@@ -84,6 +85,28 @@ fn parse_accounts<'a, 'b: 'a>(
     Ok(accounts)
 }
 
+/// Pays `amount` out of the bidder's pot, as lamports when the auction takes native SOL.
+fn pay_from_pot<'a, 'b: 'a>(
+    accounts: &Accounts<'a, 'b>,
+    native_sol: bool,
+    destination: &'a AccountInfo<'b>,
+    auction_seeds: &[&[u8]],
+    amount: u64,
+) -> ProgramResult {
+    if native_sol {
+        return transfer_program_lamports(accounts.bidder_pot, destination, amount);
+    }
+
+    spl_token_transfer(TokenTransferParams {
+        source: accounts.bidder_pot_token.clone(),
+        destination: destination.clone(),
+        authority: accounts.auction.clone(),
+        authority_signer_seeds: auction_seeds,
+        token_program: accounts.token_program.clone(),
+        amount,
+    })
+}
+
 pub fn claim_bid(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -93,12 +116,6 @@ pub fn claim_bid(
     let accounts = parse_accounts(program_id, accounts)?;
     let clock = Clock::from_account_info(accounts.clock_sysvar)?;
 
-    // The account within the pot must be owned by us.
-    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
-    if actual_account.owner != *accounts.auction.key {
-        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
-    }
-
     // Derive and load Auction.
     let auction_bump = assert_derivation(
         program_id,
@@ -132,26 +149,43 @@ pub fn claim_bid(
         return Err(AuctionError::InvalidState.into());
     }
 
-    let instant_sale_price = accounts.auction_extended.and_then(|info| {
-        assert_derivation(
-            program_id,
-            info,
-            &[
-                PREFIX.as_bytes(),
-                program_id.as_ref(),
-                args.resource.as_ref(),
-                EXTENDED.as_bytes(),
-            ],
-        )
-        .ok()?;
-
-        AuctionDataExtended::from_account_info(info)
-            .ok()?
-            .instant_sale_price
-    });
-
-    // Auction either must have ended or bidder pay instant_sale_price
-    if !auction.ended(clock.unix_timestamp)? {
+    // The extended data decides what the winner owes, so it cannot be left out.
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+    let instant_sale_price = auction_extended.instant_sale_price;
+    let native_sol = auction_extended.native_sol;
+
+    // What the pot holds. Native pots keep the bid as lamports above their rent exemption, token
+    // pots in an SPL account that must be owned by us.
+    let pot_balance = if native_sol {
+        if accounts.bidder_pot_token.key != accounts.bidder_pot.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+        if *accounts.destination.owner == spl_token::id() {
+            return Err(AuctionError::InvalidNativeDestination.into());
+        }
+        BidderPot::native_balance(accounts.bidder_pot)?
+    } else {
+        assert_owned_by(accounts.destination, &spl_token::id())?;
+        assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+        let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
+        if actual_account.owner != *accounts.auction.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+        actual_account.amount
+    };
+
+    // Auction either must have ended (on time or by selling out) or bidder pay instant_sale_price
+    if auction.state != AuctionState::Ended && !auction.ended(clock.unix_timestamp)? {
         match instant_sale_price {
             Some(instant_sale_price)
                 if auction.bid_state.amount(bid_index.unwrap()) < instant_sale_price =>
@@ -197,18 +231,84 @@ pub fn claim_bid(
         return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
     }
 
-    // Transfer SPL bid balance back to the user.
-    spl_token_transfer(TokenTransferParams {
-        source: accounts.bidder_pot_token.clone(),
-        destination: accounts.destination.clone(),
-        authority: accounts.auction.clone(),
-        authority_signer_seeds: auction_seeds,
-        token_program: accounts.token_program.clone(),
-        amount: actual_account.amount,
-    })?;
+    let deposit = auction_extended.bid_deposit.clone();
+
+    // Once claimed, the pot only holds the winner's deposit. It is forfeited if it wasn't returned
+    // within the redeem period, and only ever moves once.
+    if bidder_pot.emptied {
+        let forfeited = match (&deposit, auction.ended_at) {
+            (Some(deposit), Some(ended_at)) => {
+                clock.unix_timestamp > ended_at.saturating_add(deposit.redeem_period)
+            }
+            _ => false,
+        };
+        if !forfeited {
+            return Err(AuctionError::BidAlreadyClaimed.into());
+        }
+        if bidder_pot.deposit_settled {
+            return Err(AuctionError::DepositAlreadySettled.into());
+        }
+
+        pay_from_pot(
+            &accounts,
+            native_sol,
+            accounts.destination,
+            auction_seeds,
+            cmp::min(deposit.unwrap().amount, pot_balance),
+        )?;
+
+        bidder_pot.deposit_settled = true;
+        bidder_pot.save(accounts.bidder_pot)?;
+        return Ok(());
+    }
+
+    // The bid itself, leaving the deposit in the pot.
+    let held = match &deposit {
+        Some(deposit) => pot_balance.saturating_sub(deposit.amount),
+        None => pot_balance,
+    };
+
+    // Vickrey winners only pay the second price and uniform price winners the clearing price.
+    let amount = match auction.settlement_price(&auction_extended, bid_index.unwrap()) {
+        Some(price) => cmp::min(price, held),
+        None => held,
+    };
+
+    // Transfer the bid balance to the destination.
+    pay_from_pot(
+        &accounts,
+        native_sol,
+        accounts.destination,
+        auction_seeds,
+        amount,
+    )?;
+
+    // Hand back what the winner bid over their price. It must go out now, as anything left in the
+    // pot after the claim is treated as the winner's deposit.
+    let refund = held - amount;
+    if refund > 0 {
+        let bidder_token = accounts
+            .bidder_token
+            .ok_or(AuctionError::InvalidRefundAccount)?;
+        if native_sol {
+            if bidder_token.key != accounts.bidder.key {
+                return Err(AuctionError::InvalidRefundAccount.into());
+            }
+        } else {
+            assert_owned_by(bidder_token, &spl_token::id())?;
+            let bidder_token_account: Account = assert_initialized(bidder_token)?;
+            if bidder_token_account.owner != *accounts.bidder.key
+                || bidder_token_account.mint != auction.token_mint
+            {
+                return Err(AuctionError::InvalidRefundAccount.into());
+            }
+        }
+
+        pay_from_pot(&accounts, native_sol, bidder_token, auction_seeds, refund)?;
+    }
 
     bidder_pot.emptied = true;
-    bidder_pot.serialize(&mut *accounts.bidder_pot.data.borrow_mut())?;
+    bidder_pot.save(accounts.bidder_pot)?;
 
     Ok(())
 }
@@ -0,0 +1,175 @@
+//! Closes the accounts a bidder leaves behind once their bid is settled, returning the rent to
+//! the bidder. This only works after the auction has ended, and only if either:
+//!
+//! 1) The bid was claimed, so the BidderPot is marked emptied.
+//! 2) The bid was cancelled.
+//!
+//! In both cases the pot token account must already be empty, so a deposit left over after a claim
+//! has to be returned with return_deposit or forfeited first. Closing the BidderMetadata removes
+//! the bidder's record of the bid, so winners should redeem their prizes before closing.
+//!
+//! Native SOL pots point at themselves as their token account, and are passed in both places.
+//! They must hold nothing above their rent exemption.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionState, BidderMetadata, BidderPot},
+    utils::{
+        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, close_program_account, spl_token_close_account,
+        TokenCloseParams,
+    },
+    PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+    spl_token::state::Account,
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CloseBidderAccountsArgs {
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.bidder_meta, program_id)?;
+    assert_owned_by(accounts.bidder_pot, program_id)?;
+    assert_signer(accounts.bidder)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    Ok(accounts)
+}
+
+pub fn close_bidder_accounts(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CloseBidderAccountsArgs,
+) -> ProgramResult {
+    msg!("+ Processing CloseBidderAccounts");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    // Derive and load Auction.
+    let auction_bump = assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    let auction_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        &[auction_bump],
+    ];
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+    if auction.state != AuctionState::Ended && !auction.ended(clock.unix_timestamp)? {
+        return Err(AuctionError::BidderAccountsInUse.into());
+    }
+
+    // Derive the Metadata and Pot keys for this bidder.
+    assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+
+    // Confirm we're looking at the real SPL account for this bidder.
+    let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+    if !bidder_pot.emptied && !metadata.cancelled {
+        return Err(AuctionError::BidderAccountsInUse.into());
+    }
+
+    // Anything still in the pot belongs to someone, so it has to be taken out first.
+    if bidder_pot.bidder_pot == *accounts.bidder_pot.key {
+        if BidderPot::native_balance(accounts.bidder_pot)? > 0 {
+            return Err(AuctionError::BidderPotNotEmpty.into());
+        }
+        close_program_account(accounts.bidder_pot, accounts.bidder)?;
+        close_program_account(accounts.bidder_meta, accounts.bidder)?;
+        return Ok(());
+    }
+
+    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
+    if actual_account.owner != *accounts.auction.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+    if actual_account.amount > 0 {
+        return Err(AuctionError::BidderPotNotEmpty.into());
+    }
+
+    spl_token_close_account(TokenCloseParams {
+        account: accounts.bidder_pot_token.clone(),
+        destination: accounts.bidder.clone(),
+        authority: accounts.auction.clone(),
+        authority_signer_seeds: auction_seeds,
+        token_program: accounts.token_program.clone(),
+    })?;
+    close_program_account(accounts.bidder_pot, accounts.bidder)?;
+    close_program_account(accounts.bidder_meta, accounts.bidder)?;
+
+    Ok(())
+}
@@ -0,0 +1,345 @@
+//! Commits a sealed bid on a running sealed-bid auction. The bidder deposits as much as they are
+//! willing to bid into their pot and stores a hash of the real amount. The amount only enters the
+//! auction once it is revealed, see reveal_bid.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, BidCommitment, BidderMetadata, BidderPot,
+        BIDDER_METADATA_LEN, BID_COMMITMENT_LEN,
+    },
+    utils::{
+        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
+        TokenTransferParams,
+    },
+    EXTENDED, PREFIX, SEALED,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        hash::Hash,
+        msg,
+        program_error::ProgramError,
+        program_option::COption,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+    spl_token::state::Account,
+    std::mem,
+};
+
+/// Arguments for the CommitBid instruction discriminant .
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CommitBidArgs {
+    /// Amount moved into the bidder pot, which caps the bid that can be revealed later.
+    pub deposit: u64,
+    /// hashv of the bidder key, then the bid amount and a salt as big-endian u64s.
+    pub commitment: Hash,
+    /// Resource being bid on.
+    pub resource: Pubkey,
+    /// Merkle proof of the bidder's wallet, when bidding is gated on an allowlist.
+    pub allowlist_proof: Vec<[u8; 32]>,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bid_commitment: &'a AccountInfo<'b>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    bidder_token: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    system: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+    transfer_authority: &'a AccountInfo<'b>,
+    gate_token: Option<&'a AccountInfo<'b>>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder: next_account_info(account_iter)?,
+        bidder_token: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        bid_commitment: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        mint: next_account_info(account_iter)?,
+        transfer_authority: next_account_info(account_iter)?,
+        payer: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        rent: next_account_info(account_iter)?,
+        system: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+        gate_token: next_account_info(account_iter).ok(),
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.bidder_token, &spl_token::id())?;
+
+    if !accounts.bidder_pot.data_is_empty() {
+        assert_owned_by(accounts.bidder_pot, program_id)?;
+    }
+    if !accounts.bidder_meta.data_is_empty() {
+        assert_owned_by(accounts.bidder_meta, program_id)?;
+    }
+    if !accounts.bid_commitment.data_is_empty() {
+        assert_owned_by(accounts.bid_commitment, program_id)?;
+    }
+
+    assert_owned_by(accounts.mint, &spl_token::id())?;
+    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+    assert_signer(accounts.bidder)?;
+    assert_signer(accounts.payer)?;
+    assert_signer(accounts.transfer_authority)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    if *accounts.token_program.key != spl_token::id() {
+        return Err(AuctionError::InvalidTokenProgram.into());
+    }
+
+    Ok(accounts)
+}
+
+pub fn commit_bid<'r, 'b: 'r>(
+    program_id: &Pubkey,
+    accounts: &'r [AccountInfo<'b>],
+    args: CommitBidArgs,
+) -> ProgramResult {
+    msg!("+ Processing CommitBid");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    let sealed_bid = match &auction_extended.sealed_bid {
+        Some(sealed_bid) => sealed_bid,
+        None => return Err(AuctionError::NotSealedBidAuction.into()),
+    };
+
+    // Can't bid on an auction that isn't running.
+    if auction.state != AuctionState::Started {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    if let Some(bidder_gate) = &auction_extended.bidder_gate {
+        bidder_gate.check(
+            accounts.bidder.key,
+            &args.allowlist_proof,
+            accounts.gate_token,
+        )?;
+    }
+
+    if clock.unix_timestamp > auction.commit_deadline(sealed_bid)? {
+        return Err(AuctionError::CommitPhaseOver.into());
+    }
+
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+
+    // Derive Metadata key and load it.
+    let metadata_bump = assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+
+    // If metadata doesn't exist, create it.
+    if accounts.bidder_meta.owner != program_id {
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.bidder_meta,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            BIDDER_METADATA_LEN,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                accounts.bidder.key.as_ref(),
+                "metadata".as_bytes(),
+                &[metadata_bump],
+            ],
+        )?;
+    } else {
+        // Verify the last commitment was cancelled before continuing.
+        let bidder_metadata: BidderMetadata =
+            BidderMetadata::from_account_info(accounts.bidder_meta)?;
+        if bidder_metadata.cancelled == false {
+            return Err(AuctionError::BidAlreadyActive.into());
+        }
+    };
+
+    let commitment_bump = assert_derivation(
+        program_id,
+        accounts.bid_commitment,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            SEALED.as_bytes(),
+        ],
+    )?;
+
+    if accounts.bid_commitment.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.bid_commitment,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            BID_COMMITMENT_LEN,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                accounts.bidder.key.as_ref(),
+                SEALED.as_bytes(),
+                &[commitment_bump],
+            ],
+        )?;
+    }
+
+    // Derive Pot address, this account wraps/holds an SPL account to transfer tokens into and is
+    // also used as the authoriser of the SPL pot.
+    let pot_bump = assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+
+    // The account within the pot must be owned by us.
+    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
+    if actual_account.owner != *accounts.auction.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    if actual_account.delegate != COption::None {
+        return Err(AuctionError::DelegateShouldBeNone.into());
+    }
+
+    if actual_account.close_authority != COption::None {
+        return Err(AuctionError::CloseAuthorityShouldBeNone.into());
+    }
+
+    let bump_authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        accounts.auction.key.as_ref(),
+        accounts.bidder.key.as_ref(),
+        &[pot_bump],
+    ];
+
+    // If the bidder pot account is empty, we need to generate one.
+    if accounts.bidder_pot.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.bidder_pot,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            mem::size_of::<BidderPot>(),
+            bump_authority_seeds,
+        )?;
+
+        // Attach SPL token address to pot account.
+        let mut pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+        pot.bidder_pot = *accounts.bidder_pot_token.key;
+        pot.bidder_act = *accounts.bidder.key;
+        pot.auction_act = *accounts.auction.key;
+        pot.serialize(&mut *accounts.bidder_pot.data.borrow_mut())?;
+    } else {
+        // Already exists, verify that the pot contains the specified SPL address.
+        let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+        if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+    }
+
+    // Transfer the deposit into the pot.
+    spl_token_transfer(TokenTransferParams {
+        source: accounts.bidder_token.clone(),
+        destination: accounts.bidder_pot_token.clone(),
+        authority: accounts.transfer_authority.clone(),
+        authority_signer_seeds: bump_authority_seeds,
+        token_program: accounts.token_program.clone(),
+        amount: args.deposit,
+    })?;
+
+    auction_extended.total_uncancelled_bids = auction_extended
+        .total_uncancelled_bids
+        .checked_add(1)
+        .ok_or(AuctionError::NumericalOverflowError)?;
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    BidCommitment {
+        bidder_pubkey: *accounts.bidder.key,
+        auction_pubkey: *accounts.auction.key,
+        commitment: args.commitment,
+        revealed: false,
+    }
+    .serialize(&mut *accounts.bid_commitment.data.borrow_mut())?;
+
+    // The amount stays hidden until the reveal, so no bid is recorded yet.
+    BidderMetadata {
+        bidder_pubkey: *accounts.bidder.key,
+        auction_pubkey: *accounts.auction.key,
+        last_bid: 0,
+        last_bid_timestamp: clock.unix_timestamp,
+        cancelled: false,
+    }
+    .serialize(&mut *accounts.bidder_meta.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -3,8 +3,9 @@ use mem::size_of;
 use crate::{
     errors::AuctionError,
     processor::{
-        AuctionData, AuctionDataExtended, AuctionName, AuctionState, Bid, BidState, PriceFloor,
-        WinnerLimit, BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
+        AntiSnipeSettings, AuctionData, AuctionDataExtended, AuctionName, AuctionState, Bid,
+        BidDeposit, BidState, BidderGate, DutchAuctionSettings, PriceFloor, ReservePrice,
+        SealedBidSettings, WinnerLimit, BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
     },
     utils::{assert_derivation, assert_owned_by, create_or_allocate_account_raw},
     EXTENDED, PREFIX,
@@ -46,6 +47,22 @@ pub struct CreateAuctionArgs {
     pub gap_tick_size_percentage: Option<u8>,
 }
 
+/// Settings that only live in AuctionDataExtended, which older create instructions leave unset.
+#[derive(Clone, Default)]
+pub struct ExtendedAuctionSettings {
+    pub instant_sale_price: Option<u64>,
+    pub name: Option<AuctionName>,
+    pub sealed_bid: Option<SealedBidSettings>,
+    pub dutch_auction: Option<DutchAuctionSettings>,
+    pub reserve_price: Option<ReservePrice>,
+    pub anti_snipe: Option<AntiSnipeSettings>,
+    pub uniform_price: bool,
+    pub bidder_gate: Option<BidderGate>,
+    pub bid_deposit: Option<BidDeposit>,
+    pub start_at: Option<UnixTimestamp>,
+    pub native_sol: bool,
+}
+
 struct Accounts<'a, 'b: 'a> {
     auction: &'a AccountInfo<'b>,
     auction_extended: &'a AccountInfo<'b>,
@@ -73,11 +90,23 @@ pub fn create_auction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: CreateAuctionArgs,
-    instant_sale_price: Option<u64>,
-    name: Option<AuctionName>,
+    settings: ExtendedAuctionSettings,
 ) -> ProgramResult {
     msg!("+ Processing CreateAuction");
     let accounts = parse_accounts(program_id, accounts)?;
+    let ExtendedAuctionSettings {
+        instant_sale_price,
+        name,
+        sealed_bid,
+        dutch_auction,
+        reserve_price,
+        anti_snipe,
+        uniform_price,
+        bidder_gate,
+        bid_deposit,
+        start_at,
+        native_sol,
+    } = settings;
 
     let auction_path = [
         PREFIX.as_bytes(),
@@ -100,6 +129,7 @@ pub fn create_auction(
     };
 
     let bid_state = match args.winners {
+        WinnerLimit::Capped(n) if dutch_auction.is_some() => BidState::new_dutch(n),
         WinnerLimit::Capped(n) => BidState::new_english(n),
         WinnerLimit::Unlimited(_) => BidState::new_open_edition(),
     };
@@ -110,6 +140,121 @@ pub fn create_auction(
         }
     }
 
+    // Sealed bids need a fixed end to reveal against and a ranked bid state to reveal into. Gap
+    // extensions and instant sales both depend on seeing bids as they arrive.
+    if let Some(sealed) = &sealed_bid {
+        let valid = match (&args.winners, args.end_auction_at) {
+            (WinnerLimit::Capped(_), Some(end_auction_at)) => {
+                sealed.reveal_period > 0
+                    && sealed.reveal_period < end_auction_at
+                    && args.end_auction_gap.is_none()
+                    && instant_sale_price.is_none()
+            }
+            _ => false,
+        };
+        if !valid {
+            return Err(AuctionError::InvalidSealedBidSettings.into());
+        }
+    }
+
+    // Dutch auctions price every lot off their own schedule, which needs a start and end to run
+    // between. Ticks, gaps, instant sales and sealed bids have no meaning there.
+    if let Some(dutch) = &dutch_auction {
+        let valid = match args.winners {
+            WinnerLimit::Capped(n) => {
+                n > 0
+                    && dutch.is_valid()
+                    && args.end_auction_at.is_some()
+                    && args.end_auction_gap.is_none()
+                    && args.tick_size.is_none()
+                    && args.gap_tick_size_percentage.is_none()
+                    && instant_sale_price.is_none()
+                    && sealed_bid.is_none()
+            }
+            WinnerLimit::Unlimited(_) => false,
+        };
+        if !valid {
+            return Err(AuctionError::InvalidDutchAuctionSettings.into());
+        }
+    }
+
+    // A failed reserve takes the winners away at the end, so nobody may have been able to claim
+    // early through an instant sale or a Dutch purchase. Both blinded prices are opened by the one
+    // reveal passed to end_auction, so only one of them may be blinded.
+    if let Some(reserve) = &reserve_price {
+        let both_blinded = match (&reserve.price, &args.price_floor) {
+            (PriceFloor::BlindedPrice(_), PriceFloor::BlindedPrice(_)) => true,
+            _ => false,
+        };
+        let valid = match args.winners {
+            WinnerLimit::Capped(_) => {
+                reserve.is_valid()
+                    && !reserve.failed
+                    && !both_blinded
+                    && instant_sale_price.is_none()
+                    && dutch_auction.is_none()
+            }
+            WinnerLimit::Unlimited(_) => false,
+        };
+        if !valid {
+            return Err(AuctionError::InvalidReservePrice.into());
+        }
+    }
+
+    // Sealed and Dutch auctions both measure their schedules back from `ended_at`, so it must not
+    // move under them. The end gap is the older way of doing the same thing.
+    if let Some(anti_snipe) = &anti_snipe {
+        let valid = anti_snipe.is_valid()
+            && args.end_auction_at.is_some()
+            && args.end_auction_gap.is_none()
+            && sealed_bid.is_none()
+            && dutch_auction.is_none();
+        if !valid {
+            return Err(AuctionError::InvalidAntiSnipeSettings.into());
+        }
+    }
+
+    // Winners can only be charged the clearing price once bidding is over, so nothing may be
+    // claimed early. Vickrey settlement is a different price rule.
+    if uniform_price {
+        let vickrey = match &sealed_bid {
+            Some(sealed) => sealed.vickrey,
+            None => false,
+        };
+        let valid = match args.winners {
+            WinnerLimit::Capped(_) => {
+                instant_sale_price.is_none() && dutch_auction.is_none() && !vickrey
+            }
+            WinnerLimit::Unlimited(_) => false,
+        };
+        if !valid {
+            return Err(AuctionError::InvalidUniformPrice.into());
+        }
+    }
+
+    // Sealed bids already take a deposit with the commitment, and Dutch purchases are final.
+    if let Some(deposit) = &bid_deposit {
+        let valid = deposit.amount > 0
+            && deposit.redeem_period > 0
+            && sealed_bid.is_none()
+            && dutch_auction.is_none();
+        if !valid {
+            return Err(AuctionError::InvalidBidDeposit.into());
+        }
+    }
+
+    if let Some(start_at) = start_at {
+        if start_at <= 0 {
+            return Err(AuctionError::InvalidStartAt.into());
+        }
+    }
+
+    // Native pots hold SOL as lamports, so the mint only records that bids are in SOL. Sealed
+    // commitments still move their deposits through token accounts.
+    if native_sol && (args.token_mint != spl_token::native_mint::id() || sealed_bid.is_some()) {
+        return Err(AuctionError::InvalidNativeSolSettings.into());
+    }
+
     // Create auction account with enough space for a winner tracking.
     create_or_allocate_account_raw(
         *program_id,
@@ -160,6 +305,16 @@ pub fn create_auction(
         gap_tick_size_percentage: args.gap_tick_size_percentage,
         instant_sale_price,
         name,
+        sealed_bid,
+        dutch_auction,
+        reserve_price,
+        anti_snipe,
+        uniform_price,
+        bidder_gate,
+        bid_deposit,
+        bid_book: false,
+        start_at,
+        native_sol,
     }
     .serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
 
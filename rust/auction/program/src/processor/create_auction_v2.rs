@@ -93,7 +93,10 @@ pub fn create_auction_v2(
             tick_size: args.tick_size,
             gap_tick_size_percentage: args.gap_tick_size_percentage,
         },
-        args.instant_sale_price,
-        args.name,
+        ExtendedAuctionSettings {
+            instant_sale_price: args.instant_sale_price,
+            name: args.name,
+            ..Default::default()
+        },
     )
 }
@@ -0,0 +1,129 @@
+use mem::size_of;
+
+use crate::{
+    errors::AuctionError,
+    processor::create_auction::*,
+    processor::{
+        AntiSnipeSettings, AuctionData, AuctionDataExtended, AuctionName, AuctionState, Bid,
+        BidDeposit, BidState, BidderGate, DutchAuctionSettings, PriceFloor, ReservePrice,
+        SealedBidSettings, WinnerLimit, BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
+    },
+    utils::{assert_derivation, assert_owned_by, create_or_allocate_account_raw},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::UnixTimestamp,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    std::mem,
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CreateAuctionArgsV3 {
+    /// How many winners are allowed for this auction. See AuctionData.
+    pub winners: WinnerLimit,
+    /// End time is the cut-off point that the auction is forced to end by. See AuctionData.
+    pub end_auction_at: Option<UnixTimestamp>,
+    /// Gap time is how much time after the previous bid where the auction ends. See AuctionData.
+    pub end_auction_gap: Option<UnixTimestamp>,
+    /// Token mint for the SPL token used for bidding.
+    pub token_mint: Pubkey,
+    /// Authority
+    pub authority: Pubkey,
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+    /// Set a price floor.
+    pub price_floor: PriceFloor,
+    /// Add a tick size increment
+    pub tick_size: Option<u64>,
+    /// Add a minimum percentage increase each bid must meet.
+    pub gap_tick_size_percentage: Option<u8>,
+    /// Add a instant sale price.
+    pub instant_sale_price: Option<u64>,
+    /// Auction name
+    pub name: Option<AuctionName>,
+    /// Take bids as commitments and reveal them at the end. See SealedBidSettings.
+    pub sealed_bid: Option<SealedBidSettings>,
+    /// Run as a Dutch auction with this price schedule. See DutchAuctionSettings.
+    pub dutch_auction: Option<DutchAuctionSettings>,
+    /// Fail the auction if the top bid ends up under this price. See ReservePrice.
+    pub reserve_price: Option<ReservePrice>,
+    /// Extend the end when bids arrive close to it. See AntiSnipeSettings.
+    pub anti_snipe: Option<AntiSnipeSettings>,
+    /// Charge every winner the lowest winning bid instead of their own.
+    pub uniform_price: bool,
+    /// Only let allowlisted wallets or token holders bid. See BidderGate.
+    pub bidder_gate: Option<BidderGate>,
+    /// Take a deposit with each bidder's first bid. See BidDeposit.
+    pub bid_deposit: Option<BidDeposit>,
+    /// Let anyone start the auction once this time has passed.
+    pub start_at: Option<UnixTimestamp>,
+    /// Hold bids as lamports in the BidderPot instead of wrapped SOL token accounts.
+    pub native_sol: bool,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    system: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        payer: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        rent: next_account_info(account_iter)?,
+        system: next_account_info(account_iter)?,
+    };
+    Ok(accounts)
+}
+
+pub fn create_auction_v3(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateAuctionArgsV3,
+) -> ProgramResult {
+    create_auction(
+        program_id,
+        accounts,
+        CreateAuctionArgs {
+            winners: args.winners,
+            end_auction_at: args.end_auction_at,
+            end_auction_gap: args.end_auction_gap,
+            token_mint: args.token_mint,
+            authority: args.authority,
+            resource: args.resource,
+            price_floor: args.price_floor,
+            tick_size: args.tick_size,
+            gap_tick_size_percentage: args.gap_tick_size_percentage,
+        },
+        ExtendedAuctionSettings {
+            instant_sale_price: args.instant_sale_price,
+            name: args.name,
+            sealed_bid: args.sealed_bid,
+            dutch_auction: args.dutch_auction,
+            reserve_price: args.reserve_price,
+            anti_snipe: args.anti_snipe,
+            uniform_price: args.uniform_price,
+            bidder_gate: args.bidder_gate,
+            bid_deposit: args.bid_deposit,
+            start_at: args.start_at,
+            native_sol: args.native_sol,
+        },
+    )
+}
@@ -0,0 +1,150 @@
+//! Creates the bid book for an English auction that hasn't started yet. From then on bids pruned
+//! from the auction's bid state are kept in the book instead of being lost, and move back into
+//! the bid state when cancellations make room, so the auction ends with as many winners as there
+//! were willing bidders. place_bid and cancel_bid require the book once it exists.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        bid_book_len, AuctionData, AuctionDataExtended, AuctionState, BidBook, BidState,
+        MAX_BID_BOOK_CAPACITY,
+    },
+    utils::{assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw},
+    BID_BOOK, EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CreateBidBookArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+    /// Most bids the book can hold, at most MAX_BID_BOOK_CAPACITY.
+    pub capacity: u64,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    authority: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bid_book: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    system: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        authority: next_account_info(account_iter)?,
+        payer: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        bid_book: next_account_info(account_iter)?,
+        rent: next_account_info(account_iter)?,
+        system: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_signer(accounts.authority)?;
+    assert_signer(accounts.payer)?;
+    Ok(accounts)
+}
+
+pub fn create_bid_book(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateBidBookArgs,
+) -> ProgramResult {
+    msg!("+ Processing CreateBidBook");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let bid_book_bump = assert_derivation(
+        program_id,
+        accounts.bid_book,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            BID_BOOK.as_bytes(),
+        ],
+    )?;
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    // Every bid has to go through the book, so it can only be added before bidding starts.
+    let mut auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+    let english = matches!(auction.bid_state, BidState::EnglishAuction { .. });
+    if !english
+        || auction.state != AuctionState::Created
+        || auction_extended.sealed_bid.is_some()
+        || auction_extended.bid_book
+        || args.capacity == 0
+        || args.capacity as usize > MAX_BID_BOOK_CAPACITY
+    {
+        return Err(AuctionError::InvalidBidBookSettings.into());
+    }
+
+    create_or_allocate_account_raw(
+        *program_id,
+        accounts.bid_book,
+        accounts.rent,
+        accounts.system,
+        accounts.payer,
+        bid_book_len(args.capacity as usize),
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            BID_BOOK.as_bytes(),
+            &[bid_book_bump],
+        ],
+    )?;
+
+    BidBook {
+        auction: *accounts.auction.key,
+        capacity: args.capacity,
+        bids: vec![],
+    }
+    .serialize(&mut *accounts.bid_book.data.borrow_mut())?;
+
+    auction_extended.bid_book = true;
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionState, Bid, BidState, PriceFloor, WinnerLimit},
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, PriceFloor, WinnerLimit,
+    },
     utils::{assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw},
-    PREFIX,
+    EXTENDED, PREFIX,
 };
 
 use {
@@ -37,6 +39,7 @@ struct Accounts<'a, 'b: 'a> {
     authority: &'a AccountInfo<'b>,
     auction: &'a AccountInfo<'b>,
     clock_sysvar: &'a AccountInfo<'b>,
+    auction_extended: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -48,8 +51,12 @@ fn parse_accounts<'a, 'b: 'a>(
         authority: next_account_info(account_iter)?,
         auction: next_account_info(account_iter)?,
         clock_sysvar: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter).ok(),
     };
     assert_owned_by(accounts.auction, program_id)?;
+    if let Some(auction_extended) = accounts.auction_extended {
+        assert_owned_by(auction_extended, program_id)?;
+    }
     assert_signer(accounts.authority)?;
     Ok(accounts)
 }
@@ -105,6 +112,36 @@ pub fn end_auction<'a, 'b: 'a>(
         return Err(AuctionError::AuctionTransitionInvalid.into());
     }
 
+    // A reserve is settled here, against the top bid. If it is not met nobody wins, which leaves
+    // every bid free to be cancelled. The reserve only protects the authority, so ending without
+    // the extended account simply skips the check.
+    if let Some(auction_extended_info) = accounts.auction_extended {
+        assert_derivation(
+            program_id,
+            auction_extended_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                &args.resource.as_ref(),
+                EXTENDED.as_bytes(),
+            ],
+        )?;
+        let mut auction_extended = AuctionDataExtended::from_account_info(auction_extended_info)?;
+        if let Some(reserve) = &mut auction_extended.reserve_price {
+            reserve.price = reveal(reserve.price.clone(), args.reveal)?;
+            let minimum = match reserve.price {
+                PriceFloor::MinimumPrice(min) => min[0],
+                _ => 0,
+            };
+            if auction.num_winners() > 0 && auction.bid_state.amount(0) < minimum {
+                msg!("Top bid is under the reserve, the auction has failed");
+                reserve.failed = true;
+                auction.bid_state.clear_winners();
+            }
+            auction_extended.serialize(&mut *auction_extended_info.data.borrow_mut())?;
+        }
+    }
+
     AuctionData {
         ended_at: Some(clock.unix_timestamp),
         state: auction.state.end()?,
@@ -9,13 +9,22 @@
 //!
 //! A few solutions come to mind: don't allow cancelling bids, and simply prune all bids that
 //! are not winning bids from the state.
+//!
+//! A bidder with a live English auction bid can raise it by placing a larger bid. Only the
+//! difference is moved into their pot and the bid is re-ranked at its new amount.
+//!
+//! English auctions with a bid book move pruned bids into it rather than dropping them.
+//!
+//! Native SOL auctions take lamports from the bidder's wallet straight into the BidderPot. The
+//! pot then stands in for its own token account, and the bidder for theirs.
 
 use borsh::try_to_vec_with_schema;
 
 use crate::{
     errors::AuctionError,
     processor::{
-        AuctionData, AuctionDataExtended, AuctionState, Bid, BidderMetadata, BidderPot, PriceFloor,
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidBook, BidState, BidderMetadata,
+        BidderPot, PriceFloor,
     },
     utils::{
         assert_derivation, assert_initialized, assert_owned_by, assert_signer,
@@ -72,6 +81,8 @@ struct Accounts<'a, 'b: 'a> {
     system: &'a AccountInfo<'b>,
     token_program: &'a AccountInfo<'b>,
     transfer_authority: &'a AccountInfo<'b>,
+    gate_token: Option<&'a AccountInfo<'b>>,
+    bid_book: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -94,11 +105,12 @@ fn parse_accounts<'a, 'b: 'a>(
         rent: next_account_info(account_iter)?,
         system: next_account_info(account_iter)?,
         token_program: next_account_info(account_iter)?,
+        gate_token: next_account_info(account_iter).ok(),
+        bid_book: next_account_info(account_iter).ok(),
     };
 
     assert_owned_by(accounts.auction, program_id)?;
     assert_owned_by(accounts.auction_extended, program_id)?;
-    assert_owned_by(accounts.bidder_token, &spl_token::id())?;
 
     if !accounts.bidder_pot.data_is_empty() {
         assert_owned_by(accounts.bidder_pot, program_id)?;
@@ -108,7 +120,6 @@ fn parse_accounts<'a, 'b: 'a>(
     }
 
     assert_owned_by(accounts.mint, &spl_token::id())?;
-    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
     assert_signer(accounts.bidder)?;
     assert_signer(accounts.payer)?;
     assert_signer(accounts.transfer_authority)?;
@@ -126,6 +137,7 @@ pub fn place_bid<'r, 'b: 'r>(
     program_id: &Pubkey,
     accounts: &'r [AccountInfo<'b>],
     args: PlaceBidArgs,
+    allowlist_proof: &[[u8; 32]],
 ) -> ProgramResult {
     msg!("+ Processing PlaceBid");
     let accounts = parse_accounts(program_id, accounts)?;
@@ -156,6 +168,9 @@ pub fn place_bid<'r, 'b: 'r>(
         ],
     )?;
 
+    // The bidder's current bid when this bid raises it.
+    let mut previous_bid: Option<u64> = None;
+
     // If metadata doesn't exist, create it.
     if accounts.bidder_meta.owner != program_id {
         create_or_allocate_account_raw(
@@ -177,11 +192,15 @@ pub fn place_bid<'r, 'b: 'r>(
             ],
         )?;
     } else {
-        // Verify the last bid was cancelled before continuing.
+        // Verify the last bid was cancelled before continuing, unless it is an English auction
+        // bid being raised.
         let bidder_metadata: BidderMetadata =
             BidderMetadata::from_account_info(accounts.bidder_meta)?;
         if bidder_metadata.cancelled == false {
-            return Err(AuctionError::BidAlreadyActive.into());
+            match auction.bid_state {
+                BidState::EnglishAuction { .. } => previous_bid = Some(bidder_metadata.last_bid),
+                _ => return Err(AuctionError::BidAlreadyActive.into()),
+            }
         }
     };
 
@@ -198,18 +217,57 @@ pub fn place_bid<'r, 'b: 'r>(
         ],
     )?;
 
-    // The account within the pot must be owned by us.
-    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
-    if actual_account.owner != *accounts.auction.key {
-        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    // Load the extended settings, which decide how the bid is paid.
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let mut auction_extended: AuctionDataExtended =
+        AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+    if auction_extended.sealed_bid.is_some() {
+        return Err(AuctionError::BidsAreSealed.into());
     }
-
-    if actual_account.delegate != COption::None {
-        return Err(AuctionError::DelegateShouldBeNone.into());
+    if let Some(bidder_gate) = &auction_extended.bidder_gate {
+        bidder_gate.check(accounts.bidder.key, allowlist_proof, accounts.gate_token)?;
     }
+    let mut bid_book = match auction_extended.bid_book {
+        true => Some(BidBook::for_auction(
+            accounts.bid_book,
+            program_id,
+            accounts.auction.key,
+        )?),
+        false => None,
+    };
+
+    if auction_extended.native_sol {
+        if accounts.bidder_pot_token.key != accounts.bidder_pot.key
+            || accounts.bidder_token.key != accounts.bidder.key
+        {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+    } else {
+        assert_owned_by(accounts.bidder_token, &spl_token::id())?;
+        assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
 
-    if actual_account.close_authority != COption::None {
-        return Err(AuctionError::CloseAuthorityShouldBeNone.into());
+        // The account within the pot must be owned by us.
+        let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
+        if actual_account.owner != *accounts.auction.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+
+        if actual_account.delegate != COption::None {
+            return Err(AuctionError::DelegateShouldBeNone.into());
+        }
+
+        if actual_account.close_authority != COption::None {
+            return Err(AuctionError::CloseAuthorityShouldBeNone.into());
+        }
     }
 
     // Derive and load Auction.
@@ -263,24 +321,12 @@ pub fn place_bid<'r, 'b: 'r>(
     }
 
     // Update now we have new bid.
-    assert_derivation(
-        program_id,
-        accounts.auction_extended,
-        &[
-            PREFIX.as_bytes(),
-            program_id.as_ref(),
-            args.resource.as_ref(),
-            EXTENDED.as_bytes(),
-        ],
-    )?;
-    let mut auction_extended: AuctionDataExtended =
-        AuctionDataExtended::from_account_info(accounts.auction_extended)?;
-    auction_extended.total_uncancelled_bids = auction_extended
-        .total_uncancelled_bids
-        .checked_add(1)
-        .ok_or(AuctionError::NumericalOverflowError)?;
-    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
-
+    if previous_bid.is_none() {
+        auction_extended.total_uncancelled_bids = auction_extended
+            .total_uncancelled_bids
+            .checked_add(1)
+            .ok_or(AuctionError::NumericalOverflowError)?;
+    }
     let mut bid_price = args.amount;
 
     if let Some(instant_sale_price) = auction_extended.instant_sale_price {
@@ -290,36 +336,126 @@ pub fn place_bid<'r, 'b: 'r>(
         }
     }
 
-    // Confirm payers SPL token balance is enough to pay the bid.
-    let account: Account = Account::unpack_from_slice(&accounts.bidder_token.data.borrow())?;
-    if account.amount.saturating_sub(bid_price) < 0 {
-        msg!(
-            "Amount is too small: {:?}, compared to account amount of {:?}",
-            bid_price,
-            account.amount
-        );
-        return Err(AuctionError::BalanceTooLow.into());
+    // Dutch auction bids buy at the current price, anything above it is not taken.
+    if let Some(dutch_auction) = &auction_extended.dutch_auction {
+        let current_price = auction.dutch_price(dutch_auction, clock.unix_timestamp)?;
+        if args.amount < current_price {
+            msg!(
+                "Bid of {:?} is below the current price of {:?}",
+                args.amount,
+                current_price
+            );
+            return Err(AuctionError::BidTooSmall.into());
+        }
+        bid_price = current_price;
+    }
+
+    // A raise only pays the difference, the rest is already in the pot.
+    let transfer_amount = match previous_bid {
+        Some(previous) => {
+            if bid_price <= previous {
+                msg!(
+                    "Raised bid of {:?} must be larger than the current bid of {:?}",
+                    bid_price,
+                    previous
+                );
+                return Err(AuctionError::BidTooSmall.into());
+            }
+            bid_price - previous
+        }
+        None => bid_price,
+    };
+
+    // The deposit comes with the first bid and sits in the pot alongside it.
+    let transfer_amount = match (&auction_extended.bid_deposit, previous_bid) {
+        (Some(deposit), None) => transfer_amount
+            .checked_add(deposit.amount)
+            .ok_or(AuctionError::NumericalOverflowError)?,
+        _ => transfer_amount,
+    };
+
+    if auction_extended.native_sol {
+        // Confirm the bidder's wallet can pay the bid.
+        if accounts.bidder.lamports() < transfer_amount {
+            msg!(
+                "Amount is too small: {:?}, compared to wallet balance of {:?}",
+                transfer_amount,
+                accounts.bidder.lamports()
+            );
+            return Err(AuctionError::BalanceTooLow.into());
+        }
+
+        // Transfer lamports straight into the pot.
+        invoke(
+            &system_instruction::transfer(
+                accounts.bidder.key,
+                accounts.bidder_pot.key,
+                transfer_amount,
+            ),
+            &[
+                accounts.bidder.clone(),
+                accounts.bidder_pot.clone(),
+                accounts.system.clone(),
+            ],
+        )?;
+    } else {
+        // Confirm payers SPL token balance is enough to pay the bid.
+        let account: Account = Account::unpack_from_slice(&accounts.bidder_token.data.borrow())?;
+        if account.amount.saturating_sub(transfer_amount) < 0 {
+            msg!(
+                "Amount is too small: {:?}, compared to account amount of {:?}",
+                transfer_amount,
+                account.amount
+            );
+            return Err(AuctionError::BalanceTooLow.into());
+        }
+
+        // Transfer amount of SPL token to bid account.
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_token.clone(),
+            destination: accounts.bidder_pot_token.clone(),
+            authority: accounts.transfer_authority.clone(),
+            authority_signer_seeds: bump_authority_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: transfer_amount,
+        })?;
     }
 
-    // Transfer amount of SPL token to bid account.
-    spl_token_transfer(TokenTransferParams {
-        source: accounts.bidder_token.clone(),
-        destination: accounts.bidder_pot_token.clone(),
-        authority: accounts.transfer_authority.clone(),
-        authority_signer_seeds: bump_authority_seeds,
-        token_program: accounts.token_program.clone(),
-        amount: bid_price,
-    })?;
+    // Take the old bid out so the raised one is ranked, and gap checked, against everyone else.
+    if previous_bid.is_some() {
+        auction.bid_state.cancel_bid(*accounts.bidder.key)?;
+        if let Some(bid_book) = &mut bid_book {
+            bid_book.remove(accounts.bidder.key);
+        }
+    }
 
     // Serialize new Auction State
     auction.last_bid = Some(clock.unix_timestamp);
-    auction.place_bid(
+    let pruned = auction.place_bid(
         Bid(*accounts.bidder.key, bid_price),
         auction_extended.tick_size,
         auction_extended.gap_tick_size_percentage,
         clock.unix_timestamp,
         auction_extended.instant_sale_price,
     )?;
+
+    // Keep whatever bid fell off the bottom so it can be promoted again later.
+    if let Some(bid_book) = &mut bid_book {
+        if let Some(pruned) = pruned {
+            bid_book.insert(pruned);
+        }
+        bid_book.serialize(&mut *accounts.bid_book.unwrap().data.borrow_mut())?;
+    }
+
+    // A bid close to the end pushes it back, stored in ended_at so ended() sees the real end.
+    if let (Some(anti_snipe), Some(end)) = (&mut auction_extended.anti_snipe, auction.ended_at) {
+        let new_end = anti_snipe.extend(end, clock.unix_timestamp)?;
+        if new_end != end {
+            msg!("Bid close to the end, extending the auction to {}", new_end);
+            auction.ended_at = Some(new_end);
+        }
+    }
+    auction_extended.serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
     auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
 
     // Update latest metadata with results from the bid.
@@ -0,0 +1,339 @@
+//! Makes an offer on an English auction that ended without a single bid. The offer is escrowed
+//! in the same BidderPot a bid would use and recorded in the BidderMetadata, but it stays out of
+//! the bid state until the authority accepts it with accept_offer.
+//!
+//! An offer that is not accepted is withdrawn with cancel_bid, like any losing bid.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, BidderMetadata, BidderPot, PriceFloor,
+    },
+    utils::{
+        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, create_or_allocate_account_raw, spl_token_transfer,
+        TokenTransferParams,
+    },
+    EXTENDED, PREFIX,
+};
+
+use super::BIDDER_METADATA_LEN;
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke,
+        program_error::ProgramError,
+        program_option::COption,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::{clock::Clock, Sysvar},
+    },
+    spl_token::state::Account,
+    std::mem,
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct PlaceOfferArgs {
+    /// Size of the offer being made.
+    pub amount: u64,
+    /// Resource the auction was for.
+    pub resource: Pubkey,
+    /// Merkle proof of the bidder's wallet, when bidding is gated on an allowlist.
+    pub allowlist_proof: Vec<[u8; 32]>,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    bidder_token: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    system: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+    transfer_authority: &'a AccountInfo<'b>,
+    gate_token: Option<&'a AccountInfo<'b>>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder: next_account_info(account_iter)?,
+        bidder_token: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        mint: next_account_info(account_iter)?,
+        transfer_authority: next_account_info(account_iter)?,
+        payer: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        rent: next_account_info(account_iter)?,
+        system: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+        gate_token: next_account_info(account_iter).ok(),
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+
+    if !accounts.bidder_pot.data_is_empty() {
+        assert_owned_by(accounts.bidder_pot, program_id)?;
+    }
+    if !accounts.bidder_meta.data_is_empty() {
+        assert_owned_by(accounts.bidder_meta, program_id)?;
+    }
+
+    assert_owned_by(accounts.mint, &spl_token::id())?;
+    assert_signer(accounts.bidder)?;
+    assert_signer(accounts.payer)?;
+    assert_signer(accounts.transfer_authority)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    if *accounts.token_program.key != spl_token::id() {
+        return Err(AuctionError::InvalidTokenProgram.into());
+    }
+
+    Ok(accounts)
+}
+
+pub fn place_offer<'r, 'b: 'r>(
+    program_id: &Pubkey,
+    accounts: &'r [AccountInfo<'b>],
+    args: PlaceOfferArgs,
+) -> ProgramResult {
+    msg!("+ Processing PlaceOffer");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    // Offers are only taken once the auction is over, an auction that ran out of time is ended
+    // here just like place_bid would.
+    if auction.state != AuctionState::Ended {
+        if auction.state == AuctionState::Cancelled || !auction.ended(clock.unix_timestamp)? {
+            return Err(AuctionError::InvalidState.into());
+        }
+        auction.state = auction.state.end()?;
+        auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+    }
+    auction.assert_open_for_offers(&auction_extended)?;
+
+    if let Some(bidder_gate) = &auction_extended.bidder_gate {
+        bidder_gate.check(
+            accounts.bidder.key,
+            &args.allowlist_proof,
+            accounts.gate_token,
+        )?;
+    }
+
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+
+    // Derive Metadata key and load it.
+    let metadata_bump = assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+
+    // If metadata doesn't exist, create it, otherwise the last bid or offer must be cancelled.
+    if accounts.bidder_meta.owner != program_id {
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.bidder_meta,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            BIDDER_METADATA_LEN,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                accounts.auction.key.as_ref(),
+                accounts.bidder.key.as_ref(),
+                "metadata".as_bytes(),
+                &[metadata_bump],
+            ],
+        )?;
+    } else if !BidderMetadata::from_account_info(accounts.bidder_meta)?.cancelled {
+        return Err(AuctionError::BidAlreadyActive.into());
+    }
+
+    // Derive Pot address, this account wraps/holds an SPL account to transfer tokens into and is
+    // also used as the authoriser of the SPL pot.
+    let pot_bump = assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+
+    if auction_extended.native_sol {
+        if accounts.bidder_pot_token.key != accounts.bidder_pot.key
+            || accounts.bidder_token.key != accounts.bidder.key
+        {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+    } else {
+        assert_owned_by(accounts.bidder_token, &spl_token::id())?;
+        assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+
+        // The account within the pot must be owned by us.
+        let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
+        if actual_account.owner != *accounts.auction.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+
+        if actual_account.delegate != COption::None {
+            return Err(AuctionError::DelegateShouldBeNone.into());
+        }
+
+        if actual_account.close_authority != COption::None {
+            return Err(AuctionError::CloseAuthorityShouldBeNone.into());
+        }
+    }
+
+    let bump_authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        accounts.auction.key.as_ref(),
+        accounts.bidder.key.as_ref(),
+        &[pot_bump],
+    ];
+
+    // If the bidder pot account is empty, we need to generate one.
+    if accounts.bidder_pot.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            accounts.bidder_pot,
+            accounts.rent,
+            accounts.system,
+            accounts.payer,
+            mem::size_of::<BidderPot>(),
+            bump_authority_seeds,
+        )?;
+
+        // Attach SPL token address to pot account.
+        let mut pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+        pot.bidder_pot = *accounts.bidder_pot_token.key;
+        pot.bidder_act = *accounts.bidder.key;
+        pot.auction_act = *accounts.auction.key;
+        pot.serialize(&mut *accounts.bidder_pot.data.borrow_mut())?;
+    } else {
+        // Already exists, verify that the pot contains the specified SPL address.
+        let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+        if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+    }
+
+    let offer = match &auction.price_floor {
+        PriceFloor::MinimumPrice(min) => {
+            if args.amount < min[0] {
+                return Err(AuctionError::BidTooSmall.into());
+            }
+            args.amount
+        }
+        _ => args.amount,
+    };
+
+    // Like a bid, the offer brings the deposit along, so an accepted offer settles the same way.
+    let transfer_amount = match &auction_extended.bid_deposit {
+        Some(deposit) => offer
+            .checked_add(deposit.amount)
+            .ok_or(AuctionError::NumericalOverflowError)?,
+        None => offer,
+    };
+
+    if auction_extended.native_sol {
+        if accounts.bidder.lamports() < transfer_amount {
+            return Err(AuctionError::BalanceTooLow.into());
+        }
+
+        // Transfer lamports straight into the pot.
+        invoke(
+            &system_instruction::transfer(
+                accounts.bidder.key,
+                accounts.bidder_pot.key,
+                transfer_amount,
+            ),
+            &[
+                accounts.bidder.clone(),
+                accounts.bidder_pot.clone(),
+                accounts.system.clone(),
+            ],
+        )?;
+    } else {
+        let account: Account = Account::unpack_from_slice(&accounts.bidder_token.data.borrow())?;
+        if account.amount < transfer_amount {
+            return Err(AuctionError::BalanceTooLow.into());
+        }
+
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_token.clone(),
+            destination: accounts.bidder_pot_token.clone(),
+            authority: accounts.transfer_authority.clone(),
+            authority_signer_seeds: bump_authority_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: transfer_amount,
+        })?;
+    }
+
+    BidderMetadata {
+        bidder_pubkey: *accounts.bidder.key,
+        auction_pubkey: *accounts.auction.key,
+        last_bid: offer,
+        last_bid_timestamp: clock.unix_timestamp,
+        cancelled: false,
+    }
+    .serialize(&mut *accounts.bidder_meta.data.borrow_mut())?;
+
+    Ok(())
+}
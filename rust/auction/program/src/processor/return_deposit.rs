@@ -0,0 +1,192 @@
+//! Returns the deposit of a winner whose bid was claimed. Only the authority can do this, so an
+//! auction run by another program can hold the deposit back until the winner redeems their prize.
+//!
+//! Native SOL pots pay the deposit back to the bidder's wallet.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, BidderPot},
+    utils::{
+        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, spl_token_transfer, transfer_program_lamports,
+        TokenTransferParams,
+    },
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+    std::cmp,
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct ReturnDepositArgs {
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    bidder_token: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder_token: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        bidder: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.bidder_pot, program_id)?;
+    assert_signer(accounts.authority)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    if *accounts.token_program.key != spl_token::id() {
+        return Err(AuctionError::InvalidTokenProgram.into());
+    }
+
+    Ok(accounts)
+}
+
+pub fn return_deposit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ReturnDepositArgs,
+) -> ProgramResult {
+    msg!("+ Processing ReturnDeposit");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    // Derive and load Auction.
+    let auction_bump = assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    let auction_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        &[auction_bump],
+    ];
+
+    let auction = AuctionData::from_account_info(accounts.auction)?;
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    // Only winners leave their deposit behind when their bid is claimed.
+    if auction.is_winner(accounts.bidder.key).is_none() {
+        return Err(AuctionError::InvalidState.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+    let deposit = match auction_extended.bid_deposit {
+        Some(deposit) => deposit,
+        None => return Err(AuctionError::InvalidState.into()),
+    };
+    let native_sol = auction_extended.native_sol;
+
+    // Derive Pot address and confirm we're looking at the real pot account for this bidder.
+    assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+    let mut bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    // The bid has to be claimed first, or the deposit would be paid out of it.
+    if !bidder_pot.emptied {
+        return Err(AuctionError::InvalidState.into());
+    }
+    if bidder_pot.deposit_settled {
+        return Err(AuctionError::DepositAlreadySettled.into());
+    }
+
+    if native_sol {
+        if accounts.bidder_token.key != accounts.bidder.key {
+            return Err(AuctionError::InvalidRefundAccount.into());
+        }
+
+        let amount = cmp::min(
+            deposit.amount,
+            BidderPot::native_balance(accounts.bidder_pot)?,
+        );
+        transfer_program_lamports(accounts.bidder_pot, accounts.bidder_token, amount)?;
+    } else {
+        assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+        let pot_account: Account = assert_initialized(accounts.bidder_pot_token)?;
+        if pot_account.owner != *accounts.auction.key {
+            return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+        }
+
+        assert_owned_by(accounts.bidder_token, &spl_token::id())?;
+        let bidder_token_account: Account = assert_initialized(accounts.bidder_token)?;
+        if bidder_token_account.owner != *accounts.bidder.key
+            || bidder_token_account.mint != auction.token_mint
+        {
+            return Err(AuctionError::InvalidRefundAccount.into());
+        }
+
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_pot_token.clone(),
+            destination: accounts.bidder_token.clone(),
+            authority: accounts.auction.clone(),
+            authority_signer_seeds: auction_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: cmp::min(deposit.amount, pot_account.amount),
+        })?;
+    }
+
+    bidder_pot.deposit_settled = true;
+    bidder_pot.save(accounts.bidder_pot)?;
+
+    Ok(())
+}
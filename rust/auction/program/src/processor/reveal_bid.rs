@@ -0,0 +1,256 @@
+//! Reveals a sealed bid committed with commit_bid. Reveals are only accepted in the window between
+//! the commit deadline and the end of the auction. Whatever the deposit held above the revealed
+//! amount goes straight back to the bidder, and the bid is ranked like any other English auction
+//! bid. Bids that are never revealed, or fail to reveal, can be cancelled once the auction ends.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidCommitment, BidderMetadata,
+        BidderPot,
+    },
+    utils::{
+        assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+        assert_token_program_matches_package, spl_token_transfer, TokenTransferParams,
+    },
+    EXTENDED, PREFIX, SEALED,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        hash, msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{clock::Clock, Sysvar},
+    },
+    spl_token::state::Account,
+};
+
+/// Arguments for the RevealBid instruction discriminant .
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct RevealBidArgs {
+    /// The amount that was committed to.
+    pub amount: u64,
+    /// The salt that was committed to.
+    pub salt: u64,
+    /// Resource being bid on.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    bid_commitment: &'a AccountInfo<'b>,
+    bidder_meta: &'a AccountInfo<'b>,
+    bidder_pot: &'a AccountInfo<'b>,
+    bidder_pot_token: &'a AccountInfo<'b>,
+    bidder: &'a AccountInfo<'b>,
+    bidder_token: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    token_program: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        bidder: next_account_info(account_iter)?,
+        bidder_token: next_account_info(account_iter)?,
+        bidder_pot: next_account_info(account_iter)?,
+        bidder_pot_token: next_account_info(account_iter)?,
+        bidder_meta: next_account_info(account_iter)?,
+        bid_commitment: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        mint: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        token_program: next_account_info(account_iter)?,
+    };
+
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    assert_owned_by(accounts.bidder_meta, program_id)?;
+    assert_owned_by(accounts.bid_commitment, program_id)?;
+    assert_owned_by(accounts.bidder_pot, program_id)?;
+    assert_owned_by(accounts.bidder_token, &spl_token::id())?;
+    assert_owned_by(accounts.bidder_pot_token, &spl_token::id())?;
+    assert_owned_by(accounts.mint, &spl_token::id())?;
+    assert_signer(accounts.bidder)?;
+    assert_token_program_matches_package(accounts.token_program)?;
+
+    if *accounts.token_program.key != spl_token::id() {
+        return Err(AuctionError::InvalidTokenProgram.into());
+    }
+
+    Ok(accounts)
+}
+
+pub fn reveal_bid(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RevealBidArgs,
+) -> ProgramResult {
+    msg!("+ Processing RevealBid");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    // Derive and load Auction.
+    let auction_bump = assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    let auction_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        &[auction_bump],
+    ];
+
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    let sealed_bid = match &auction_extended.sealed_bid {
+        Some(sealed_bid) => sealed_bid,
+        None => return Err(AuctionError::NotSealedBidAuction.into()),
+    };
+
+    if auction.state != AuctionState::Started
+        || clock.unix_timestamp <= auction.commit_deadline(sealed_bid)?
+        || auction.ended(clock.unix_timestamp)?
+    {
+        return Err(AuctionError::NotInRevealPhase.into());
+    }
+
+    if auction.token_mint != *accounts.mint.key {
+        return Err(AuctionError::IncorrectMint.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_meta,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+
+    // A cancelled commitment has already had its deposit returned.
+    let metadata = BidderMetadata::from_account_info(accounts.bidder_meta)?;
+    if metadata.cancelled {
+        return Err(AuctionError::BidderPotEmpty.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.bid_commitment,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+            SEALED.as_bytes(),
+        ],
+    )?;
+
+    let mut bid_commitment = BidCommitment::from_account_info(accounts.bid_commitment)?;
+    if bid_commitment.revealed {
+        return Err(AuctionError::BidAlreadyRevealed.into());
+    }
+
+    // The bidder key is part of the hash so nobody can copy a commitment and reveal it as their own.
+    let reveal_hash = hash::hashv(&[
+        accounts.bidder.key.as_ref(),
+        &args.amount.to_be_bytes(),
+        &args.salt.to_be_bytes(),
+    ]);
+    if reveal_hash != bid_commitment.commitment {
+        return Err(AuctionError::InvalidReveal.into());
+    }
+
+    assert_derivation(
+        program_id,
+        accounts.bidder_pot,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            accounts.auction.key.as_ref(),
+            accounts.bidder.key.as_ref(),
+        ],
+    )?;
+
+    // Confirm we're looking at the real SPL account for this bidder.
+    let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
+    if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    let actual_account: Account = assert_initialized(accounts.bidder_pot_token)?;
+    if actual_account.owner != *accounts.auction.key {
+        return Err(AuctionError::BidderPotTokenAccountOwnerMismatch.into());
+    }
+
+    if args.amount > actual_account.amount {
+        return Err(AuctionError::RevealExceedsDeposit.into());
+    }
+
+    // Return the part of the deposit above the revealed amount.
+    let excess = actual_account.amount - args.amount;
+    if excess > 0 {
+        spl_token_transfer(TokenTransferParams {
+            source: accounts.bidder_pot_token.clone(),
+            destination: accounts.bidder_token.clone(),
+            authority: accounts.auction.clone(),
+            authority_signer_seeds: auction_seeds,
+            token_program: accounts.token_program.clone(),
+            amount: excess,
+        })?;
+    }
+
+    auction.place_bid(
+        Bid(*accounts.bidder.key, args.amount),
+        auction_extended.tick_size,
+        None,
+        clock.unix_timestamp,
+        None,
+    )?;
+    auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    bid_commitment.revealed = true;
+    bid_commitment.serialize(&mut *accounts.bid_commitment.data.borrow_mut())?;
+
+    BidderMetadata {
+        last_bid: args.amount,
+        last_bid_timestamp: clock.unix_timestamp,
+        ..metadata
+    }
+    .serialize(&mut *accounts.bidder_meta.data.borrow_mut())?;
+
+    Ok(())
+}
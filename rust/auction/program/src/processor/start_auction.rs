@@ -1,8 +1,8 @@
 use crate::{
     errors::AuctionError,
-    processor::{AuctionData, AuctionState, Bid, BidState, WinnerLimit},
+    processor::{AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, WinnerLimit},
     utils::{assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw},
-    PREFIX,
+    EXTENDED, PREFIX,
 };
 
 use {
@@ -23,6 +23,7 @@ struct Accounts<'a, 'b: 'a> {
     authority: &'a AccountInfo<'b>,
     auction: &'a AccountInfo<'b>,
     clock_sysvar: &'a AccountInfo<'b>,
+    auction_extended: Option<&'a AccountInfo<'b>>,
 }
 
 fn parse_accounts<'a, 'b: 'a>(
@@ -34,8 +35,12 @@ fn parse_accounts<'a, 'b: 'a>(
         authority: next_account_info(account_iter)?,
         auction: next_account_info(account_iter)?,
         clock_sysvar: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter).ok(),
     };
     assert_owned_by(accounts.auction, program_id)?;
+    if let Some(auction_extended) = accounts.auction_extended {
+        assert_owned_by(auction_extended, program_id)?;
+    }
     assert_signer(accounts.authority)?;
     Ok(accounts)
 }
@@ -75,6 +80,26 @@ pub fn start_auction<'a, 'b: 'a>(
         return Err(AuctionError::InvalidAuthority.into());
     }
 
+    // A scheduled auction can't be started early.
+    if let Some(auction_extended) = accounts.auction_extended {
+        assert_derivation(
+            program_id,
+            auction_extended,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                args.resource.as_ref(),
+                EXTENDED.as_bytes(),
+            ],
+        )?;
+        let auction_extended = AuctionDataExtended::from_account_info(auction_extended)?;
+        if let Some(start_at) = auction_extended.start_at {
+            if clock.unix_timestamp < start_at {
+                return Err(AuctionError::AuctionStartTooEarly.into());
+            }
+        }
+    }
+
     // Calculate the relative end time.
     let ended_at = if let Some(end_auction_at) = auction.end_auction_at {
         match clock.unix_timestamp.checked_add(end_auction_at) {
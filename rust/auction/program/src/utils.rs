@@ -5,7 +5,7 @@ use {
     solana_program::{
         account_info::AccountInfo,
         entrypoint::ProgramResult,
-        msg,
+        hash, msg,
         program::{invoke, invoke_signed},
         program_error::ProgramError,
         program_pack::Pack,
@@ -27,6 +27,20 @@ pub fn assert_initialized<T: Pack + IsInitialized>(
     }
 }
 
+/// Walks a Merkle proof up from `leaf`, hashing each pair smallest first, and checks it reaches
+/// `root`.
+pub fn verify_merkle_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let mut node = leaf;
+    for sibling in proof {
+        node = if node <= *sibling {
+            hash::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hash::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+    node == *root
+}
+
 pub fn assert_token_program_matches_package(token_program_info: &AccountInfo) -> ProgramResult {
     if *token_program_info.key != spl_token::id() {
         return Err(AuctionError::InvalidTokenProgram.into());
@@ -166,6 +180,79 @@ pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult
     result.map_err(|_| AuctionError::TokenTransferFailed.into())
 }
 
+/// TokenCloseParams
+pub struct TokenCloseParams<'a: 'b, 'b> {
+    /// account
+    pub account: AccountInfo<'a>,
+    /// destination
+    pub destination: AccountInfo<'a>,
+    /// authority
+    pub authority: AccountInfo<'a>,
+    /// authority_signer_seeds
+    pub authority_signer_seeds: &'b [&'b [u8]],
+    /// token_program
+    pub token_program: AccountInfo<'a>,
+}
+
+/// Close an empty SPL token account, sending its rent to `destination`.
+#[inline(always)]
+pub fn spl_token_close_account(params: TokenCloseParams<'_, '_>) -> ProgramResult {
+    let TokenCloseParams {
+        account,
+        destination,
+        authority,
+        authority_signer_seeds,
+        token_program,
+    } = params;
+
+    invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            account.key,
+            destination.key,
+            authority.key,
+            &[],
+        )?,
+        &[account, destination, authority, token_program],
+        &[authority_signer_seeds],
+    )
+}
+
+/// Close an account owned by this program, moving its lamports to `destination` and zeroing its
+/// data so it can't be read again later in the same transaction.
+pub fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(AuctionError::NumericalOverflowError)?;
+    **account.lamports.borrow_mut() = 0;
+
+    for byte in account.data.borrow_mut().iter_mut() {
+        *byte = 0;
+    }
+
+    Ok(())
+}
+
+/// Move lamports out of an account owned by this program, such as a native SOL BidderPot.
+pub fn transfer_program_lamports(
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    **source.lamports.borrow_mut() = source
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(AuctionError::BalanceTooLow)?;
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(amount)
+        .ok_or(AuctionError::NumericalOverflowError)?;
+
+    Ok(())
+}
+
 /// TokenMintToParams
 pub struct TokenCreateAccount<'a> {
     /// payer
@@ -9,9 +9,13 @@ use solana_sdk::{
 use metaplex_auction::{
     instruction,
     processor::{
-        CancelBidArgs, ClaimBidArgs, CreateAuctionArgs, CreateAuctionArgsV2, EndAuctionArgs,
-        PlaceBidArgs, PriceFloor, StartAuctionArgs, WinnerLimit,
+        AcceptOfferArgs, AntiSnipeSettings, BidDeposit, BidderGate, CancelAuctionArgs,
+        CancelBidArgs, ClaimBidArgs, CloseBidderAccountsArgs, CommitBidArgs, CreateAuctionArgs,
+        CreateAuctionArgsV2, CreateAuctionArgsV3, CreateBidBookArgs, DutchAuctionSettings,
+        EndAuctionArgs, PlaceBidArgs, PlaceBidArgsV2, PlaceOfferArgs, PriceFloor, ReservePrice,
+        ReturnDepositArgs, SealedBidSettings, StartAuctionArgs, WinnerLimit,
     },
+    EXTENDED, PREFIX,
 };
 
 fn string_to_array(value: &str) -> Result<[u8; 32], TransportError> {
@@ -172,7 +176,7 @@ pub async fn create_auction(
                     price_floor,
                     gap_tick_size_percentage,
                     tick_size,
-                    name: string_to_array(name)?,
+                    name: Some(string_to_array(name)?),
                     instant_sale_price,
                 },
             )],
@@ -206,6 +210,327 @@ pub async fn create_auction(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub async fn create_sealed_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    end_auction_at: i64,
+    sealed_bid: SealedBidSettings,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: Some(end_auction_at),
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                sealed_bid: Some(sealed_bid),
+                dutch_auction: None,
+                reserve_price: None,
+                anti_snipe: None,
+                uniform_price: false,
+                bidder_gate: None,
+                bid_deposit: None,
+                start_at: None,
+                native_sol: false,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_dutch_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    end_auction_at: i64,
+    dutch_auction: DutchAuctionSettings,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: Some(end_auction_at),
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                sealed_bid: None,
+                dutch_auction: Some(dutch_auction),
+                reserve_price: None,
+                anti_snipe: None,
+                uniform_price: false,
+                bidder_gate: None,
+                bid_deposit: None,
+                start_at: None,
+                native_sol: false,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_reserve_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    reserve_price: ReservePrice,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: None,
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                sealed_bid: None,
+                dutch_auction: None,
+                reserve_price: Some(reserve_price),
+                anti_snipe: None,
+                uniform_price: false,
+                bidder_gate: None,
+                bid_deposit: None,
+                start_at: None,
+                native_sol: false,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_anti_snipe_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    end_auction_at: i64,
+    anti_snipe: AntiSnipeSettings,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: Some(end_auction_at),
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                sealed_bid: None,
+                dutch_auction: None,
+                reserve_price: None,
+                anti_snipe: Some(anti_snipe),
+                uniform_price: false,
+                bidder_gate: None,
+                bid_deposit: None,
+                start_at: None,
+                native_sol: false,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_uniform_price_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: None,
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                sealed_bid: None,
+                dutch_auction: None,
+                reserve_price: None,
+                anti_snipe: None,
+                uniform_price: true,
+                bidder_gate: None,
+                bid_deposit: None,
+                start_at: None,
+                native_sol: false,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_gated_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    bidder_gate: BidderGate,
+    bid_deposit: Option<BidDeposit>,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: None,
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                sealed_bid: None,
+                dutch_auction: None,
+                reserve_price: None,
+                anti_snipe: None,
+                uniform_price: false,
+                bidder_gate: Some(bidder_gate),
+                bid_deposit,
+                start_at: None,
+                native_sol: false,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_scheduled_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    max_winners: usize,
+    start_at: i64,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: Some(60),
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: *mint_keypair,
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                sealed_bid: None,
+                dutch_auction: None,
+                reserve_price: None,
+                anti_snipe: None,
+                uniform_price: false,
+                bidder_gate: None,
+                bid_deposit: None,
+                start_at: Some(start_at),
+                native_sol: false,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
 pub async fn end_auction(
     banks_client: &mut BanksClient,
     program_id: &Pubkey,
@@ -230,6 +555,29 @@ pub async fn end_auction(
     Ok(())
 }
 
+pub async fn cancel_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    recent_blockhash: &Hash,
+    payer: &Keypair,
+    resource: &Pubkey,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::cancel_auction_instruction(
+            *program_id,
+            payer.pubkey(),
+            CancelAuctionArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
 pub async fn start_auction(
     banks_client: &mut BanksClient,
     program_id: &Pubkey,
@@ -237,10 +585,18 @@ pub async fn start_auction(
     payer: &Keypair,
     resource: &Pubkey,
 ) -> Result<(), TransportError> {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, program_id);
     let transaction = Transaction::new_signed_with_payer(
         &[instruction::start_auction_instruction(
             *program_id,
             payer.pubkey(),
+            Some(auction_extended_pubkey),
             StartAuctionArgs {
                 resource: *resource,
             },
@@ -289,53 +645,59 @@ pub async fn place_bid(
 }
 
 #[allow(clippy::too_many_arguments)]
-pub async fn cancel_bid(
+pub async fn place_offer(
     banks_client: &mut BanksClient,
     recent_blockhash: &Hash,
     program_id: &Pubkey,
     payer: &Keypair,
     bidder: &Keypair,
     bidder_spl_account: &Keypair,
+    transfer_authority: &Keypair,
     resource: &Pubkey,
     mint: &Pubkey,
+    amount: u64,
 ) -> Result<(), TransportError> {
     let transaction = Transaction::new_signed_with_payer(
-        &[instruction::cancel_bid_instruction(
+        &[instruction::place_offer_instruction(
             *program_id,
             bidder.pubkey(),
             bidder.pubkey(),
             bidder_spl_account.pubkey(),
             *mint,
-            CancelBidArgs {
+            transfer_authority.pubkey(),
+            payer.pubkey(),
+            None,
+            PlaceOfferArgs {
+                amount,
                 resource: *resource,
+                allowlist_proof: vec![],
             },
         )],
         Some(&payer.pubkey()),
-        &[bidder, payer],
+        &[bidder, transfer_authority, payer],
         *recent_blockhash,
     );
     banks_client.process_transaction(transaction).await?;
     Ok(())
 }
 
-pub async fn approve(
+pub async fn accept_offer(
     banks_client: &mut BanksClient,
+    program_id: &Pubkey,
     recent_blockhash: &Hash,
     payer: &Keypair,
-    transfer_authority: &Pubkey,
-    spl_wallet: &Keypair,
-    amount: u64,
+    resource: &Pubkey,
+    bidder: &Pubkey,
 ) -> Result<(), TransportError> {
     let transaction = Transaction::new_signed_with_payer(
-        &[spl_token::instruction::approve(
-            &spl_token::id(),
-            &spl_wallet.pubkey(),
-            transfer_authority,
-            &payer.pubkey(),
-            &[&payer.pubkey()],
-            amount,
-        )
-        .unwrap()],
+        &[instruction::accept_offer_instruction(
+            *program_id,
+            payer.pubkey(),
+            AcceptOfferArgs {
+                resource: *resource,
+                bidder: *bidder,
+            },
+        )],
         Some(&payer.pubkey()),
         &[payer],
         *recent_blockhash,
@@ -345,32 +707,485 @@ pub async fn approve(
 }
 
 #[allow(clippy::too_many_arguments)]
-pub async fn claim_bid(
+pub async fn place_bid_v2(
     banks_client: &mut BanksClient,
     recent_blockhash: &Hash,
     program_id: &Pubkey,
     payer: &Keypair,
-    authority: &Keypair,
     bidder: &Keypair,
     bidder_spl_account: &Keypair,
-    seller: &Pubkey,
+    transfer_authority: &Keypair,
     resource: &Pubkey,
     mint: &Pubkey,
+    amount: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+    bid_book: bool,
 ) -> Result<(), TransportError> {
     let transaction = Transaction::new_signed_with_payer(
-        &[instruction::claim_bid_instruction(
+        &[instruction::place_bid_instruction_v2(
             *program_id,
-            *seller,
-            authority.pubkey(),
+            bidder.pubkey(),
             bidder.pubkey(),
             bidder_spl_account.pubkey(),
             *mint,
-            ClaimBidArgs {
+            transfer_authority.pubkey(),
+            payer.pubkey(),
+            None,
+            bid_book,
+            PlaceBidArgsV2 {
+                amount,
                 resource: *resource,
+                allowlist_proof,
             },
         )],
         Some(&payer.pubkey()),
-        &[payer, authority],
+        &[bidder, transfer_authority, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn commit_bid(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    transfer_authority: &Keypair,
+    resource: &Pubkey,
+    mint: &Pubkey,
+    deposit: u64,
+    commitment: Hash,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::commit_bid_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            *mint,
+            transfer_authority.pubkey(),
+            payer.pubkey(),
+            None,
+            CommitBidArgs {
+                deposit,
+                commitment,
+                resource: *resource,
+                allowlist_proof: vec![],
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, transfer_authority, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn cancel_bid(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    resource: &Pubkey,
+    mint: &Pubkey,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::cancel_bid_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            *mint,
+            false,
+            CancelBidArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_bid_book(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    recent_blockhash: &Hash,
+    payer: &Keypair,
+    resource: &Pubkey,
+    capacity: u64,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_bid_book_instruction(
+            *program_id,
+            payer.pubkey(),
+            payer.pubkey(),
+            CreateBidBookArgs {
+                resource: *resource,
+                capacity,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn cancel_bid_with_book(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    resource: &Pubkey,
+    mint: &Pubkey,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::cancel_bid_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            *mint,
+            true,
+            CancelBidArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn close_bidder_accounts(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    resource: &Pubkey,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::close_bidder_accounts_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            CloseBidderAccountsArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn approve(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    payer: &Keypair,
+    transfer_authority: &Pubkey,
+    spl_wallet: &Keypair,
+    amount: u64,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[spl_token::instruction::approve(
+            &spl_token::id(),
+            &spl_wallet.pubkey(),
+            transfer_authority,
+            &payer.pubkey(),
+            &[&payer.pubkey()],
+            amount,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn claim_bid(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    authority: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    seller: &Pubkey,
+    resource: &Pubkey,
+    mint: &Pubkey,
+) -> Result<(), TransportError> {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended, _) = Pubkey::find_program_address(seeds, program_id);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::claim_bid_instruction(
+            *program_id,
+            *seller,
+            authority.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            *mint,
+            auction_extended,
+            None,
+            ClaimBidArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn claim_bid_with_refund(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    authority: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    bidder_token: &Pubkey,
+    seller: &Pubkey,
+    resource: &Pubkey,
+    mint: &Pubkey,
+) -> Result<(), TransportError> {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended, _) = Pubkey::find_program_address(seeds, program_id);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::claim_bid_instruction(
+            *program_id,
+            *seller,
+            authority.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            *mint,
+            auction_extended,
+            Some(*bidder_token),
+            ClaimBidArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn return_deposit(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    authority: &Keypair,
+    bidder: &Keypair,
+    bidder_spl_account: &Keypair,
+    bidder_token: &Pubkey,
+    resource: &Pubkey,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::return_deposit_instruction(
+            *program_id,
+            *bidder_token,
+            authority.pubkey(),
+            bidder.pubkey(),
+            bidder_spl_account.pubkey(),
+            ReturnDepositArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_native_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    max_winners: usize,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            *program_id,
+            payer.pubkey(),
+            CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: None,
+                end_auction_gap: None,
+                resource: *resource,
+                token_mint: spl_token::native_mint::id(),
+                winners: WinnerLimit::Capped(max_winners),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                sealed_bid: None,
+                dutch_auction: None,
+                reserve_price: None,
+                anti_snipe: None,
+                uniform_price: false,
+                bidder_gate: None,
+                bid_deposit: None,
+                start_at: None,
+                native_sol: true,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+/// Places a bid in a native SOL auction, paid from the bidder's wallet into their pot.
+#[allow(clippy::too_many_arguments)]
+pub async fn place_native_bid(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    auction: &Pubkey,
+    resource: &Pubkey,
+    amount: u64,
+) -> Result<(), TransportError> {
+    let bidder_pot = instruction::bidder_pot_pubkey(*program_id, *auction, bidder.pubkey());
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::place_bid_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_pot,
+            spl_token::native_mint::id(),
+            bidder.pubkey(),
+            payer.pubkey(),
+            PlaceBidArgs {
+                amount,
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn cancel_native_bid(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Keypair,
+    auction: &Pubkey,
+    resource: &Pubkey,
+) -> Result<(), TransportError> {
+    let bidder_pot = instruction::bidder_pot_pubkey(*program_id, *auction, bidder.pubkey());
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::cancel_bid_instruction(
+            *program_id,
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_pot,
+            spl_token::native_mint::id(),
+            false,
+            CancelBidArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn claim_native_bid(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &Pubkey,
+    seller: &Pubkey,
+    auction: &Pubkey,
+    resource: &Pubkey,
+) -> Result<(), TransportError> {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended, _) = Pubkey::find_program_address(seeds, program_id);
+    let bidder_pot = instruction::bidder_pot_pubkey(*program_id, *auction, *bidder);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::claim_bid_instruction(
+            *program_id,
+            *seller,
+            payer.pubkey(),
+            *bidder,
+            bidder_pot,
+            spl_token::native_mint::id(),
+            auction_extended,
+            None,
+            ClaimBidArgs {
+                resource: *resource,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
         *recent_blockhash,
     );
     banks_client.process_transaction(transaction).await?;
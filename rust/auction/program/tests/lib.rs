@@ -1,7 +1,7 @@
 #![allow(warnings)]
 
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{borsh::try_from_slice_unchecked, instruction::InstructionError};
+use solana_program::{borsh::try_from_slice_unchecked, hash, instruction::InstructionError};
 use solana_program_test::*;
 use solana_sdk::program_pack::Pack;
 use solana_sdk::{
@@ -18,10 +18,12 @@ use metaplex_auction::{
     errors::AuctionError,
     instruction,
     processor::{
-        process_instruction, AuctionData, AuctionState, Bid, BidState, BidderPot, CancelBidArgs,
-        CreateAuctionArgs, PlaceBidArgs, PriceFloor, StartAuctionArgs, WinnerLimit,
+        process_instruction, AntiSnipeSettings, AuctionData, AuctionDataExtended, BidDeposit,
+        BidderGate, AuctionState, Bid, BidCommitment,
+        BidState, BidderPot, CancelBidArgs, CreateAuctionArgs, DutchAuctionSettings, DutchDecay,
+        PlaceBidArgs, PriceFloor, ReservePrice, SealedBidSettings, StartAuctionArgs, WinnerLimit,
     },
-    PREFIX,
+    EXTENDED, PREFIX, SEALED,
 };
 use std::mem;
 
@@ -1129,3 +1131,1963 @@ async fn test_cancel_bid_with_instant_sale_price() {
         }
     }
 }
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_sealed_bid_commit() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut banks_client, &payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Bidding runs for an hour, the last ten minutes of which are for reveals.
+    helpers::create_sealed_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        1,
+        3600,
+        SealedBidSettings {
+            reveal_period: 600,
+            vickrey: true,
+        },
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    let bidder = Keypair::new();
+    let auction_spl_pot = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &bidder,
+        &mint,
+        &payer.pubkey(),
+    )
+    .await
+    .unwrap();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &auction_spl_pot,
+        &mint,
+        &auction_pubkey,
+    )
+    .await
+    .unwrap();
+    helpers::mint_tokens(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &mint,
+        &bidder.pubkey(),
+        &mint_manager,
+        10_000_000,
+    )
+    .await
+    .unwrap();
+
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        &mut banks_client,
+        &recent_blockhash,
+        &payer,
+        &transfer_authority.pubkey(),
+        &bidder,
+        8000,
+    )
+    .await
+    .expect("approve");
+
+    // Open bids are refused on a sealed auction.
+    helpers::place_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidder,
+        &auction_spl_pot,
+        &transfer_authority,
+        &resource,
+        &mint,
+        5000,
+    )
+    .await
+    .expect_err("place_bid");
+
+    let commitment = solana_program::hash::hashv(&[
+        bidder.pubkey().as_ref(),
+        &5000u64.to_be_bytes(),
+        &42u64.to_be_bytes(),
+    ]);
+    helpers::commit_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidder,
+        &auction_spl_pot,
+        &transfer_authority,
+        &resource,
+        &mint,
+        8000,
+        commitment,
+    )
+    .await
+    .expect("commit_bid");
+
+    // The whole deposit sits in the pot, but no bid is ranked until it is revealed.
+    let balance = helpers::get_token_balance(&mut banks_client, &auction_spl_pot.pubkey()).await;
+    assert_eq!(balance, 8000);
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &auction_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.num_winners(), 0);
+
+    let (commitment_pubkey, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_pubkey.as_ref(),
+            bidder.pubkey().as_ref(),
+            SEALED.as_bytes(),
+        ],
+        &program_id,
+    );
+    let bid_commitment: BidCommitment = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &commitment_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(bid_commitment.commitment, commitment);
+    assert!(!bid_commitment.revealed);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_dutch_auction_sells_out() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut banks_client, &payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Two lots, with the price dropping hourly so it holds at 5000 for the whole test.
+    helpers::create_dutch_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        2,
+        36000,
+        DutchAuctionSettings {
+            start_price: 5000,
+            floor_price: 1000,
+            decay: DutchDecay::Linear(500),
+            step_interval: 3600,
+        },
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    let mut bidders = vec![];
+    for _ in 0..3 {
+        let bidder = Keypair::new();
+        let auction_spl_pot = Keypair::new();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &bidder,
+            &mint,
+            &payer.pubkey(),
+        )
+        .await
+        .unwrap();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &auction_spl_pot,
+            &mint,
+            &auction_pubkey,
+        )
+        .await
+        .unwrap();
+        helpers::mint_tokens(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &mint,
+            &bidder.pubkey(),
+            &mint_manager,
+            10_000_000,
+        )
+        .await
+        .unwrap();
+        bidders.push((bidder, auction_spl_pot));
+    }
+
+    // Below the current price, over it, at it, and once everything is sold.
+    let bids = [(0, 4000, false), (0, 6000, true), (1, 5000, true), (2, 7000, false)];
+    for (bidder, amount, succeeds) in bids.iter() {
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut banks_client,
+            &recent_blockhash,
+            &payer,
+            &transfer_authority.pubkey(),
+            &bidders[*bidder].0,
+            *amount,
+        )
+        .await
+        .expect("approve");
+
+        let result = helpers::place_bid(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidders[*bidder].0,
+            &bidders[*bidder].1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await;
+        assert_eq!(result.is_ok(), *succeeds);
+    }
+
+    // Both buyers paid the current price, not what they offered.
+    for (bidder, _) in bidders.iter().take(2) {
+        let balance = helpers::get_token_balance(&mut banks_client, &bidder.pubkey()).await;
+        assert_eq!(balance, 10_000_000 - 5000);
+    }
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &auction_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.state, AuctionState::Ended);
+    assert_eq!(auction.winner_at(0), Some(bidders[0].0.pubkey()));
+    assert_eq!(auction.winner_at(1), Some(bidders[1].0.pubkey()));
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_raise_bid() {
+    let (
+        program_id,
+        mut banks_client,
+        bidders,
+        payer,
+        resource,
+        mint,
+        _,
+        auction_pubkey,
+        recent_blockhash,
+    ) = setup_auction(true, 2, None, PriceFloor::None([0; 32]), Some(0), None).await;
+
+    let pre_balance = helpers::get_token_balance(&mut banks_client, &bidders[0].0.pubkey()).await;
+
+    // Bid, get outbid, then raise back over the top; equal and lower raises are rejected.
+    let bids = [
+        (0, 3000, true),
+        (1, 4000, true),
+        (0, 3000, false),
+        (0, 2000, false),
+        (0, 5000, true),
+    ];
+    for (bidder, amount, succeeds) in bids.iter() {
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut banks_client,
+            &recent_blockhash,
+            &payer,
+            &transfer_authority.pubkey(),
+            &bidders[*bidder].0,
+            *amount,
+        )
+        .await
+        .expect("approve");
+
+        let result = helpers::place_bid(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidders[*bidder].0,
+            &bidders[*bidder].1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await;
+        assert_eq!(result.is_ok(), *succeeds);
+    }
+
+    // Only the difference was taken on the raise, so the pot holds the new total.
+    let post_balance = (
+        helpers::get_token_balance(&mut banks_client, &bidders[0].0.pubkey()).await,
+        helpers::get_token_balance(&mut banks_client, &bidders[0].1.pubkey()).await,
+    );
+    assert_eq!(post_balance.0, pre_balance - 5000);
+    assert_eq!(post_balance.1, 5000);
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &auction_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.num_winners(), 2);
+    assert_eq!(auction.winner_at(0), Some(bidders[0].0.pubkey()));
+    assert_eq!(auction.winner_at(1), Some(bidders[1].0.pubkey()));
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_reserve_not_met() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut banks_client, &payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    helpers::create_reserve_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        1,
+        ReservePrice {
+            price: PriceFloor::MinimumPrice([5000, 0, 0, 0]),
+            failed: false,
+        },
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    let bidder = Keypair::new();
+    let auction_spl_pot = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &bidder,
+        &mint,
+        &payer.pubkey(),
+    )
+    .await
+    .unwrap();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &auction_spl_pot,
+        &mint,
+        &auction_pubkey,
+    )
+    .await
+    .unwrap();
+    helpers::mint_tokens(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &mint,
+        &bidder.pubkey(),
+        &mint_manager,
+        10_000_000,
+    )
+    .await
+    .unwrap();
+
+    // Bids under the reserve are still taken while the auction runs.
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        &mut banks_client,
+        &recent_blockhash,
+        &payer,
+        &transfer_authority.pubkey(),
+        &bidder,
+        4000,
+    )
+    .await
+    .expect("approve");
+    helpers::place_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidder,
+        &auction_spl_pot,
+        &transfer_authority,
+        &resource,
+        &mint,
+        4000,
+    )
+    .await
+    .expect("place_bid");
+
+    helpers::end_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .expect("end_auction");
+
+    // The auction failed, so the only bidder is not a winner.
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &auction_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    let auction_extended: AuctionDataExtended = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &auction_extended_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.state, AuctionState::Ended);
+    assert_eq!(auction.num_winners(), 0);
+    assert_eq!(auction_extended.reserve_price.unwrap().failed, true);
+
+    // And can take the bid back.
+    helpers::cancel_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidder,
+        &auction_spl_pot,
+        &resource,
+        &mint,
+    )
+    .await
+    .expect("cancel_bid");
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &bidder.pubkey()).await,
+        10_000_000
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_anti_snipe_extends_end() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut banks_client, &payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Every bid lands inside the window, but the end can only move 30 seconds in total.
+    helpers::create_anti_snipe_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        1,
+        600,
+        AntiSnipeSettings {
+            window: 3600,
+            extension: 7200,
+            max_extension: 30,
+            extended_by: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &auction_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    let original_end = auction.ended_at.unwrap();
+
+    for amount in [1000, 2000].iter() {
+        let bidder = Keypair::new();
+        let auction_spl_pot = Keypair::new();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &bidder,
+            &mint,
+            &payer.pubkey(),
+        )
+        .await
+        .unwrap();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &auction_spl_pot,
+            &mint,
+            &auction_pubkey,
+        )
+        .await
+        .unwrap();
+        helpers::mint_tokens(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &mint,
+            &bidder.pubkey(),
+            &mint_manager,
+            10_000_000,
+        )
+        .await
+        .unwrap();
+
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut banks_client,
+            &recent_blockhash,
+            &payer,
+            &transfer_authority.pubkey(),
+            &bidder,
+            *amount,
+        )
+        .await
+        .expect("approve");
+        helpers::place_bid(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidder,
+            &auction_spl_pot,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await
+        .expect("place_bid");
+
+        let auction: AuctionData = try_from_slice_unchecked(
+            &helpers::get_account(&mut banks_client, &auction_pubkey)
+                .await
+                .data,
+        )
+        .unwrap();
+        assert_eq!(auction.ended_at, Some(original_end + 30));
+    }
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_uniform_price_claim() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut banks_client, &payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    helpers::create_uniform_price_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        2,
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    let mut bidders = vec![];
+    for amount in [3000, 5000, 1000].iter() {
+        let bidder = Keypair::new();
+        let auction_spl_pot = Keypair::new();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &bidder,
+            &mint,
+            &payer.pubkey(),
+        )
+        .await
+        .unwrap();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &auction_spl_pot,
+            &mint,
+            &auction_pubkey,
+        )
+        .await
+        .unwrap();
+        helpers::mint_tokens(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &mint,
+            &bidder.pubkey(),
+            &mint_manager,
+            10_000_000,
+        )
+        .await
+        .unwrap();
+
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut banks_client,
+            &recent_blockhash,
+            &payer,
+            &transfer_authority.pubkey(),
+            &bidder,
+            *amount,
+        )
+        .await
+        .expect("approve");
+        helpers::place_bid(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidder,
+            &auction_spl_pot,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await
+        .expect("place_bid");
+        bidders.push((bidder, auction_spl_pot));
+    }
+
+    helpers::end_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .expect("end_auction");
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &auction_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.clearing_price(), 3000);
+
+    let collection = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &collection,
+        &mint,
+        &payer.pubkey(),
+    )
+    .await
+    .unwrap();
+    let refund = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &refund,
+        &mint,
+        &bidders[1].0.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    // The refund can't be left in the pot, so the claim needs the bidder's token account.
+    let err = helpers::claim_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &collection.pubkey(),
+        &resource,
+        &mint,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidRefundAccount as u32)
+        )
+    );
+
+    // The top bidder pays the clearing price and gets the rest back.
+    helpers::claim_bid_with_refund(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &refund.pubkey(),
+        &collection.pubkey(),
+        &resource,
+        &mint,
+    )
+    .await
+    .expect("claim_bid");
+
+    let balance = helpers::get_token_balance(&mut banks_client, &collection.pubkey()).await;
+    assert_eq!(balance, 3000);
+    let balance = helpers::get_token_balance(&mut banks_client, &refund.pubkey()).await;
+    assert_eq!(balance, 2000);
+    let balance = helpers::get_token_balance(&mut banks_client, &bidders[1].1.pubkey()).await;
+    assert_eq!(balance, 0);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_allowlist_with_deposit() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut banks_client, &payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let mut bidders = vec![];
+    for _ in 0..2 {
+        let bidder = Keypair::new();
+        let auction_spl_pot = Keypair::new();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &bidder,
+            &mint,
+            &payer.pubkey(),
+        )
+        .await
+        .unwrap();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &auction_spl_pot,
+            &mint,
+            &auction_pubkey,
+        )
+        .await
+        .unwrap();
+        helpers::mint_tokens(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &mint,
+            &bidder.pubkey(),
+            &mint_manager,
+            10_000_000,
+        )
+        .await
+        .unwrap();
+        bidders.push((bidder, auction_spl_pot));
+    }
+
+    // An allowlist of one wallet, so the root is the leaf and the proof is empty.
+    let root = hash::hashv(&[bidders[0].0.pubkey().as_ref()]).to_bytes();
+    helpers::create_gated_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        1,
+        BidderGate::Allowlist(root),
+        Some(BidDeposit {
+            amount: 100,
+            redeem_period: 3600,
+        }),
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    for (index, succeeds) in [(0, true), (1, false)].iter() {
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut banks_client,
+            &recent_blockhash,
+            &payer,
+            &transfer_authority.pubkey(),
+            &bidders[*index].0,
+            1100,
+        )
+        .await
+        .expect("approve");
+
+        let result = helpers::place_bid_v2(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidders[*index].0,
+            &bidders[*index].1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            1000,
+            vec![],
+            false,
+        )
+        .await;
+        assert_eq!(result.is_ok(), *succeeds);
+    }
+
+    // The allowed bidder paid the deposit on top of their bid.
+    let balance = helpers::get_token_balance(&mut banks_client, &bidders[0].1.pubkey()).await;
+    assert_eq!(balance, 1100);
+
+    helpers::end_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    let collection = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &collection,
+        &mint,
+        &payer.pubkey(),
+    )
+    .await
+    .unwrap();
+    helpers::claim_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &collection.pubkey(),
+        &resource,
+        &mint,
+    )
+    .await
+    .expect("claim_bid");
+
+    // The claim leaves the deposit in the pot, and the winner can't cancel it out.
+    let balance = helpers::get_token_balance(&mut banks_client, &bidders[0].1.pubkey()).await;
+    assert_eq!(balance, 100);
+    let err = helpers::cancel_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &resource,
+        &mint,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidState as u32)
+        )
+    );
+
+    let refund = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &refund,
+        &mint,
+        &bidders[0].0.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    // The authority returns the deposit once, after that it is settled.
+    helpers::return_deposit(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &refund.pubkey(),
+        &resource,
+    )
+    .await
+    .expect("return_deposit");
+    let balance = helpers::get_token_balance(&mut banks_client, &refund.pubkey()).await;
+    assert_eq!(balance, 100);
+
+    let err = helpers::return_deposit(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &bidders[0].1.pubkey(),
+        &resource,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::DepositAlreadySettled as u32)
+        )
+    );
+
+    let pot_key = instruction::bidder_pot_pubkey(program_id, auction_pubkey, bidders[0].0.pubkey());
+    let pot: BidderPot =
+        try_from_slice_unchecked(&helpers::get_account(&mut banks_client, &pot_key).await.data)
+            .unwrap();
+    assert!(pot.emptied && pot.deposit_settled);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_close_bidder_accounts() {
+    let (
+        program_id,
+        mut banks_client,
+        bidders,
+        payer,
+        resource,
+        mint,
+        _,
+        auction_pubkey,
+        recent_blockhash,
+    ) = setup_auction(true, 1, None, PriceFloor::None([0; 32]), None, None).await;
+
+    for (bidder, amount) in [(0, 1000), (1, 2000)].iter() {
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut banks_client,
+            &recent_blockhash,
+            &payer,
+            &transfer_authority.pubkey(),
+            &bidders[*bidder].0,
+            *amount,
+        )
+        .await
+        .expect("approve");
+        helpers::place_bid(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidders[*bidder].0,
+            &bidders[*bidder].1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await
+        .expect("place_bid");
+    }
+
+    // Nothing can be closed while the auction is running.
+    helpers::close_bidder_accounts(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &resource,
+    )
+    .await
+    .expect_err("close_bidder_accounts");
+
+    helpers::end_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .expect("end_auction");
+
+    // A second fee payer keeps these attempts from repeating the transactions around them.
+    let fee_payer = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &fee_payer.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // The unclaimed winner and the loser who hasn't cancelled yet still have bids to settle.
+    for bidder in [0, 1].iter() {
+        helpers::close_bidder_accounts(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &fee_payer,
+            &bidders[*bidder].0,
+            &bidders[*bidder].1,
+            &resource,
+        )
+        .await
+        .expect_err("close_bidder_accounts");
+    }
+
+    helpers::cancel_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &resource,
+        &mint,
+    )
+    .await
+    .expect("cancel_bid");
+
+    let (bidder_meta_pubkey, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_pubkey.as_ref(),
+            bidders[0].0.pubkey().as_ref(),
+            "metadata".as_bytes(),
+        ],
+        &program_id,
+    );
+    let closed_accounts = [bidders[0].2, bidders[0].1.pubkey(), bidder_meta_pubkey];
+    let mut rent = 0;
+    for pubkey in closed_accounts.iter() {
+        rent += helpers::get_account(&mut banks_client, pubkey)
+            .await
+            .lamports;
+    }
+    let pre_lamports = helpers::get_account(&mut banks_client, &bidders[0].0.pubkey())
+        .await
+        .lamports;
+
+    helpers::close_bidder_accounts(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &resource,
+    )
+    .await
+    .expect("close_bidder_accounts");
+
+    // All three accounts are gone and their rent went back to the bidder.
+    for pubkey in closed_accounts.iter() {
+        assert!(banks_client.get_account(*pubkey).await.unwrap().is_none());
+    }
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &bidders[0].0.pubkey())
+            .await
+            .lamports,
+        pre_lamports + rent
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_bid_book_backfills_winners() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (mint_keypair, mint_manager) =
+        helpers::create_mint(&mut banks_client, &payer, &recent_blockhash)
+            .await
+            .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Four winners keep eight bids in the bid state, the book takes the rest.
+    helpers::create_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &resource,
+        &mint,
+        4,
+        "Some name",
+        None,
+        PriceFloor::None([0; 32]),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    helpers::create_bid_book(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+        4,
+    )
+    .await
+    .unwrap();
+    helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    // Ten bids of 100 to 1000, the two lowest get pruned into the book.
+    let mut bidders = vec![];
+    for n in 0..10 {
+        let bidder = Keypair::new();
+        let auction_spl_pot = Keypair::new();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &bidder,
+            &mint,
+            &payer.pubkey(),
+        )
+        .await
+        .unwrap();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &auction_spl_pot,
+            &mint,
+            &auction_pubkey,
+        )
+        .await
+        .unwrap();
+        helpers::mint_tokens(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &mint,
+            &bidder.pubkey(),
+            &mint_manager,
+            10_000_000,
+        )
+        .await
+        .unwrap();
+
+        let amount = (n + 1) * 100;
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut banks_client,
+            &recent_blockhash,
+            &payer,
+            &transfer_authority.pubkey(),
+            &bidder,
+            amount,
+        )
+        .await
+        .expect("approve");
+        helpers::place_bid_v2(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidder,
+            &auction_spl_pot,
+            &transfer_authority,
+            &resource,
+            &mint,
+            amount,
+            vec![],
+            true,
+        )
+        .await
+        .expect("place_bid_v2");
+
+        bidders.push((bidder, auction_spl_pot));
+    }
+
+    // Bidding without the book is refused.
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        &mut banks_client,
+        &recent_blockhash,
+        &payer,
+        &transfer_authority.pubkey(),
+        &bidders[0].0,
+        2000,
+    )
+    .await
+    .expect("approve");
+    helpers::place_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &transfer_authority,
+        &resource,
+        &mint,
+        2000,
+    )
+    .await
+    .expect_err("place_bid");
+
+    // The top six cancel, and the two pruned bids move back up.
+    for bidder in bidders[4..].iter() {
+        helpers::cancel_bid_with_book(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidder.0,
+            &bidder.1,
+            &resource,
+            &mint,
+        )
+        .await
+        .expect("cancel_bid_with_book");
+    }
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &auction_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.num_winners(), 4);
+    for (index, bidder) in bidders[..4].iter().rev().enumerate() {
+        assert_eq!(auction.winner_at(index), Some(bidder.0.pubkey()));
+    }
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_scheduled_start() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (mint_keypair, _) = helpers::create_mint(&mut banks_client, &payer, &recent_blockhash)
+        .await
+        .unwrap();
+    let mint = mint_keypair.pubkey();
+
+    // One auction scheduled far in the future, and one whose start time has already passed.
+    let early = Pubkey::new_unique();
+    let due = Pubkey::new_unique();
+    for (resource, start_at) in [(early, i64::MAX), (due, 1)].iter() {
+        helpers::create_scheduled_auction(
+            &mut banks_client,
+            &program_id,
+            &payer,
+            &recent_blockhash,
+            resource,
+            &mint,
+            1,
+            *start_at,
+        )
+        .await
+        .unwrap();
+    }
+
+    let err = helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &early,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::AuctionStartTooEarly as u32)
+        )
+    );
+
+    helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &due,
+    )
+    .await
+    .expect("start_auction");
+
+    // The end is measured from the real start.
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), due.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &auction_pubkey)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.state, AuctionState::Started);
+    assert!(auction.ended_at.unwrap() > 60);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_native_sol_bids() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test =
+        ProgramTest::new("metaplex_auction", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    // Native pots only make sense for SOL.
+    let other_resource = Pubkey::new_unique();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_auction_instruction_v3(
+            program_id,
+            payer.pubkey(),
+            metaplex_auction::processor::CreateAuctionArgsV3 {
+                authority: payer.pubkey(),
+                end_auction_at: None,
+                end_auction_gap: None,
+                resource: other_resource,
+                token_mint: Pubkey::new_unique(),
+                winners: WinnerLimit::Capped(1),
+                price_floor: PriceFloor::None([0; 32]),
+                gap_tick_size_percentage: None,
+                tick_size: None,
+                name: None,
+                instant_sale_price: None,
+                sealed_bid: None,
+                dutch_auction: None,
+                reserve_price: None,
+                anti_snipe: None,
+                uniform_price: false,
+                bidder_gate: None,
+                bid_deposit: None,
+                start_at: None,
+                native_sol: true,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    let err = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AuctionError::InvalidNativeSolSettings as u32)
+        )
+    );
+
+    helpers::create_native_auction(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &recent_blockhash,
+        &resource,
+        1,
+    )
+    .await
+    .unwrap();
+    helpers::start_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    // Bidders only hold SOL, there are no token accounts involved.
+    let bidders = [Keypair::new(), Keypair::new()];
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::transfer(&payer.pubkey(), &bidders[0].pubkey(), 5_000_000_000),
+            system_instruction::transfer(&payer.pubkey(), &bidders[1].pubkey(), 5_000_000_000),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    for (bidder, amount) in [(0, 1_000_000_000), (1, 2_000_000_000)].iter() {
+        helpers::place_native_bid(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidders[*bidder],
+            &auction_pubkey,
+            &resource,
+            *amount,
+        )
+        .await
+        .expect("place_native_bid");
+
+        // The pot holds the bid on top of its rent.
+        let pot =
+            instruction::bidder_pot_pubkey(program_id, auction_pubkey, bidders[*bidder].pubkey());
+        let pot_account = helpers::get_account(&mut banks_client, &pot).await;
+        let rent = banks_client.get_rent().await.unwrap();
+        assert_eq!(
+            pot_account.lamports,
+            rent.minimum_balance(pot_account.data.len()) + amount
+        );
+        assert_eq!(
+            helpers::get_account(&mut banks_client, &bidders[*bidder].pubkey())
+                .await
+                .lamports,
+            5_000_000_000 - amount
+        );
+    }
+
+    helpers::end_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .expect("end_auction");
+
+    // The loser gets their lamports straight back to their wallet.
+    helpers::cancel_native_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0],
+        &auction_pubkey,
+        &resource,
+    )
+    .await
+    .expect("cancel_native_bid");
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &bidders[0].pubkey())
+            .await
+            .lamports,
+        5_000_000_000
+    );
+
+    // And the winner's bid goes to the seller's wallet.
+    let seller = Pubkey::new_unique();
+    helpers::claim_native_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[1].pubkey(),
+        &seller,
+        &auction_pubkey,
+        &resource,
+    )
+    .await
+    .expect("claim_native_bid");
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &seller)
+            .await
+            .lamports,
+        2_000_000_000
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_cancel_auction_refunds_winners() {
+    let (
+        program_id,
+        mut banks_client,
+        bidders,
+        payer,
+        resource,
+        mint,
+        _,
+        auction_pubkey,
+        recent_blockhash,
+    ) = setup_auction(true, 1, None, PriceFloor::None([0; 32]), None, None).await;
+
+    for (bidder, amount) in [(0, 1000), (1, 2000)].iter() {
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut banks_client,
+            &recent_blockhash,
+            &payer,
+            &transfer_authority.pubkey(),
+            &bidders[*bidder].0,
+            *amount,
+        )
+        .await
+        .expect("approve");
+        helpers::place_bid(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidders[*bidder].0,
+            &bidders[*bidder].1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await
+        .expect("place_bid");
+    }
+
+    // Only the authority may cancel.
+    let stranger = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &stranger.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    helpers::cancel_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &stranger,
+        &resource,
+    )
+    .await
+    .expect_err("cancel_auction");
+
+    helpers::cancel_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .expect("cancel_auction");
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &banks_client
+            .get_account(auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.state, AuctionState::Cancelled);
+    assert_eq!(auction.bid_state.num_winners(), 0);
+
+    // A cancelled auction can neither be ended nor take more bids.
+    helpers::end_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .expect_err("end_auction");
+
+    // The bidder who was winning gets their whole bid back.
+    let pre_balance = helpers::get_token_balance(&mut banks_client, &bidders[1].0.pubkey()).await;
+    helpers::cancel_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &resource,
+        &mint,
+    )
+    .await
+    .expect("cancel_bid");
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &bidders[1].0.pubkey()).await,
+        pre_balance + 2000
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &bidders[1].1.pubkey()).await,
+        0
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_offers_after_auction_without_bids() {
+    let (
+        program_id,
+        mut banks_client,
+        bidders,
+        payer,
+        resource,
+        mint,
+        _,
+        auction_pubkey,
+        recent_blockhash,
+    ) = setup_auction(true, 1, None, PriceFloor::None([0; 32]), None, None).await;
+
+    // No offers while the auction is running.
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        &mut banks_client,
+        &recent_blockhash,
+        &payer,
+        &transfer_authority.pubkey(),
+        &bidders[0].0,
+        1000,
+    )
+    .await
+    .expect("approve");
+    helpers::place_offer(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &transfer_authority,
+        &resource,
+        &mint,
+        1000,
+    )
+    .await
+    .expect_err("place_offer");
+
+    helpers::end_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .expect("end_auction");
+
+    for (bidder, amount) in [(0, 1000), (1, 2000)].iter() {
+        let transfer_authority = Keypair::new();
+        helpers::approve(
+            &mut banks_client,
+            &recent_blockhash,
+            &payer,
+            &transfer_authority.pubkey(),
+            &bidders[*bidder].0,
+            *amount,
+        )
+        .await
+        .expect("approve");
+        helpers::place_offer(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidders[*bidder].0,
+            &bidders[*bidder].1,
+            &transfer_authority,
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await
+        .expect("place_offer");
+        assert_eq!(
+            helpers::get_token_balance(&mut banks_client, &bidders[*bidder].1.pubkey()).await,
+            *amount
+        );
+    }
+
+    // Only the authority can accept, and the auction has a single spot to fill.
+    let stranger = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &stranger.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    helpers::accept_offer(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &stranger,
+        &resource,
+        &bidders[0].0.pubkey(),
+    )
+    .await
+    .expect_err("accept_offer");
+    helpers::accept_offer(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+        &bidders[0].0.pubkey(),
+    )
+    .await
+    .expect("accept_offer");
+    helpers::accept_offer(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+        &bidders[1].0.pubkey(),
+    )
+    .await
+    .expect_err("accept_offer");
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &banks_client
+            .get_account(auction_pubkey)
+            .await
+            .expect("get_account")
+            .expect("account not found")
+            .data,
+    )
+    .unwrap();
+    assert_eq!(auction.winner_at(0), Some(bidders[0].0.pubkey()));
+    assert_eq!(auction.bid_state.amount(0), 1000);
+
+    // The accepted offer is a winning bid now and stays put, the other one is refunded.
+    helpers::cancel_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &resource,
+        &mint,
+    )
+    .await
+    .expect_err("cancel_bid");
+
+    let pre_balance = helpers::get_token_balance(&mut banks_client, &bidders[1].0.pubkey()).await;
+    helpers::cancel_bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[1].0,
+        &bidders[1].1,
+        &resource,
+        &mint,
+    )
+    .await
+    .expect("cancel_bid");
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &bidders[1].0.pubkey()).await,
+        pre_balance + 2000
+    );
+}
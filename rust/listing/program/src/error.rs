@@ -0,0 +1,109 @@
+//! Error types
+
+use {
+    num_derive::FromPrimitive,
+    solana_program::{
+        decode_error::DecodeError,
+        msg,
+        program_error::{PrintProgramError, ProgramError},
+    },
+    thiserror::Error,
+};
+
+/// Errors that may be returned by the Listing program.
+#[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum ListingError {
+    /// Invalid instruction data passed in.
+    #[error("Failed to unpack instruction data")]
+    InstructionUnpackError,
+
+    /// Uninitialized
+    #[error("Uninitialized")]
+    Uninitialized,
+
+    /// Account does not have correct owner
+    #[error("Account does not have correct owner")]
+    IncorrectOwner,
+
+    /// NumericalOverflowError
+    #[error("NumericalOverflowError")]
+    NumericalOverflowError,
+
+    /// Token transfer failed
+    #[error("Token transfer failed")]
+    TokenTransferFailed,
+
+    /// Derived key invalid
+    #[error("Derived key invalid")]
+    DerivedKeyInvalid,
+
+    /// Data type mismatch
+    #[error("Data type mismatch")]
+    DataTypeMismatch,
+
+    /// Token mint does not match the listing
+    #[error("Token mint does not match the listing")]
+    MintMismatch,
+
+    /// Token is already listed by this seller
+    #[error("Token is already listed by this seller")]
+    AlreadyListed,
+
+    /// Seller does not match the listing
+    #[error("Seller does not match the listing")]
+    SellerMismatch,
+
+    /// Escrow does not match the listing
+    #[error("Escrow does not match the listing")]
+    EscrowMismatch,
+
+    /// The escrow account must be owned by the listing
+    #[error("The escrow account must be owned by the listing")]
+    EscrowOwnerMismatch,
+
+    /// The escrow account must be empty when the listing is made
+    #[error("The escrow account must be empty when the listing is made")]
+    EscrowNotEmpty,
+
+    /// Delegate should be none
+    #[error("Delegate should be none")]
+    DelegateShouldBeNone,
+
+    /// Close authority should be none
+    #[error("Close authority should be none")]
+    CloseAuthorityShouldBeNone,
+
+    /// Price does not match the listing
+    #[error("Price does not match the listing")]
+    PriceMismatch,
+
+    /// Creators passed in do not match the creators on the metadata
+    #[error("Creators passed in do not match the creators on the metadata")]
+    CreatorMismatch,
+
+    /// Metadata does not match the mint of the listing
+    #[error("Metadata does not match the mint of the listing")]
+    MetadataMismatch,
+
+    /// Token metadata program does not match
+    #[error("Token metadata program does not match")]
+    TokenMetadataProgramMismatch,
+}
+
+impl PrintProgramError for ListingError {
+    fn print<E>(&self) {
+        msg!(&self.to_string());
+    }
+}
+
+impl From<ListingError> for ProgramError {
+    fn from(e: ListingError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for ListingError {
+    fn type_of() -> &'static str {
+        "Listing Error"
+    }
+}
@@ -0,0 +1,155 @@
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct CreateListingArgs {
+    /// Price in lamports.
+    pub price: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct PurchaseArgs {
+    /// Price the buyer agreed to, it must match the listing.
+    pub price: u64,
+}
+
+/// Instructions supported by the Listing program.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub enum ListingInstruction {
+    /// Lists one token from the seller's token account at a fixed price in lamports. The token moves
+    /// into an escrow token account owned by the listing until it is bought or the listing is cancelled.
+    ///
+    ///   0. `[writable]` Listing, pda of ['listing', program id, mint, seller wallet]
+    ///   1. `[writable]` Escrow token account of the mint, owned by the listing, empty, with no
+    ///      delegate or close authority
+    ///   2. `[writable]` Seller token account holding the token
+    ///   3. `[signer]` Seller wallet, owner of the seller token account
+    ///   4. `[]` Mint of the token
+    ///   5. `[]` Metadata of the mint (pda of ['metadata', token metadata program id, mint])
+    ///   6. `[signer]` Payer
+    ///   7. `[]` Token program
+    ///   8. `[]` System program
+    ///   9. `[]` Rent sysvar
+    CreateListing(CreateListingArgs),
+
+    /// Buys a listed token. The price is split between the verified creators on the metadata and the
+    /// seller with the same royalty formula Metaplex uses when paying out auctions: everything goes
+    /// to the creators on the token's first sale, and `seller_fee_basis_points` of it on later sales.
+    /// The first sale also marks the metadata's primary sale as happened. The escrow and listing are
+    /// closed and their rent goes back to the seller.
+    ///
+    ///   0. `[writable]` Listing, pda of ['listing', program id, mint, seller wallet]
+    ///   1. `[writable]` Escrow token account
+    ///   2. `[writable]` Buyer token account of the mint, receives the token
+    ///   3. `[writable, signer]` Buyer wallet, pays the price
+    ///   4. `[writable]` Seller wallet
+    ///   5. `[writable]` Metadata of the mint (pda of ['metadata', token metadata program id, mint])
+    ///   6. `[]` Token program
+    ///   7. `[]` System program
+    ///   8. `[]` Token metadata program
+    ///   9. `[writable]` One wallet per creator on the metadata, in the same order, starting here
+    Purchase(PurchaseArgs),
+
+    /// Takes a listing down, returning the token to the seller and closing the escrow and listing.
+    ///
+    ///   0. `[writable]` Listing, pda of ['listing', program id, mint, seller wallet]
+    ///   1. `[writable]` Escrow token account
+    ///   2. `[writable]` Seller token account of the mint, receives the token
+    ///   3. `[writable, signer]` Seller wallet
+    ///   4. `[]` Token program
+    CancelListing,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_listing_instruction(
+    program_id: Pubkey,
+    listing: Pubkey,
+    escrow: Pubkey,
+    seller_token: Pubkey,
+    seller: Pubkey,
+    mint: Pubkey,
+    metadata: Pubkey,
+    payer: Pubkey,
+    price: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(listing, false),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(seller_token, false),
+            AccountMeta::new_readonly(seller, true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: ListingInstruction::CreateListing(CreateListingArgs { price })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_purchase_instruction(
+    program_id: Pubkey,
+    listing: Pubkey,
+    escrow: Pubkey,
+    buyer_token: Pubkey,
+    buyer: Pubkey,
+    seller: Pubkey,
+    metadata: Pubkey,
+    creators: Vec<Pubkey>,
+    price: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(listing, false),
+        AccountMeta::new(escrow, false),
+        AccountMeta::new(buyer_token, false),
+        AccountMeta::new(buyer, true),
+        AccountMeta::new(seller, false),
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(metaplex_token_metadata::id(), false),
+    ];
+    for creator in creators {
+        accounts.push(AccountMeta::new(creator, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: ListingInstruction::Purchase(PurchaseArgs { price })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn create_cancel_listing_instruction(
+    program_id: Pubkey,
+    listing: Pubkey,
+    escrow: Pubkey,
+    seller_token: Pubkey,
+    seller: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(listing, false),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(seller_token, false),
+            AccountMeta::new(seller, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: ListingInstruction::CancelListing.try_to_vec().unwrap(),
+    }
+}
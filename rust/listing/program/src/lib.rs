@@ -0,0 +1,13 @@
+//! A fixed price listing program for the Solana blockchain, paying creator royalties on every
+//! sale.
+
+pub mod entrypoint;
+pub mod error;
+pub mod instruction;
+pub mod processor;
+pub mod state;
+pub mod utils;
+// Export current sdk types for downstream users building with a different sdk version
+pub use solana_program;
+
+solana_program::declare_id!("9pjYYCLo1hJKZNaXavCAWAA2xBToioCCuLDsChqUsgJW");
@@ -0,0 +1,34 @@
+use {
+    crate::instruction::ListingInstruction,
+    borsh::BorshDeserialize,
+    cancel_listing::process_cancel_listing,
+    create_listing::process_create_listing,
+    purchase::process_purchase,
+    solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey},
+};
+
+pub mod cancel_listing;
+pub mod create_listing;
+pub mod purchase;
+
+pub fn process_instruction<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    input: &[u8],
+) -> ProgramResult {
+    let instruction = ListingInstruction::try_from_slice(input)?;
+    match instruction {
+        ListingInstruction::CreateListing(args) => {
+            msg!("Instruction: Create Listing");
+            process_create_listing(program_id, accounts, args.price)
+        }
+        ListingInstruction::Purchase(args) => {
+            msg!("Instruction: Purchase");
+            process_purchase(program_id, accounts, args.price)
+        }
+        ListingInstruction::CancelListing => {
+            msg!("Instruction: Cancel Listing");
+            process_cancel_listing(program_id, accounts)
+        }
+    }
+}
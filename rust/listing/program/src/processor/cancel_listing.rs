@@ -0,0 +1,89 @@
+use {
+    crate::{
+        error::ListingError,
+        state::{Key, Listing, PREFIX},
+        utils::{
+            assert_initialized, assert_owned_by, assert_signer, close_program_account,
+            spl_token_close_account, spl_token_transfer,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+};
+
+pub fn process_cancel_listing<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let listing_info = next_account_info(account_info_iter)?;
+    let escrow_info = next_account_info(account_info_iter)?;
+    let seller_token_info = next_account_info(account_info_iter)?;
+    let seller_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(seller_info)?;
+    assert_owned_by(listing_info, program_id)?;
+
+    if *token_program_info.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let listing = Listing::from_account_info(listing_info)?;
+    if listing.key != Key::ListingV1 {
+        return Err(ListingError::Uninitialized.into());
+    }
+    if listing.seller != *seller_info.key {
+        return Err(ListingError::SellerMismatch.into());
+    }
+    if listing.escrow != *escrow_info.key {
+        return Err(ListingError::EscrowMismatch.into());
+    }
+
+    let seller_token: Account = assert_initialized(seller_token_info)?;
+    if seller_token.mint != listing.mint {
+        return Err(ListingError::MintMismatch.into());
+    }
+    let escrow: Account = assert_initialized(escrow_info)?;
+
+    let (_, bump) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            listing.mint.as_ref(),
+            listing.seller.as_ref(),
+        ],
+        program_id,
+    );
+    let authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        listing.mint.as_ref(),
+        listing.seller.as_ref(),
+        &[bump],
+    ];
+
+    spl_token_transfer(
+        escrow_info.clone(),
+        seller_token_info.clone(),
+        escrow.amount,
+        listing_info.clone(),
+        authority_seeds,
+        token_program_info.clone(),
+    )?;
+    spl_token_close_account(
+        escrow_info.clone(),
+        seller_info.clone(),
+        listing_info.clone(),
+        authority_seeds,
+        token_program_info.clone(),
+    )?;
+    close_program_account(listing_info, seller_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use {
+    crate::{
+        error::ListingError,
+        state::{Key, Listing, MAX_LISTING_SIZE, PREFIX},
+        utils::{
+            assert_derivation, assert_initialized, assert_metadata_for_mint, assert_owned_by,
+            assert_signer, create_or_allocate_account_raw,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke,
+        program_error::ProgramError,
+        program_option::COption,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+};
+
+pub fn process_create_listing<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    price: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let listing_info = next_account_info(account_info_iter)?;
+    let escrow_info = next_account_info(account_info_iter)?;
+    let seller_token_info = next_account_info(account_info_iter)?;
+    let seller_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(seller_info)?;
+    assert_signer(payer_info)?;
+
+    if *token_program_info.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(escrow_info, &spl_token::id())?;
+    assert_owned_by(seller_token_info, &spl_token::id())?;
+
+    // Royalties are paid out of the metadata's creators, so only tokens with metadata can be listed.
+    assert_metadata_for_mint(metadata_info, mint_info.key)?;
+
+    let listing_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        seller_info.key.as_ref(),
+    ];
+    let bump = assert_derivation(program_id, listing_info, listing_seeds)?;
+
+    if listing_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            listing_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_LISTING_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                mint_info.key.as_ref(),
+                seller_info.key.as_ref(),
+                &[bump],
+            ],
+        )?;
+    } else {
+        assert_owned_by(listing_info, program_id)?;
+        if Listing::from_account_info(listing_info)?.key == Key::ListingV1 {
+            return Err(ListingError::AlreadyListed.into());
+        }
+    }
+
+    let escrow: Account = assert_initialized(escrow_info)?;
+    if escrow.owner != *listing_info.key {
+        return Err(ListingError::EscrowOwnerMismatch.into());
+    }
+    if escrow.mint != *mint_info.key {
+        return Err(ListingError::MintMismatch.into());
+    }
+    if escrow.amount != 0 {
+        return Err(ListingError::EscrowNotEmpty.into());
+    }
+    if escrow.delegate != COption::None {
+        return Err(ListingError::DelegateShouldBeNone.into());
+    }
+    if escrow.close_authority != COption::None {
+        return Err(ListingError::CloseAuthorityShouldBeNone.into());
+    }
+
+    let seller_token: Account = assert_initialized(seller_token_info)?;
+    if seller_token.mint != *mint_info.key {
+        return Err(ListingError::MintMismatch.into());
+    }
+
+    invoke(
+        &spl_token::instruction::transfer(
+            token_program_info.key,
+            seller_token_info.key,
+            escrow_info.key,
+            seller_info.key,
+            &[],
+            1,
+        )?,
+        &[
+            seller_token_info.clone(),
+            escrow_info.clone(),
+            seller_info.clone(),
+            token_program_info.clone(),
+        ],
+    )
+    .map_err(|_| ListingError::TokenTransferFailed)?;
+
+    Listing {
+        key: Key::ListingV1,
+        seller: *seller_info.key,
+        mint: *mint_info.key,
+        escrow: *escrow_info.key,
+        price,
+    }
+    .serialize(&mut *listing_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use {
+    crate::{
+        error::ListingError,
+        state::{Key, Listing, PREFIX},
+        utils::{
+            assert_initialized, assert_metadata_for_mint, assert_owned_by, assert_signer,
+            calculate_creator_payouts, close_program_account, spl_token_close_account,
+            spl_token_transfer,
+        },
+    },
+    metaplex_token_metadata::instruction::update_primary_sale_happened_via_token,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program::{invoke, invoke_signed},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction,
+    },
+    spl_token::state::Account,
+};
+
+fn pay<'a>(
+    buyer_info: &AccountInfo<'a>,
+    recipient_info: &AccountInfo<'a>,
+    system_info: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    invoke(
+        &system_instruction::transfer(buyer_info.key, recipient_info.key, amount),
+        &[
+            buyer_info.clone(),
+            recipient_info.clone(),
+            system_info.clone(),
+        ],
+    )
+}
+
+pub fn process_purchase<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    price: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let listing_info = next_account_info(account_info_iter)?;
+    let escrow_info = next_account_info(account_info_iter)?;
+    let buyer_token_info = next_account_info(account_info_iter)?;
+    let buyer_info = next_account_info(account_info_iter)?;
+    let seller_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(buyer_info)?;
+    assert_owned_by(listing_info, program_id)?;
+
+    if *token_program_info.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if *token_metadata_program_info.key != metaplex_token_metadata::id() {
+        return Err(ListingError::TokenMetadataProgramMismatch.into());
+    }
+
+    let listing = Listing::from_account_info(listing_info)?;
+    if listing.key != Key::ListingV1 {
+        return Err(ListingError::Uninitialized.into());
+    }
+    // The buyer names the price they agreed to, so a relisting can't change it under them.
+    if listing.price != price {
+        return Err(ListingError::PriceMismatch.into());
+    }
+    if listing.seller != *seller_info.key {
+        return Err(ListingError::SellerMismatch.into());
+    }
+    if listing.escrow != *escrow_info.key {
+        return Err(ListingError::EscrowMismatch.into());
+    }
+
+    let buyer_token: Account = assert_initialized(buyer_token_info)?;
+    if buyer_token.mint != listing.mint {
+        return Err(ListingError::MintMismatch.into());
+    }
+    let escrow: Account = assert_initialized(escrow_info)?;
+    let metadata = assert_metadata_for_mint(metadata_info, &listing.mint)?;
+
+    // Creator wallets follow the fixed accounts, in the order they appear on the metadata.
+    let payouts = calculate_creator_payouts(price, &metadata)?;
+    let mut paid_to_creators: u64 = 0;
+    if let Some(creators) = &metadata.data.creators {
+        for (creator, amount) in creators.iter().zip(payouts) {
+            let creator_info = next_account_info(account_info_iter)?;
+            if *creator_info.key != creator.address {
+                return Err(ListingError::CreatorMismatch.into());
+            }
+
+            msg!("Paying creator {} lamports", amount);
+            pay(buyer_info, creator_info, system_info, amount)?;
+            paid_to_creators = paid_to_creators
+                .checked_add(amount)
+                .ok_or(ListingError::NumericalOverflowError)?;
+        }
+    }
+
+    let seller_amount = price
+        .checked_sub(paid_to_creators)
+        .ok_or(ListingError::NumericalOverflowError)?;
+    msg!("Paying seller {} lamports", seller_amount);
+    pay(buyer_info, seller_info, system_info, seller_amount)?;
+
+    let (_, bump) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            listing.mint.as_ref(),
+            listing.seller.as_ref(),
+        ],
+        program_id,
+    );
+    let authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        listing.mint.as_ref(),
+        listing.seller.as_ref(),
+        &[bump],
+    ];
+
+    // Once sold, later sales only pay creators their seller_fee_basis_points. The listing can
+    // flip the flag because it owns the escrow holding the token.
+    if !metadata.primary_sale_happened {
+        invoke_signed(
+            &update_primary_sale_happened_via_token(
+                *token_metadata_program_info.key,
+                *metadata_info.key,
+                *listing_info.key,
+                *escrow_info.key,
+            ),
+            &[
+                metadata_info.clone(),
+                listing_info.clone(),
+                escrow_info.clone(),
+                token_metadata_program_info.clone(),
+            ],
+            &[authority_seeds],
+        )?;
+    }
+
+    spl_token_transfer(
+        escrow_info.clone(),
+        buyer_token_info.clone(),
+        escrow.amount,
+        listing_info.clone(),
+        authority_seeds,
+        token_program_info.clone(),
+    )?;
+    spl_token_close_account(
+        escrow_info.clone(),
+        seller_info.clone(),
+        listing_info.clone(),
+        authority_seeds,
+        token_program_info.clone(),
+    )?;
+    close_program_account(listing_info, seller_info)?;
+
+    Ok(())
+}
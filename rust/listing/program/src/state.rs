@@ -0,0 +1,47 @@
+use {
+    crate::utils::try_from_slice_checked,
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+};
+
+/// prefix used for PDAs to avoid certain collision attacks (https://en.wikipedia.org/wiki/Collision_attack#Chosen-prefix_collision_attack)
+pub const PREFIX: &str = "listing";
+
+pub const MAX_LISTING_SIZE: usize = 1 + // key
+32 + // seller
+32 + // mint
+32 + // escrow
+8 + // price
+50; // padding
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug, Copy)]
+pub enum Key {
+    Uninitialized,
+    ListingV1,
+}
+
+/// A token up for sale at a fixed price. The token sits in the escrow account, a token account
+/// owned by the listing, until it is bought or the listing is cancelled.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct Listing {
+    pub key: Key,
+    /// Wallet that listed the token and gets paid for it.
+    pub seller: Pubkey,
+    /// Mint of the token being sold.
+    pub mint: Pubkey,
+    /// Token account holding the token while it is listed.
+    pub escrow: Pubkey,
+    /// Price in lamports.
+    pub price: u64,
+}
+
+impl Listing {
+    pub fn from_account_info(a: &AccountInfo) -> Result<Listing, ProgramError> {
+        let listing: Listing =
+            try_from_slice_checked(&a.data.borrow_mut(), Key::ListingV1, MAX_LISTING_SIZE)?;
+
+        Ok(listing)
+    }
+}
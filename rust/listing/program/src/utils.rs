@@ -0,0 +1,245 @@
+use {
+    crate::{error::ListingError, state::Key},
+    borsh::BorshDeserialize,
+    metaplex_token_metadata::state::Metadata,
+    solana_program::{
+        account_info::AccountInfo,
+        borsh::try_from_slice_unchecked,
+        entrypoint::ProgramResult,
+        msg,
+        program::{invoke, invoke_signed},
+        program_error::ProgramError,
+        program_pack::{IsInitialized, Pack},
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::{rent::Rent, Sysvar},
+    },
+    std::convert::TryInto,
+};
+
+/// assert initialized account
+pub fn assert_initialized<T: Pack + IsInitialized>(
+    account_info: &AccountInfo,
+) -> Result<T, ProgramError> {
+    let account: T = T::unpack_unchecked(&account_info.data.borrow())?;
+    if !account.is_initialized() {
+        Err(ListingError::Uninitialized.into())
+    } else {
+        Ok(account)
+    }
+}
+
+pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account.owner != owner {
+        Err(ListingError::IncorrectOwner.into())
+    } else {
+        Ok(())
+    }
+}
+
+pub fn assert_signer(account_info: &AccountInfo) -> ProgramResult {
+    if !account_info.is_signer {
+        Err(ProgramError::MissingRequiredSignature)
+    } else {
+        Ok(())
+    }
+}
+
+pub fn assert_derivation(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+    path: &[&[u8]],
+) -> Result<u8, ProgramError> {
+    let (key, bump) = Pubkey::find_program_address(path, program_id);
+    if key != *account.key {
+        return Err(ListingError::DerivedKeyInvalid.into());
+    }
+    Ok(bump)
+}
+
+pub fn try_from_slice_checked<T: BorshDeserialize>(
+    data: &[u8],
+    data_type: Key,
+    data_size: usize,
+) -> Result<T, ProgramError> {
+    if (data[0] != data_type as u8 && data[0] != Key::Uninitialized as u8)
+        || data.len() != data_size
+    {
+        return Err(ListingError::DataTypeMismatch.into());
+    }
+
+    let result: T = try_from_slice_unchecked(data)?;
+
+    Ok(result)
+}
+
+/// Loads the metadata of `mint`, checking it is the token metadata program's PDA for it.
+pub fn assert_metadata_for_mint(
+    metadata_info: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<Metadata, ProgramError> {
+    let token_metadata_program = metaplex_token_metadata::id();
+    assert_owned_by(metadata_info, &token_metadata_program)?;
+    assert_derivation(
+        &token_metadata_program,
+        metadata_info,
+        &[
+            metaplex_token_metadata::state::PREFIX.as_bytes(),
+            token_metadata_program.as_ref(),
+            mint.as_ref(),
+        ],
+    )
+    .map_err(|_| ListingError::MetadataMismatch)?;
+
+    Metadata::from_account_info(metadata_info)
+}
+
+/// Splits a sale between the creators, in the order of `metadata.data.creators`, with the same
+/// royalty formula Metaplex's empty_payment_account uses for auctions. On the first sale the
+/// creators get everything, after that they get `seller_fee_basis_points` of it, each according to
+/// their share. Only verified creators are paid, and unverified shares stay with the seller.
+pub fn calculate_creator_payouts(
+    price: u64,
+    metadata: &Metadata,
+) -> Result<Vec<u64>, ProgramError> {
+    let creators = match &metadata.data.creators {
+        Some(creators) => creators,
+        None => return Ok(vec![]),
+    };
+
+    let numerator: u128 = if metadata.primary_sale_happened {
+        // during secondary sale, artists get a percentage of the proceeds
+        metadata.data.seller_fee_basis_points as u128
+    } else {
+        // during primary sale, artists get all of the proceeds
+        10000
+    };
+
+    let mut payouts = Vec::with_capacity(creators.len());
+    for creator in creators {
+        if !creator.verified {
+            payouts.push(0);
+            continue;
+        }
+
+        // Shares are percentages, so the artist further multiplier is the share in basis points.
+        let artist_further_multiplier = (creator.share as u128) * 100u128;
+        let amount = (price as u128)
+            .checked_mul(numerator)
+            .ok_or(ListingError::NumericalOverflowError)?
+            .checked_mul(artist_further_multiplier)
+            .ok_or(ListingError::NumericalOverflowError)?
+            / (10000 * 10000);
+        payouts.push(amount as u64);
+    }
+
+    Ok(payouts)
+}
+
+pub fn create_or_allocate_account_raw<'a>(
+    program_id: Pubkey,
+    new_account_info: &AccountInfo<'a>,
+    rent_sysvar_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    size: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<(), ProgramError> {
+    let rent = &Rent::from_account_info(rent_sysvar_info)?;
+    let required_lamports = rent
+        .minimum_balance(size)
+        .max(1)
+        .saturating_sub(new_account_info.lamports());
+
+    if required_lamports > 0 {
+        msg!("Transfer {} lamports to the new account", required_lamports);
+        invoke(
+            &system_instruction::transfer(payer_info.key, new_account_info.key, required_lamports),
+            &[
+                payer_info.clone(),
+                new_account_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+
+    let accounts = &[new_account_info.clone(), system_program_info.clone()];
+
+    msg!("Allocate space for the account");
+    invoke_signed(
+        &system_instruction::allocate(new_account_info.key, size.try_into().unwrap()),
+        accounts,
+        &[signer_seeds],
+    )?;
+
+    msg!("Assign the account to the owning program");
+    invoke_signed(
+        &system_instruction::assign(new_account_info.key, &program_id),
+        accounts,
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn spl_token_transfer<'a: 'b, 'b>(
+    source: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    amount: u64,
+    authority: AccountInfo<'a>,
+    authority_signer_seeds: &'b [&'b [u8]],
+    token_program: AccountInfo<'a>,
+) -> ProgramResult {
+    let result = invoke_signed(
+        &spl_token::instruction::transfer(
+            token_program.key,
+            source.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+        )?,
+        &[source, destination, authority, token_program],
+        &[authority_signer_seeds],
+    );
+
+    result.map_err(|_| ListingError::TokenTransferFailed.into())
+}
+
+/// Close the empty escrow token account, sending its rent to `destination`.
+pub fn spl_token_close_account<'a: 'b, 'b>(
+    account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    authority_signer_seeds: &'b [&'b [u8]],
+    token_program: AccountInfo<'a>,
+) -> ProgramResult {
+    invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            account.key,
+            destination.key,
+            authority.key,
+            &[],
+        )?,
+        &[account, destination, authority, token_program],
+        &[authority_signer_seeds],
+    )
+}
+
+/// Close an account owned by this program, moving its lamports to `destination` and zeroing its
+/// data so it can't be read again later in the same transaction.
+pub fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ListingError::NumericalOverflowError)?;
+    **account.lamports.borrow_mut() = 0;
+
+    for byte in account.data.borrow_mut().iter_mut() {
+        *byte = 0;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,292 @@
+use metaplex_listing::{instruction, state::PREFIX};
+use metaplex_token_metadata::state::Creator;
+use solana_program::{hash::Hash, program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+    transport::TransportError,
+};
+
+pub async fn get_account(banks_client: &mut BanksClient, pubkey: &Pubkey) -> Account {
+    banks_client
+        .get_account(*pubkey)
+        .await
+        .expect("account not found")
+        .expect("account empty")
+}
+
+pub async fn get_token_balance(banks_client: &mut BanksClient, token: &Pubkey) -> u64 {
+    let token_account = get_account(banks_client, token).await;
+    let account_info: spl_token::state::Account =
+        spl_token::state::Account::unpack_from_slice(token_account.data.as_slice()).unwrap();
+    account_info.amount
+}
+
+pub fn listing_pubkey(mint: &Pubkey, seller: &Pubkey) -> Pubkey {
+    let program_id = metaplex_listing::id();
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint.as_ref(),
+        seller.as_ref(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn metadata_pubkey(mint: &Pubkey) -> Pubkey {
+    let program_id = metaplex_token_metadata::id();
+    let seeds = &[
+        metaplex_token_metadata::state::PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint.as_ref(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub async fn fund(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    wallet: &Pubkey,
+    lamports: u64,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            wallet,
+            lamports,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_mint(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    mint: &Keypair,
+) -> Result<(), TransportError> {
+    let rent = banks_client.get_rent().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &payer.pubkey(),
+                None,
+                0,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+        &[payer, mint],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_token_account(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    account: &Keypair,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<(), TransportError> {
+    let rent = banks_client.get_rent().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &account.pubkey(),
+                mint,
+                owner,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+        &[payer, account],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+/// Mints a single token into `token`, owned by `owner`, and gives the mint metadata with the
+/// given creators. The first creator is the update authority and verifies itself, and the rest
+/// sign the metadata, so all of them are verified.
+#[allow(clippy::too_many_arguments)]
+pub async fn create_token_with_metadata(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    mint: &Keypair,
+    token: &Keypair,
+    owner: &Pubkey,
+    creators: &[(&Keypair, u8)],
+    seller_fee_basis_points: u16,
+) -> Result<Pubkey, TransportError> {
+    create_mint(banks_client, payer, recent_blockhash, mint).await?;
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        token,
+        &mint.pubkey(),
+        owner,
+    )
+    .await?;
+
+    let metadata = metadata_pubkey(&mint.pubkey());
+    let mut instructions = vec![
+        spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &token.pubkey(),
+            &payer.pubkey(),
+            &[],
+            1,
+        )
+        .unwrap(),
+        metaplex_token_metadata::instruction::create_metadata_accounts(
+            metaplex_token_metadata::id(),
+            metadata,
+            mint.pubkey(),
+            payer.pubkey(),
+            payer.pubkey(),
+            creators[0].0.pubkey(),
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            Some(
+                creators
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (creator, share))| Creator {
+                        address: creator.pubkey(),
+                        verified: i == 0,
+                        share: *share,
+                    })
+                    .collect(),
+            ),
+            seller_fee_basis_points,
+            true,
+            true,
+        ),
+    ];
+    let mut signers = vec![payer, creators[0].0];
+    for (creator, _) in &creators[1..] {
+        instructions.push(metaplex_token_metadata::instruction::sign_metadata(
+            metaplex_token_metadata::id(),
+            metadata,
+            creator.pubkey(),
+        ));
+        signers.push(creator);
+    }
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(metadata)
+}
+
+/// Lists the seller's token at `price`. Returns the listing and its escrow.
+pub async fn create_listing(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    seller: &Keypair,
+    seller_token: &Pubkey,
+    mint: &Pubkey,
+    price: u64,
+) -> Result<(Pubkey, Pubkey), TransportError> {
+    let listing = listing_pubkey(mint, &seller.pubkey());
+    let escrow = Keypair::new();
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &escrow,
+        mint,
+        &listing,
+    )
+    .await?;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_listing_instruction(
+            metaplex_listing::id(),
+            listing,
+            escrow.pubkey(),
+            *seller_token,
+            seller.pubkey(),
+            *mint,
+            metadata_pubkey(mint),
+            payer.pubkey(),
+            price,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, seller],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok((listing, escrow.pubkey()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn purchase(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    listing: &Pubkey,
+    escrow: &Pubkey,
+    buyer: &Keypair,
+    buyer_token: &Pubkey,
+    seller: &Pubkey,
+    mint: &Pubkey,
+    creators: Vec<Pubkey>,
+    price: u64,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_purchase_instruction(
+            metaplex_listing::id(),
+            *listing,
+            *escrow,
+            *buyer_token,
+            buyer.pubkey(),
+            *seller,
+            metadata_pubkey(mint),
+            creators,
+            price,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, buyer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
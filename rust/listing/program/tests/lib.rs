@@ -0,0 +1,338 @@
+#![allow(warnings)]
+
+use metaplex_listing::error::ListingError;
+use metaplex_token_metadata::state::Metadata;
+use solana_program::{
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, entrypoint::ProgramResult,
+    instruction::InstructionError, pubkey::Pubkey,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+mod helpers;
+
+// The listing and token metadata processors tie the account slice to the lifetime of the
+// accounts in it, which the test runtime does not hand out, so they're wrapped to match.
+fn process_listing_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let accounts: &[AccountInfo] = unsafe { std::mem::transmute(accounts) };
+    metaplex_listing::processor::process_instruction(program_id, accounts, input)
+}
+
+fn process_token_metadata_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let accounts: &[AccountInfo] = unsafe { std::mem::transmute(accounts) };
+    metaplex_token_metadata::processor::process_instruction(program_id, accounts, input)
+}
+
+fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "metaplex_listing",
+        metaplex_listing::id(),
+        processor!(process_listing_instruction),
+    );
+    program_test.add_program(
+        "metaplex_token_metadata",
+        metaplex_token_metadata::id(),
+        processor!(process_token_metadata_instruction),
+    );
+    program_test
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_purchase_pays_royalties() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let creator_a = Keypair::new();
+    let creator_b = Keypair::new();
+    let seller = Keypair::new();
+    let first_buyer = Keypair::new();
+    let second_buyer = Keypair::new();
+    for wallet in &[&creator_a, &creator_b, &seller, &first_buyer, &second_buyer] {
+        helpers::fund(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &wallet.pubkey(),
+            1_000_000_000,
+        )
+        .await
+        .unwrap();
+    }
+
+    let mint = Keypair::new();
+    let seller_token = Keypair::new();
+    let metadata = helpers::create_token_with_metadata(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &mint,
+        &seller_token,
+        &seller.pubkey(),
+        &[(&creator_a, 70), (&creator_b, 30)],
+        500,
+    )
+    .await
+    .unwrap();
+    let creators = vec![creator_a.pubkey(), creator_b.pubkey()];
+
+    // On the first sale the whole price goes to the creators, by share.
+    let (listing, escrow) = helpers::create_listing(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &seller,
+        &seller_token.pubkey(),
+        &mint.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+    let first_buyer_token = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &first_buyer_token,
+        &mint.pubkey(),
+        &first_buyer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let reclaimed_rent = helpers::get_account(&mut banks_client, &listing)
+        .await
+        .lamports
+        + helpers::get_account(&mut banks_client, &escrow)
+            .await
+            .lamports;
+    let seller_before = helpers::get_account(&mut banks_client, &seller.pubkey())
+        .await
+        .lamports;
+
+    helpers::purchase(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &listing,
+        &escrow,
+        &first_buyer,
+        &first_buyer_token.pubkey(),
+        &seller.pubkey(),
+        &mint.pubkey(),
+        creators.clone(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &creator_a.pubkey())
+            .await
+            .lamports,
+        1_000_000_000 + 700_000
+    );
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &creator_b.pubkey())
+            .await
+            .lamports,
+        1_000_000_000 + 300_000
+    );
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &first_buyer.pubkey())
+            .await
+            .lamports,
+        1_000_000_000 - 1_000_000
+    );
+    // The seller only gets the listing and escrow rent back.
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &seller.pubkey())
+            .await
+            .lamports,
+        seller_before + reclaimed_rent
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &first_buyer_token.pubkey()).await,
+        1
+    );
+    assert!(banks_client.get_account(listing).await.unwrap().is_none());
+    assert!(banks_client.get_account(escrow).await.unwrap().is_none());
+
+    let metadata_account: Metadata = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &metadata)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert!(metadata_account.primary_sale_happened);
+
+    // On a resale the creators only get seller_fee_basis_points, and the rest goes to the seller.
+    let (listing, escrow) = helpers::create_listing(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &first_buyer,
+        &first_buyer_token.pubkey(),
+        &mint.pubkey(),
+        2_000_000,
+    )
+    .await
+    .unwrap();
+    let second_buyer_token = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &second_buyer_token,
+        &mint.pubkey(),
+        &second_buyer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let reclaimed_rent = helpers::get_account(&mut banks_client, &listing)
+        .await
+        .lamports
+        + helpers::get_account(&mut banks_client, &escrow)
+            .await
+            .lamports;
+    let first_buyer_before = helpers::get_account(&mut banks_client, &first_buyer.pubkey())
+        .await
+        .lamports;
+
+    helpers::purchase(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &listing,
+        &escrow,
+        &second_buyer,
+        &second_buyer_token.pubkey(),
+        &first_buyer.pubkey(),
+        &mint.pubkey(),
+        creators,
+        2_000_000,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &creator_a.pubkey())
+            .await
+            .lamports,
+        1_000_000_000 + 700_000 + 70_000
+    );
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &creator_b.pubkey())
+            .await
+            .lamports,
+        1_000_000_000 + 300_000 + 30_000
+    );
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &first_buyer.pubkey())
+            .await
+            .lamports,
+        first_buyer_before + 1_900_000 + reclaimed_rent
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &second_buyer_token.pubkey()).await,
+        1
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_purchase_price_mismatch() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let creator = Keypair::new();
+    let seller = Keypair::new();
+    let buyer = Keypair::new();
+    for wallet in &[&creator, &seller, &buyer] {
+        helpers::fund(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &wallet.pubkey(),
+            1_000_000_000,
+        )
+        .await
+        .unwrap();
+    }
+
+    let mint = Keypair::new();
+    let seller_token = Keypair::new();
+    helpers::create_token_with_metadata(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &mint,
+        &seller_token,
+        &seller.pubkey(),
+        &[(&creator, 100)],
+        500,
+    )
+    .await
+    .unwrap();
+
+    let (listing, escrow) = helpers::create_listing(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &seller,
+        &seller_token.pubkey(),
+        &mint.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+    let buyer_token = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &buyer_token,
+        &mint.pubkey(),
+        &buyer.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let err = helpers::purchase(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &listing,
+        &escrow,
+        &buyer,
+        &buyer_token.pubkey(),
+        &seller.pubkey(),
+        &mint.pubkey(),
+        vec![creator.pubkey()],
+        999_999,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ListingError::PriceMismatch as u32)
+        )
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &escrow).await,
+        1
+    );
+}
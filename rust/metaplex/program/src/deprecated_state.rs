@@ -111,6 +111,13 @@ impl AuctionManager for AuctionManagerV1 {
         self.state.status = status
     }
 
+    fn offers_closed(&self) -> bool {
+        // Offers are only supported on V2 auction managers.
+        true
+    }
+
+    fn close_offers(&mut self) {}
+
     fn configs_validated(&self) -> u64 {
         self.state.winning_config_items_validated as u64
     }
@@ -470,6 +470,50 @@ pub enum MetaplexError {
     /// Auction manager already in finished state
     #[error("Auction manager already in finished state")]
     AuctionManagerInFishedState,
+
+    /// The winner has not redeemed their bid yet
+    #[error("The winner has not redeemed their bid yet")]
+    BidNotRedeemed,
+
+    /// Native SOL auctions must use the auction manager as their accept payment account
+    #[error("Native SOL auctions must use the auction manager as their accept payment account")]
+    AcceptPaymentNativeSolMismatch,
+
+    /// Auction was cancelled, there is nothing to redeem
+    #[error("Auction was cancelled, there is nothing to redeem")]
+    AuctionCancelled,
+
+    /// The auctioneer has taken prizes back, offers can no longer be accepted
+    #[error("The auctioneer has taken prizes back, offers can no longer be accepted")]
+    OffersClosed,
+
+    /// Store fee can be at most 1000 basis points
+    #[error("Store fee can be at most 1000 basis points")]
+    StoreFeeTooHigh,
+
+    /// Store fee destination does not match the store
+    #[error("Store fee destination does not match the store")]
+    StoreFeeDestinationMismatch,
+
+    /// An updated safety deposit config can't change its winning config type
+    #[error("An updated safety deposit config can't change its winning config type")]
+    SafetyDepositConfigTypeMismatch,
+
+    /// Updated safety deposit config does not fit in the existing account
+    #[error("Updated safety deposit config does not fit in the existing account")]
+    SafetyDepositConfigTooLarge,
+
+    /// Store has no creator allowlist
+    #[error("Store has no creator allowlist")]
+    StoreHasNoCreatorAllowlist,
+
+    /// Creator is not in the store's creator allowlist
+    #[error("Creator is not in the store's creator allowlist")]
+    CreatorNotInAllowlist,
+
+    /// A deactivated creator can't be closed while the store has a creator allowlist
+    #[error("A deactivated creator can't be closed while the store has a creator allowlist")]
+    CannotCloseDeactivatedCreator,
 }
 
 impl PrintProgramError for MetaplexError {
@@ -1,7 +1,7 @@
 use {
     crate::{
         deprecated_state::AuctionManagerSettingsV1,
-        state::{SafetyDepositConfig, TupleNumericType, PREFIX},
+        state::{SafetyDepositConfig, TupleNumericType, PREFIX, TOTALS},
     },
     borsh::{BorshDeserialize, BorshSerialize},
     metaplex_token_metadata::state::EDITION_MARKER_BIT_SIZE,
@@ -16,9 +16,23 @@ pub struct SetStoreArgs {
     pub public: bool,
 }
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetStoreFeeArgs {
+    pub fee_basis_points: u16,
+}
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct SetWhitelistedCreatorArgs {
     pub activated: bool,
 }
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetStoreCreatorAllowlistArgs {
+    /// Merkle root of the allowed creator keys, None to turn the allowlist off.
+    pub root: Option<[u8; 32]>,
+}
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ClaimWhitelistedCreatorArgs {
+    /// Merkle proof of the creator key against the store's creator allowlist root.
+    pub proof: Vec<[u8; 32]>,
+}
 
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct EmptyPaymentAccountArgs {
@@ -73,6 +87,12 @@ pub struct EndAuctionArgs {
     pub reveal: Option<(u64, u64)>,
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct AcceptOfferArgs {
+    /// The bidder whose offer is accepted.
+    pub bidder: Pubkey,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct SetStoreIndexArgs {
     pub page: u64,
@@ -244,10 +264,12 @@ pub enum MetaplexInstruction {
     ///
     ///   0. `[writable]` Auction manager
     ///   1. `[writable]` Auction
-    ///   3. `[signer]` Auction manager authority
+    ///   3. `[signer]` Auction manager authority, or any signer once the scheduled start time has passed
     ///   4. `[]` Store key
     ///   5. `[]` Auction program
     ///   6. `[]` Clock sysvar
+    ///   7. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///           Optional, needed for auctions with a scheduled start time.
     StartAuction,
 
     /// If the auction manager is in a Disbursing or Finished state, then this means Auction must be in Ended state.
@@ -259,8 +281,11 @@ pub enum MetaplexInstruction {
     /// funds at any time without any danger to the user of losing out on their NFT, because the AM will honor their bid with an NFT
     /// at ANY time.
     ///
-    ///   0. `[writable]` The accept payment account on the auction manager
-    ///   1. `[writable]` The bidder pot token account
+    /// Claiming a winner's bid again once their deposit is forfeited pays the deposit out instead. It goes to a token account
+    /// owned by the auction manager authority passed in place of the accept payment account (the authority wallet for native SOL).
+    ///
+    ///   0. `[writable]` The accept payment account on the auction manager (the auction manager itself for native SOL auctions)
+    ///   1. `[writable]` The bidder pot token account (the bidder pot pda for native SOL auctions)
     ///   2. `[writable]` The bidder pot pda account [seed of ['auction', program_id, auction key, bidder key] -
     ///           relative to the auction program, not auction manager
     ///   3. `[writable]` Auction manager
@@ -273,6 +298,8 @@ pub enum MetaplexInstruction {
     ///   10. `[]` Clock sysvar
     ///   11. `[]` Token program
     ///   12. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   13. `[writable]` Bidder token account, refunded whatever a Vickrey or uniform price winner bid over their price.
+    ///            Only needed when there is a refund (the bidder wallet for native SOL auctions)
     ClaimBid,
 
     /// At any time, the auction manager authority may empty whatever funds are in the accept payment account
@@ -296,6 +323,7 @@ pub enum MetaplexInstruction {
     ///
     ///   0. `[writable]` The accept payment account on the auction manager
     ///   1. `[writable]` The destination account of same mint type as the accept payment account. Must be an Associated Token Account.
+    ///           For native SOL auctions the accept payment account is the auction manager and this is the recipient's wallet.
     ///   2. `[writable]` Auction manager
     ///   3. `[writable]` Payout ticket info to keep track of this artist or auctioneer's payment, pda of [metaplex, auction manager, winning config index OR 'participation', safety deposit key]
     ///   4. `[signer]` payer
@@ -311,6 +339,10 @@ pub enum MetaplexInstruction {
     ///   13. `[]` Rent sysvar
     ///   14. `[]` AuctionWinnerTokenTypeTracker, pda of seed ['metaplex', program id, auction manager key, 'totals']
     ///   15. `[]` Safety deposit config pda of ['metaplex', program id, auction manager, safety deposit]
+    ///   16. `[]` Auction extended, pda of ['auction', auction program id, vault key, 'extended'], required for winning configs
+    ///   17. `[writable]` Store fee destination, required when the store takes a fee from this payout. A token account
+    ///           of the accept payment mint owned by the store's fee destination, or the fee destination wallet itself
+    ///           for native SOL auctions.
     EmptyPaymentAccount(EmptyPaymentAccountArgs),
 
     /// Given a signer wallet, create a store with pda ['metaplex', wallet] (if it does not exist) and/or update it
@@ -547,9 +579,12 @@ pub enum MetaplexInstruction {
     ///   4. `[]` Authority for the Auction Manager
     ///   5. `[signer]` Payer
     ///   6. `[]` Accept payment account of same token mint as the auction for taking payment for open editions, owner should be auction manager key
+    ///           For native SOL auctions this is the auction manager itself, which collects the lamports
     ///   7. `[]` Store that this auction manager will belong to
     ///   8. `[]` System sysvar    
     ///   9. `[]` Rent sysvar
+    ///   10. `[]` Optional auction data extended - pda of ['auction', auction program id, vault key, 'extended'] relative to auction program
+    ///            Required for native SOL auctions
     InitAuctionManagerV2(InitAuctionManagerV2Args),
 
     /// NOTE: Requires an AuctionManagerV2.
@@ -635,7 +670,8 @@ pub enum MetaplexInstruction {
     ///
     ///   0. `[writable]` Auction manager
     ///   1. `[writable]` Auction
-    ///   2. `[]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///   2. `[writable]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///           Written when the auction settles its reserve price.
     ///   3. `[signer]` Auction manager authority
     ///   4. `[]` Store key
     ///   5. `[]` Auction program
@@ -665,6 +701,112 @@ pub enum MetaplexInstruction {
     ///   7. `[]` Rent sysvar
     ///   8. `[]` Clock sysvar
     SetAuctionCache,
+    /// Returns the deposit of a winner whose bid was claimed, once they have redeemed it. Anyone can
+    /// call this, the deposit only goes back to the bidder.
+    ///
+    ///   0. `[writable]` The bidder's token account of the auction mint (the bidder wallet for native SOL auctions)
+    ///   1. `[writable]` The bidder pot token account (the bidder pot pda for native SOL auctions)
+    ///   2. `[writable]` The bidder pot pda account [seed of ['auction', program_id, auction key, bidder key] -
+    ///           relative to the auction program, not auction manager
+    ///   3. `[]` Auction manager
+    ///   4. `[]` The auction
+    ///   5. `[]` Auction extended (pda relative to auction of ['auction', program id, vault key, 'extended'])
+    ///   6. `[]` The bidder wallet
+    ///   7. `[]` Bidder metadata (pda relative to auction of ['auction', program id, auction key, bidder key, 'metadata'])
+    ///   8. `[]` Bid redemption key, pda of ['metaplex', auction key, bidder metadata key]
+    ///   9. `[]` Store
+    ///   10. `[]` Auction program
+    ///   11. `[]` Token program
+    ReturnBidDeposit,
+    /// Cancels an auction that has not ended. Nobody wins, every bidder can cancel their bid for a
+    /// refund, and the auctioneer takes back the items with RedeemUnusedWinningConfigItemsAsAuctioneer
+    /// and WithdrawMasterEdition.
+    ///
+    ///   0. `[writable]` Auction manager
+    ///   1. `[writable]` Auction
+    ///   2. `[]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///   3. `[signer]` Auction manager authority
+    ///   4. `[]` Store key
+    ///   5. `[]` Auction program
+    ///   6. `[]` Clock sysvar
+    CancelAuction,
+    /// Accepts an offer made on an English auction that ended without a bid, making it a winning bid.
+    /// The offer is then claimed with ClaimBid and redeemed like any other winning bid. Offers can only
+    /// be accepted until the auctioneer takes a prize back.
+    ///
+    ///   0. `[]` Auction manager
+    ///   1. `[writable]` Auction
+    ///   2. `[]` Auction extended data account (pda relative to auction of ['auction', program id, vault key, 'extended']).
+    ///   3. `[]` Bidder metadata of the offer (pda relative to auction of ['auction', program id, auction key, bidder key, 'metadata'])
+    ///   4. `[signer]` Auction manager authority
+    ///   5. `[]` Store key
+    ///   6. `[]` Auction program
+    AcceptOffer(AcceptOfferArgs),
+    /// Sets the fee the store takes out of every primary sale payout in EmptyPaymentAccount, and the
+    /// wallet it goes to. The fee is capped at 1000 basis points.
+    ///
+    ///   0. `[writable]` The store key, seed of ['metaplex', admin wallet]
+    ///   1. `[signer]`  The admin wallet
+    ///   2. `[]` The fee destination wallet
+    SetStoreFee(SetStoreFeeArgs),
+    /// NOTE: Requires an AuctionManagerV2 that is Initialized or Validated, ie not yet started.
+    ///
+    /// Rewrites the SafetyDepositConfig of a box that was already validated, so a wrong amount range or
+    /// participation config can be fixed without decommissioning the auction manager. The new config
+    /// must keep the winning config type and fit in the existing account. The box comes out of
+    /// safety_config_items_validated (and has_participation) and is validated again against the new
+    /// config in the same instruction, so the auction manager is Validated afterwards only if every
+    /// box still is.
+    ///   0. `[writable]` Safety deposit config, pda of seed ['metaplex', program id, auction manager key, safety deposit key]
+    ///   1. `[writable]` AuctionWinnerTokenTypeTracker, pda of seed ['metaplex', program id, auction manager key, 'totals']
+    ///   2. `[writable]` Auction manager
+    ///   3. `[]` Safety deposit box account
+    ///   4. `[]` Safety deposit box storage account where the actual nft token is stored
+    ///   5. `[]` Vault account
+    ///   6. `[]` The auction manager's store key
+    ///   7. `[]` Metadata account of the token in the safety deposit box
+    ///   8. `[]` Edition OR MasterEdition record key, pda of ['metadata', token metadata program id, mint id, 'edition']
+    ///   9. `[signer]` Authority
+    UpdateSafetyDepositConfig(SafetyDepositConfig),
+    /// Given an existing store, adds or updates many whitelisted creators at once, setting all of them
+    /// to the same activated flag. Creates their PDAs like SetWhitelistedCreator does.
+    ///
+    ///   0. `[signer]`  The admin wallet
+    ///   1. `[signer]`  Payer
+    ///   2. `[]` The store key, seed of ['metaplex', admin wallet]
+    ///   3. `[]` System
+    ///   4. `[]` Rent sysvar
+    ///
+    /// Followed by a pair of accounts for every creator:
+    ///
+    ///   5. `[writable]` The whitelisted creator pda key, seed of ['metaplex', store key, creator key]
+    ///   6. `[]` The creator key
+    SetWhitelistedCreators(SetWhitelistedCreatorArgs),
+    /// Closes a whitelisted creator pda of the store, sending its rent to the destination. An item
+    /// of the creator can no longer be validated unless the creator is whitelisted again. A
+    /// deactivated creator can't be closed while the store has a creator allowlist.
+    ///
+    ///   0. `[writable]` The whitelisted creator pda key, seed of ['metaplex', store key, creator key]
+    ///   1. `[signer]`  The admin wallet
+    ///   2. `[]` The store key, seed of ['metaplex', admin wallet]
+    ///   3. `[writable]` Destination of the rent lamports
+    CloseWhitelistedCreator,
+    /// Sets or clears the Merkle root of creators admitted to the store, see ClaimWhitelistedCreator.
+    ///
+    ///   0. `[writable]` The store key, seed of ['metaplex', admin wallet]
+    ///   1. `[signer]`  The admin wallet
+    SetStoreCreatorAllowlist(SetStoreCreatorAllowlistArgs),
+    /// Creates an activated whitelisted creator pda for a creator in the store's creator allowlist,
+    /// proven with a Merkle proof, so the admin doesn't have to whitelist each one. A creator that
+    /// already has a pda is left as the admin set it.
+    ///
+    ///   0. `[writable]` The whitelisted creator pda key, seed of ['metaplex', store key, creator key]
+    ///   1. `[writable, signer]` Payer
+    ///   2. `[]` The creator key
+    ///   3. `[]` The store key, seed of ['metaplex', admin wallet]
+    ///   4. `[]` System
+    ///   5. `[]` Rent sysvar
+    ClaimWhitelistedCreator(ClaimWhitelistedCreatorArgs),
 }
 
 /// Creates an DeprecatedInitAuctionManager instruction
@@ -714,10 +856,21 @@ pub fn create_init_auction_manager_v2_instruction(
     length_type: TupleNumericType,
     max_ranges: u64,
 ) -> Instruction {
+    let (auction_token_tracker, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_manager.as_ref(),
+            TOTALS.as_bytes(),
+        ],
+        &program_id,
+    );
+
     Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(auction_manager, false),
+            AccountMeta::new(auction_token_tracker, false),
             AccountMeta::new_readonly(vault, false),
             AccountMeta::new_readonly(auction, false),
             AccountMeta::new_readonly(auction_manager_authority, false),
@@ -867,8 +1020,18 @@ pub fn create_validate_safety_deposit_box_v2_instruction(
         ],
         &program_id,
     );
+    let (auction_token_tracker, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_manager.as_ref(),
+            TOTALS.as_bytes(),
+        ],
+        &program_id,
+    );
     let accounts = vec![
         AccountMeta::new(validation, false),
+        AccountMeta::new(auction_token_tracker, false),
         AccountMeta::new(auction_manager, false),
         AccountMeta::new(metadata, false),
         AccountMeta::new(original_authority_lookup, false),
@@ -1056,17 +1219,23 @@ pub fn create_start_auction_instruction(
     auction: Pubkey,
     auction_manager_authority: Pubkey,
     store: Pubkey,
+    auction_extended: Option<Pubkey>,
 ) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(auction_manager, false),
+        AccountMeta::new(auction, false),
+        AccountMeta::new_readonly(auction_manager_authority, true),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(metaplex_auction::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    if let Some(auction_extended) = auction_extended {
+        accounts.push(AccountMeta::new_readonly(auction_extended, false));
+    }
+
     Instruction {
         program_id,
-        accounts: vec![
-            AccountMeta::new(auction_manager, false),
-            AccountMeta::new(auction, false),
-            AccountMeta::new_readonly(auction_manager_authority, true),
-            AccountMeta::new_readonly(store, false),
-            AccountMeta::new_readonly(metaplex_auction::id(), false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-        ],
+        accounts,
         data: MetaplexInstruction::StartAuction.try_to_vec().unwrap(),
     }
 }
@@ -1523,7 +1692,7 @@ pub fn create_end_auction_instruction(
         accounts: vec![
             AccountMeta::new(auction_manager, false),
             AccountMeta::new(auction, false),
-            AccountMeta::new_readonly(auction_data_extended, false),
+            AccountMeta::new(auction_data_extended, false),
             AccountMeta::new_readonly(auction_manager_authority, true),
             AccountMeta::new_readonly(store, false),
             AccountMeta::new_readonly(metaplex_auction::id(), false),
@@ -1534,3 +1703,269 @@ pub fn create_end_auction_instruction(
             .unwrap(),
     }
 }
+
+/// Creates an ReturnBidDeposit instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_return_bid_deposit_instruction(
+    program_id: Pubkey,
+    bidder_token: Pubkey,
+    bidder_pot_token: Pubkey,
+    bidder_pot: Pubkey,
+    auction_manager: Pubkey,
+    auction: Pubkey,
+    auction_data_extended: Pubkey,
+    bidder: Pubkey,
+    bidder_metadata: Pubkey,
+    bid_redemption: Pubkey,
+    store: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(bidder_token, false),
+            AccountMeta::new(bidder_pot_token, false),
+            AccountMeta::new(bidder_pot, false),
+            AccountMeta::new_readonly(auction_manager, false),
+            AccountMeta::new_readonly(auction, false),
+            AccountMeta::new_readonly(auction_data_extended, false),
+            AccountMeta::new_readonly(bidder, false),
+            AccountMeta::new_readonly(bidder_metadata, false),
+            AccountMeta::new_readonly(bid_redemption, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(metaplex_auction::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetaplexInstruction::ReturnBidDeposit.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an CancelAuction instruction
+pub fn create_cancel_auction_instruction(
+    program_id: Pubkey,
+    auction_manager: Pubkey,
+    auction: Pubkey,
+    auction_data_extended: Pubkey,
+    auction_manager_authority: Pubkey,
+    store: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(auction_manager, false),
+            AccountMeta::new(auction, false),
+            AccountMeta::new_readonly(auction_data_extended, false),
+            AccountMeta::new_readonly(auction_manager_authority, true),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(metaplex_auction::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: MetaplexInstruction::CancelAuction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an AcceptOffer instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_accept_offer_instruction(
+    program_id: Pubkey,
+    auction_manager: Pubkey,
+    auction: Pubkey,
+    auction_data_extended: Pubkey,
+    bidder_metadata: Pubkey,
+    auction_manager_authority: Pubkey,
+    store: Pubkey,
+    accept_offer_args: AcceptOfferArgs,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(auction_manager, false),
+            AccountMeta::new(auction, false),
+            AccountMeta::new_readonly(auction_data_extended, false),
+            AccountMeta::new_readonly(bidder_metadata, false),
+            AccountMeta::new_readonly(auction_manager_authority, true),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(metaplex_auction::id(), false),
+        ],
+        data: MetaplexInstruction::AcceptOffer(accept_offer_args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn create_set_store_fee_instruction(
+    program_id: Pubkey,
+    store: Pubkey,
+    admin: Pubkey,
+    fee_destination: Pubkey,
+    fee_basis_points: u16,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(store, false),
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new_readonly(fee_destination, false),
+        ],
+        data: MetaplexInstruction::SetStoreFee(SetStoreFeeArgs { fee_basis_points })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an UpdateSafetyDepositConfig instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_update_safety_deposit_config_instruction(
+    program_id: Pubkey,
+    auction_manager: Pubkey,
+    safety_deposit_box: Pubkey,
+    safety_deposit_token_store: Pubkey,
+    vault: Pubkey,
+    store: Pubkey,
+    metadata: Pubkey,
+    edition: Pubkey,
+    auction_manager_authority: Pubkey,
+    safety_deposit_config: SafetyDepositConfig,
+) -> Instruction {
+    let (validation, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_manager.as_ref(),
+            safety_deposit_box.as_ref(),
+        ],
+        &program_id,
+    );
+    let (auction_token_tracker, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_manager.as_ref(),
+            TOTALS.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(validation, false),
+            AccountMeta::new(auction_token_tracker, false),
+            AccountMeta::new(auction_manager, false),
+            AccountMeta::new_readonly(safety_deposit_box, false),
+            AccountMeta::new_readonly(safety_deposit_token_store, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new_readonly(auction_manager_authority, true),
+        ],
+        data: MetaplexInstruction::UpdateSafetyDepositConfig(safety_deposit_config)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an SetWhitelistedCreators instruction
+pub fn create_set_whitelisted_creators_instruction(
+    program_id: Pubkey,
+    store: Pubkey,
+    admin: Pubkey,
+    payer: Pubkey,
+    creators: &[Pubkey],
+    activated: bool,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(admin, true),
+        AccountMeta::new_readonly(payer, true),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+    for creator in creators {
+        let (whitelisted_creator, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                store.as_ref(),
+                creator.as_ref(),
+            ],
+            &program_id,
+        );
+        accounts.push(AccountMeta::new(whitelisted_creator, false));
+        accounts.push(AccountMeta::new_readonly(*creator, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::SetWhitelistedCreators(SetWhitelistedCreatorArgs { activated })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an CloseWhitelistedCreator instruction
+pub fn create_close_whitelisted_creator_instruction(
+    program_id: Pubkey,
+    whitelisted_creator: Pubkey,
+    admin: Pubkey,
+    store: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(whitelisted_creator, false),
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new(destination, false),
+        ],
+        data: MetaplexInstruction::CloseWhitelistedCreator
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an SetStoreCreatorAllowlist instruction
+pub fn create_set_store_creator_allowlist_instruction(
+    program_id: Pubkey,
+    store: Pubkey,
+    admin: Pubkey,
+    root: Option<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(store, false),
+            AccountMeta::new_readonly(admin, true),
+        ],
+        data: MetaplexInstruction::SetStoreCreatorAllowlist(SetStoreCreatorAllowlistArgs { root })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an ClaimWhitelistedCreator instruction
+pub fn create_claim_whitelisted_creator_instruction(
+    program_id: Pubkey,
+    whitelisted_creator: Pubkey,
+    payer: Pubkey,
+    creator: Pubkey,
+    store: Pubkey,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(whitelisted_creator, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(creator, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::ClaimWhitelistedCreator(ClaimWhitelistedCreatorArgs { proof })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
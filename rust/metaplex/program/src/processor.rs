@@ -1,7 +1,11 @@
 use {
     crate::instruction::MetaplexInstruction,
+    accept_offer::process_accept_offer,
     borsh::BorshDeserialize,
+    cancel_auction::process_cancel_auction,
     claim_bid::process_claim_bid,
+    claim_whitelisted_creator::process_claim_whitelisted_creator,
+    close_whitelisted_creator::process_close_whitelisted_creator,
     decommission_auction_manager::process_decommission_auction_manager,
     deprecated_init_auction_manager_v1::process_deprecated_init_auction_manager_v1,
     deprecated_populate_participation_printing_account::process_deprecated_populate_participation_printing_account,
@@ -15,17 +19,26 @@ use {
     redeem_participation_bid::process_redeem_participation_bid,
     redeem_printing_v2_bid::process_redeem_printing_v2_bid,
     redeem_unused_winning_config_items_as_auctioneer::process_redeem_unused_winning_config_items_as_auctioneer,
+    return_bid_deposit::process_return_bid_deposit,
     set_auction_cache::process_set_auction_cache,
     set_store::process_set_store,
+    set_store_creator_allowlist::process_set_store_creator_allowlist,
+    set_store_fee::process_set_store_fee,
     set_store_index::process_set_store_index,
     set_whitelisted_creator::process_set_whitelisted_creator,
+    set_whitelisted_creators::process_set_whitelisted_creators,
     solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey},
     start_auction::process_start_auction,
+    update_safety_deposit_config::process_update_safety_deposit_config,
     validate_safety_deposit_box_v2::process_validate_safety_deposit_box_v2,
     withdraw_master_edition::process_withdraw_master_edition,
 };
 
+pub mod accept_offer;
+pub mod cancel_auction;
 pub mod claim_bid;
+pub mod claim_whitelisted_creator;
+pub mod close_whitelisted_creator;
 pub mod decommission_auction_manager;
 pub mod deprecated_init_auction_manager_v1;
 pub mod deprecated_populate_participation_printing_account;
@@ -39,11 +52,16 @@ pub mod redeem_full_rights_transfer_bid;
 pub mod redeem_participation_bid;
 pub mod redeem_printing_v2_bid;
 pub mod redeem_unused_winning_config_items_as_auctioneer;
+pub mod return_bid_deposit;
 pub mod set_auction_cache;
 pub mod set_store;
+pub mod set_store_creator_allowlist;
+pub mod set_store_fee;
 pub mod set_store_index;
 pub mod set_whitelisted_creator;
+pub mod set_whitelisted_creators;
 pub mod start_auction;
+pub mod update_safety_deposit_config;
 pub mod validate_safety_deposit_box_v2;
 pub mod withdraw_master_edition;
 
@@ -161,5 +179,41 @@ pub fn process_instruction<'a>(
             msg!("Instruction: Set Auction Cache");
             process_set_auction_cache(program_id, accounts)
         }
+        MetaplexInstruction::ReturnBidDeposit => {
+            msg!("Instruction: Return Bid Deposit");
+            process_return_bid_deposit(program_id, accounts)
+        }
+        MetaplexInstruction::CancelAuction => {
+            msg!("Instruction: Cancel Auction");
+            process_cancel_auction(program_id, accounts)
+        }
+        MetaplexInstruction::AcceptOffer(args) => {
+            msg!("Instruction: Accept Offer");
+            process_accept_offer(program_id, accounts, args)
+        }
+        MetaplexInstruction::SetStoreFee(args) => {
+            msg!("Instruction: Set Store Fee");
+            process_set_store_fee(program_id, accounts, args.fee_basis_points)
+        }
+        MetaplexInstruction::UpdateSafetyDepositConfig(safety_deposit_config) => {
+            msg!("Instruction: Update Safety Deposit Config");
+            process_update_safety_deposit_config(program_id, accounts, safety_deposit_config)
+        }
+        MetaplexInstruction::SetWhitelistedCreators(args) => {
+            msg!("Instruction: Set Whitelisted Creators");
+            process_set_whitelisted_creators(program_id, accounts, args.activated)
+        }
+        MetaplexInstruction::CloseWhitelistedCreator => {
+            msg!("Instruction: Close Whitelisted Creator");
+            process_close_whitelisted_creator(program_id, accounts)
+        }
+        MetaplexInstruction::SetStoreCreatorAllowlist(args) => {
+            msg!("Instruction: Set Store Creator Allowlist");
+            process_set_store_creator_allowlist(program_id, accounts, args.root)
+        }
+        MetaplexInstruction::ClaimWhitelistedCreator(args) => {
+            msg!("Instruction: Claim Whitelisted Creator");
+            process_claim_whitelisted_creator(program_id, accounts, args.proof)
+        }
     }
 }
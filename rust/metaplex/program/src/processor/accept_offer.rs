@@ -0,0 +1,118 @@
+use {
+    crate::{
+        error::MetaplexError,
+        instruction::AcceptOfferArgs as MetaplexAcceptOfferArgs,
+        state::{get_auction_manager, AuctionManagerStatus, Store, PREFIX},
+        utils::{assert_authority_correct, assert_owned_by},
+    },
+    metaplex_auction::{
+        instruction::{accept_offer_instruction, AcceptOfferArgs},
+        processor::AuctionData,
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        pubkey::Pubkey,
+    },
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn issue_accept_offer<'a>(
+    auction_program: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    auction: AccountInfo<'a>,
+    auction_extended: AccountInfo<'a>,
+    bidder_metadata: AccountInfo<'a>,
+    vault: Pubkey,
+    bidder: Pubkey,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &accept_offer_instruction(
+            *auction_program.key,
+            *authority.key,
+            AcceptOfferArgs {
+                resource: vault,
+                bidder,
+            },
+        ),
+        &[
+            auction_program,
+            authority,
+            auction,
+            auction_extended,
+            bidder_metadata,
+        ],
+        &[&signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn process_accept_offer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: MetaplexAcceptOfferArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let auction_manager_info = next_account_info(account_info_iter)?;
+    let auction_info = next_account_info(account_info_iter)?;
+    let auction_data_extended_info = next_account_info(account_info_iter)?;
+    let bidder_metadata_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let auction_program_info = next_account_info(account_info_iter)?;
+
+    let auction_manager = get_auction_manager(auction_manager_info)?;
+    let auction = AuctionData::from_account_info(auction_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    if auction.authority != *auction_manager_info.key {
+        return Err(MetaplexError::AuctionAuthorityMismatch.into());
+    }
+
+    assert_authority_correct(&auction_manager.authority(), authority_info)?;
+    assert_owned_by(auction_info, &store.auction_program)?;
+    assert_owned_by(auction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    if auction_manager.store() != *store_info.key {
+        return Err(MetaplexError::AuctionManagerStoreMismatch.into());
+    }
+
+    if auction_manager.auction() != *auction_info.key {
+        return Err(MetaplexError::AuctionManagerAuctionMismatch.into());
+    }
+
+    if store.auction_program != *auction_program_info.key {
+        return Err(MetaplexError::AuctionManagerAuctionProgramMismatch.into());
+    }
+
+    if auction_manager.status() == AuctionManagerStatus::Finished {
+        return Err(MetaplexError::AuctionManagerInFishedState.into());
+    }
+
+    // A prize already taken back can't go to the new winner.
+    if auction_manager.offers_closed() {
+        return Err(MetaplexError::OffersClosed.into());
+    }
+
+    let auction_key = auction_manager.auction();
+    let seeds = &[PREFIX.as_bytes(), &auction_key.as_ref()];
+    let (_, bump_seed) = Pubkey::find_program_address(seeds, &program_id);
+    let authority_seeds = &[PREFIX.as_bytes(), &auction_key.as_ref(), &[bump_seed]];
+
+    issue_accept_offer(
+        auction_program_info.clone(),
+        auction_manager_info.clone(),
+        auction_info.clone(),
+        auction_data_extended_info.clone(),
+        bidder_metadata_info.clone(),
+        auction_manager.vault(),
+        args.bidder,
+        authority_seeds,
+    )?;
+
+    Ok(())
+}
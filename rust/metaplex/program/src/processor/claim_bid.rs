@@ -2,7 +2,7 @@ use {
     crate::{
         error::MetaplexError,
         state::{get_auction_manager, AuctionManagerStatus, Store, PREFIX},
-        utils::{assert_derivation, assert_owned_by},
+        utils::{assert_derivation, assert_initialized, assert_owned_by},
     },
     metaplex_auction::{
         instruction::claim_bid_instruction,
@@ -16,13 +16,15 @@ use {
         program::invoke_signed,
         pubkey::Pubkey,
     },
+    spl_token::state::Account,
 };
 
 #[allow(clippy::too_many_arguments)]
 pub fn issue_claim_bid<'a>(
     auction_program: AccountInfo<'a>,
     auction: AccountInfo<'a>,
-    auction_extended: Option<AccountInfo<'a>>,
+    auction_extended: AccountInfo<'a>,
+    bidder_token: Option<AccountInfo<'a>>,
     accept_payment: AccountInfo<'a>,
     authority: AccountInfo<'a>,
     bidder: AccountInfo<'a>,
@@ -45,12 +47,13 @@ pub fn issue_claim_bid<'a>(
         bidder_pot,
         accept_payment.clone(),
         token_program,
+        auction_extended.clone(),
     ];
 
-    let mut auction_extended_key: Option<Pubkey> = None;
-    if let Some(auction_extended_account) = auction_extended {
-        auction_extended_key = Some(*auction_extended_account.key);
-        account_infos.push(auction_extended_account);
+    let mut bidder_token_key: Option<Pubkey> = None;
+    if let Some(bidder_token_account) = bidder_token {
+        bidder_token_key = Some(*bidder_token_account.key);
+        account_infos.push(bidder_token_account);
     }
     invoke_signed(
         &claim_bid_instruction(
@@ -60,7 +63,8 @@ pub fn issue_claim_bid<'a>(
             *bidder.key,
             *bidder_pot_token_acct.key,
             *token_mint.key,
-            auction_extended_key,
+            *auction_extended.key,
+            bidder_token_key,
             ClaimBidArgs { resource: vault },
         ),
         account_infos.as_ref(),
@@ -84,7 +88,8 @@ pub fn process_claim_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
     let auction_program_info = next_account_info(account_info_iter)?;
     let clock_info = next_account_info(account_info_iter)?;
     let token_program_info = next_account_info(account_info_iter)?;
-    let auction_extended_info = next_account_info(account_info_iter).ok();
+    let auction_extended_info = next_account_info(account_info_iter)?;
+    let bidder_token_info = next_account_info(account_info_iter).ok();
 
     let mut auction_manager = get_auction_manager(auction_manager_info)?;
     let store = Store::from_account_info(store_info)?;
@@ -93,15 +98,11 @@ pub fn process_claim_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
 
     assert_owned_by(auction_info, &store.auction_program)?;
     assert_owned_by(auction_manager_info, program_id)?;
-    assert_owned_by(accept_payment_info, &spl_token::id())?;
-    assert_owned_by(bidder_pot_token_info, &spl_token::id())?;
     assert_owned_by(bidder_pot_info, &store.auction_program)?;
     assert_owned_by(token_mint_info, &spl_token::id())?;
     assert_owned_by(vault_info, &store.token_vault_program)?;
     assert_owned_by(store_info, program_id)?;
-    if let Some(auction_extended) = auction_extended_info {
-        assert_owned_by(auction_extended, &store.auction_program)?;
-    }
+    assert_owned_by(auction_extended_info, &store.auction_program)?;
 
     if auction_manager.store() != *store_info.key {
         return Err(MetaplexError::AuctionManagerStoreMismatch.into());
@@ -119,19 +120,38 @@ pub fn process_claim_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
         return Err(MetaplexError::AuctionManagerTokenProgramMismatch.into());
     }
 
-    if auction_manager.accept_payment() != *accept_payment_info.key {
+    // Native SOL bids are paid into the auction manager, and the auction program checks the pot.
+    let native_sol = auction_manager.accept_payment() == *auction_manager_info.key;
+
+    // Claiming an emptied pot only forfeits the winner's deposit. No payout splits it, so it goes
+    // straight to the auctioneer instead of the accept payment account.
+    if token_pot_info.emptied {
+        if native_sol {
+            if *accept_payment_info.key != auction_manager.authority() {
+                return Err(MetaplexError::IncorrectOwner.into());
+            }
+        } else {
+            assert_owned_by(accept_payment_info, &spl_token::id())?;
+            let destination: Account = assert_initialized(accept_payment_info)?;
+            if destination.owner != auction_manager.authority() {
+                return Err(MetaplexError::IncorrectOwner.into());
+            }
+        }
+    } else if auction_manager.accept_payment() != *accept_payment_info.key {
         return Err(MetaplexError::AcceptPaymentMismatch.into());
     }
 
+    if !native_sol {
+        assert_owned_by(accept_payment_info, &spl_token::id())?;
+        assert_owned_by(bidder_pot_token_info, &spl_token::id())?;
+    }
+
     if auction_manager.vault() != *vault_info.key {
         return Err(MetaplexError::AuctionManagerVaultMismatch.into());
     }
 
-    let mut instant_sale_price: Option<u64> = None;
-    if let Some(auction_extended) = auction_extended_info {
-        instant_sale_price =
-            AuctionDataExtended::get_instant_sale_price(&auction_extended.data.borrow());
-    }
+    let instant_sale_price =
+        AuctionDataExtended::get_instant_sale_price(&auction_extended_info.data.borrow());
     if !instant_sale_price.is_some() {
         if auction.state != AuctionState::Ended {
             return Err(MetaplexError::AuctionHasNotEnded.into());
@@ -161,7 +181,8 @@ pub fn process_claim_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> Progr
     issue_claim_bid(
         auction_program_info.clone(),
         auction_info.clone(),
-        auction_extended_info.map_or(None, |acc| Some(acc.clone())),
+        auction_extended_info.clone(),
+        bidder_token_info.map_or(None, |acc| Some(acc.clone())),
         accept_payment_info.clone(),
         auction_manager_info.clone(),
         bidder_info.clone(),
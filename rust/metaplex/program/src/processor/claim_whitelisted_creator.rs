@@ -0,0 +1,59 @@
+use {
+    crate::{
+        error::MetaplexError,
+        processor::set_whitelisted_creator::set_whitelisted_creator,
+        state::Store,
+        utils::{assert_owned_by, assert_signer, verify_merkle_proof},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        hash,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_claim_whitelisted_creator<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let whitelisted_creator_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let store = Store::from_account_info(store_info)?;
+    let root = match store.creator_allowlist_root {
+        Some(root) => root,
+        None => return Err(MetaplexError::StoreHasNoCreatorAllowlist.into()),
+    };
+
+    let leaf = hash::hashv(&[creator_info.key.as_ref()]).to_bytes();
+    if !verify_merkle_proof(leaf, &proof, &root) {
+        return Err(MetaplexError::CreatorNotInAllowlist.into());
+    }
+
+    // An entry the admin set up, or deactivated, takes precedence over the allowlist.
+    if !whitelisted_creator_info.data_is_empty() {
+        return Err(MetaplexError::AlreadyInitialized.into());
+    }
+
+    set_whitelisted_creator(
+        program_id,
+        whitelisted_creator_info,
+        creator_info.key,
+        store_info,
+        payer_info,
+        system_info,
+        rent_info,
+        true,
+    )
+}
@@ -0,0 +1,72 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{Store, WhitelistedCreator, PREFIX},
+        utils::{assert_derivation, assert_owned_by, assert_signer},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_close_whitelisted_creator<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let whitelisted_creator_info = next_account_info(account_info_iter)?;
+    let admin_wallet_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+
+    assert_signer(admin_wallet_info)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_owned_by(whitelisted_creator_info, program_id)?;
+
+    assert_derivation(
+        program_id,
+        store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            admin_wallet_info.key.as_ref(),
+        ],
+    )?;
+
+    let whitelisted_creator = WhitelistedCreator::from_account_info(whitelisted_creator_info)?;
+    assert_derivation(
+        program_id,
+        whitelisted_creator_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_info.key.as_ref(),
+            whitelisted_creator.address.as_ref(),
+        ],
+    )?;
+
+    // The deactivated entry is what keeps the creator from claiming their way back in through
+    // the allowlist, so it has to stay while there is one.
+    let store = Store::from_account_info(store_info)?;
+    if !whitelisted_creator.activated && store.creator_allowlist_root.is_some() {
+        return Err(MetaplexError::CannotCloseDeactivatedCreator.into());
+    }
+
+    // Items are only checked against the whitelist during validation, so an AuctionManager that
+    // already got past it keeps working after the entry is gone.
+    let lamports = whitelisted_creator_info.lamports();
+    **destination_info.lamports.borrow_mut() = destination_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    **whitelisted_creator_info.lamports.borrow_mut() = 0;
+
+    for byte in whitelisted_creator_info.data.borrow_mut().iter_mut() {
+        *byte = 0;
+    }
+
+    Ok(())
+}
@@ -41,6 +41,7 @@ pub fn process_deprecated_init_auction_manager_v1(
         store_info,
         accept_payment_info,
         authority_info,
+        None,
     )?;
 
     if auction_manager_settings.winning_configs.len() != auction.num_possible_winners() as usize {
@@ -6,7 +6,7 @@ use {
         instruction::EmptyPaymentAccountArgs,
         state::{
             get_auction_manager, AuctionManager, Key, PayoutTicket, Store, MAX_PAYOUT_TICKET_SIZE,
-            PREFIX, TOTALS,
+            MAX_STORE_FEE_BASIS_POINTS, PREFIX, TOTALS,
         },
         utils::{
             assert_derivation, assert_initialized, assert_is_ata, assert_owned_by,
@@ -14,8 +14,7 @@ use {
             spl_token_transfer,
         },
     },
-    borsh::BorshSerialize,
-    metaplex_auction::processor::AuctionData,
+    metaplex_auction::processor::{AuctionData, AuctionDataExtended},
     metaplex_token_metadata::state::{MasterEditionV1, Metadata},
     metaplex_token_vault::state::SafetyDepositBox,
     solana_program::{
@@ -28,6 +27,7 @@ use {
         sysvar::Sysvar,
     },
     spl_token::state::Account,
+    std::cmp,
 };
 
 fn assert_destination_ownership_validity(
@@ -75,24 +75,78 @@ fn assert_destination_ownership_validity(
     Ok(())
 }
 
+/// Native SOL payouts go straight to the creator's or auctioneer's wallet.
+fn assert_native_destination_validity(
+    auction_manager: &Box<dyn AuctionManager>,
+    metadata: &Metadata,
+    destination_info: &AccountInfo,
+    creator_index: Option<u8>,
+) -> ProgramResult {
+    let expected = match (&metadata.data.creators, creator_index) {
+        (Some(creators), Some(index)) => match creators.get(index as usize) {
+            Some(creator) => creator.address,
+            None => return Err(MetaplexError::InvalidCreatorIndex.into()),
+        },
+        _ => auction_manager.authority(),
+    };
+
+    if *destination_info.key != expected {
+        return Err(MetaplexError::IncorrectOwner.into());
+    }
+
+    Ok(())
+}
+
+/// The store fee goes to the store's fee destination wallet, or its token account for SPL
+/// auctions.
+fn assert_fee_destination_validity(
+    store: &Store,
+    fee_destination_info: &AccountInfo,
+    accept_payment_info: &AccountInfo,
+    native_sol: bool,
+) -> ProgramResult {
+    if native_sol {
+        if *fee_destination_info.key != store.fee_destination {
+            return Err(MetaplexError::StoreFeeDestinationMismatch.into());
+        }
+        return Ok(());
+    }
+
+    assert_owned_by(fee_destination_info, &store.token_program)?;
+    let fee_destination: Account = assert_initialized(fee_destination_info)?;
+    let accept_payment: Account = assert_initialized(accept_payment_info)?;
+    if fee_destination.owner != store.fee_destination {
+        return Err(MetaplexError::StoreFeeDestinationMismatch.into());
+    }
+    if fee_destination.mint != accept_payment.mint {
+        return Err(MetaplexError::AcceptPaymentMintMismatch.into());
+    }
+
+    Ok(())
+}
+
 fn calculate_owed_amount(
     auction_token_tracker_info: Option<&AccountInfo>,
     safety_deposit_config_info: Option<&AccountInfo>,
     auction_manager: &Box<dyn AuctionManager>,
     auction: &AuctionData,
+    auction_extended: Option<&AuctionDataExtended>,
     metadata: &Metadata,
+    primary_sale_happened: bool,
     winning_config_index: &Option<u8>,
     winning_config_item_index: &Option<u8>,
     creator_index: &Option<u8>,
 ) -> Result<u64, ProgramError> {
-    let primary_sale_happened = auction_manager.get_primary_sale_happened(
-        metadata,
-        *winning_config_index,
-        *winning_config_item_index,
-    )?;
-
     let mut amount_available_to_split: u128 = match winning_config_index {
-        Some(index) => auction.bid_state.amount(*index as usize) as u128,
+        Some(index) => {
+            // Only what the auction collected from the winner is split, which in Vickrey and
+            // uniform price auctions can be less than their bid.
+            let index = *index as usize;
+            let auction_extended = auction_extended.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            auction
+                .settlement_price(auction_extended, index)
+                .unwrap_or_else(|| auction.bid_state.amount(index)) as u128
+        }
         None => {
             // this means the amount owed is the amount collected from participation nft bids.
             auction_manager.get_collected_to_accept_payment(safety_deposit_config_info)?
@@ -234,6 +288,8 @@ pub fn process_empty_payment_account(
     let rent_info = next_account_info(account_info_iter)?;
     let auction_token_tracker_info = next_account_info(account_info_iter).ok();
     let safety_deposit_config_info = next_account_info(account_info_iter).ok();
+    let auction_extended_info = next_account_info(account_info_iter).ok();
+    let fee_destination_info = next_account_info(account_info_iter).ok();
 
     if let Some(tracker_info) = auction_token_tracker_info {
         assert_derivation(
@@ -255,18 +311,14 @@ pub fn process_empty_payment_account(
     let safety_deposit = SafetyDepositBox::from_account_info(safety_deposit_info)?;
     let metadata = Metadata::from_account_info(metadata_info)?;
     let auction = AuctionData::from_account_info(auction_info)?;
-    let destination: Account = assert_initialized(destination_info)?;
-    let accept_payment: Account = assert_initialized(accept_payment_info)?;
+
+    // Native SOL auctions collect lamports in the auction manager rather than a token account.
+    let native_sol = *accept_payment_info.key == *auction_manager_info.key;
 
     if auction_manager.store() != *store_info.key {
         return Err(MetaplexError::AuctionManagerStoreMismatch.into());
     }
 
-    msg!(
-        "At this point, accept payment has {:?} in it",
-        accept_payment.amount
-    );
-
     // Before continuing further, assert all bid monies have been pushed to the main escrow
     // account so that we have a complete (less the unredeemed participation nft bids) accounting
     // to work with
@@ -280,8 +332,6 @@ pub fn process_empty_payment_account(
     if !payout_ticket_info.data_is_empty() {
         assert_owned_by(payout_ticket_info, program_id)?;
     }
-    assert_owned_by(destination_info, token_program_info.key)?;
-    assert_owned_by(accept_payment_info, token_program_info.key)?;
     assert_owned_by(metadata_info, &store.token_metadata_program)?;
     if *master_edition_info.key != solana_program::system_program::id() {
         assert_owned_by(master_edition_info, &store.token_metadata_program)?;
@@ -290,7 +340,6 @@ pub fn process_empty_payment_account(
     assert_owned_by(store_info, program_id)?;
     assert_owned_by(vault_info, &store.token_vault_program)?;
     assert_owned_by(auction_info, &store.auction_program)?;
-    assert_rent_exempt(rent, destination_info)?;
 
     // Assert the winning config points to the safety deposit you sent up
     auction_manager.assert_winning_config_safety_deposit_validity(
@@ -309,14 +358,45 @@ pub fn process_empty_payment_account(
 
     // assert the destination account matches the ownership expected to creator or auction manager authority
     // given in the argument's creator index
-    assert_destination_ownership_validity(
-        &auction_manager,
-        &metadata,
-        destination_info,
-        &destination,
-        &store,
-        args.creator_index,
-    )?;
+    let recipient = if native_sol {
+        msg!(
+            "At this point, accept payment has {:?} in it",
+            auction_manager_info.lamports()
+        );
+
+        assert_native_destination_validity(
+            &auction_manager,
+            &metadata,
+            destination_info,
+            args.creator_index,
+        )?;
+        *destination_info.key
+    } else {
+        assert_owned_by(destination_info, token_program_info.key)?;
+        assert_owned_by(accept_payment_info, token_program_info.key)?;
+        assert_rent_exempt(rent, destination_info)?;
+        let destination: Account = assert_initialized(destination_info)?;
+        let accept_payment: Account = assert_initialized(accept_payment_info)?;
+
+        msg!(
+            "At this point, accept payment has {:?} in it",
+            accept_payment.amount
+        );
+
+        assert_destination_ownership_validity(
+            &auction_manager,
+            &metadata,
+            destination_info,
+            &destination,
+            &store,
+            args.creator_index,
+        )?;
+
+        if destination.mint != accept_payment.mint {
+            return Err(MetaplexError::AcceptPaymentMintMismatch.into());
+        }
+        destination.owner
+    };
 
     // further assert that the vault and safety deposit are correctly matched to the auction manager
     if auction_manager.vault() != *vault_info.key {
@@ -327,6 +407,25 @@ pub fn process_empty_payment_account(
         return Err(MetaplexError::AuctionManagerAuctionMismatch.into());
     }
 
+    let auction_extended = match auction_extended_info {
+        Some(auction_extended_info) => {
+            assert_derivation(
+                &store.auction_program,
+                auction_extended_info,
+                &[
+                    metaplex_auction::PREFIX.as_bytes(),
+                    store.auction_program.as_ref(),
+                    vault_info.key.as_ref(),
+                    metaplex_auction::EXTENDED.as_bytes(),
+                ],
+            )?;
+            Some(AuctionDataExtended::from_account_info(
+                auction_extended_info,
+            )?)
+        }
+        None => None,
+    };
+
     if safety_deposit.vault != *vault_info.key {
         return Err(MetaplexError::SafetyDepositBoxVaultMismatch.into());
     }
@@ -354,10 +453,6 @@ pub fn process_empty_payment_account(
         return Err(MetaplexError::AcceptPaymentMismatch.into());
     }
 
-    if destination.mint != accept_payment.mint {
-        return Err(MetaplexError::AcceptPaymentMintMismatch.into());
-    }
-
     let winning_config_index_key: String = match args.winning_config_index {
         Some(val) => val.to_string(),
         None => "participation".to_owned(),
@@ -383,7 +478,7 @@ pub fn process_empty_payment_account(
             winning_config_item_index_key.as_bytes(),
             creator_index_key.as_bytes(),
             &safety_deposit_info.key.as_ref(),
-            &destination.owner.as_ref(),
+            &recipient.as_ref(),
         ],
     )?;
 
@@ -394,7 +489,7 @@ pub fn process_empty_payment_account(
         winning_config_item_index_key.as_bytes(),
         creator_index_key.as_bytes(),
         &safety_deposit_info.key.as_ref(),
-        &destination.owner.as_ref(),
+        &recipient.as_ref(),
         &[payout_bump],
     ];
 
@@ -411,29 +506,67 @@ pub fn process_empty_payment_account(
     }
 
     let mut payout_ticket = PayoutTicket::from_account_info(payout_ticket_info)?;
-    payout_ticket.recipient = destination.owner;
+    payout_ticket.recipient = recipient;
     payout_ticket.key = Key::PayoutTicketV1;
 
+    let primary_sale_happened = auction_manager.get_primary_sale_happened(
+        &metadata,
+        args.winning_config_index,
+        args.winning_config_item_index,
+    )?;
+
     let amount = calculate_owed_amount(
         auction_token_tracker_info,
         safety_deposit_config_info,
         &auction_manager,
         &auction,
+        auction_extended.as_ref(),
         &metadata,
+        primary_sale_happened,
         &args.winning_config_index,
         &args.winning_config_item_index,
         &args.creator_index,
     )?;
 
-    let final_amount = amount
-        .checked_sub(payout_ticket.amount_paid)
+    let already_paid = payout_ticket
+        .amount_paid
+        .checked_add(payout_ticket.fee_paid)
         .ok_or(MetaplexError::NumericalOverflowError)?;
+    let remaining = amount
+        .checked_sub(already_paid)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    // The store takes its fee out of primary sales before anything is paid out. Tickets made
+    // before the store fee existed have no room to record it, so those payouts go on without one.
+    let fee_basis_points = if primary_sale_happened || PayoutTicket::is_legacy(payout_ticket_info) {
+        0
+    } else {
+        cmp::min(store.fee_basis_points, MAX_STORE_FEE_BASIS_POINTS)
+    };
+    let fee = ((remaining as u128)
+        .checked_mul(fee_basis_points as u128)
+        .ok_or(MetaplexError::NumericalOverflowError)?
+        / 10000) as u64;
+    let final_amount = remaining - fee;
+
+    if fee > 0 {
+        match fee_destination_info {
+            Some(info) => {
+                assert_fee_destination_validity(&store, info, accept_payment_info, native_sol)?
+            }
+            None => return Err(ProgramError::NotEnoughAccountKeys),
+        }
+    }
 
-    if final_amount > 0 {
+    if remaining > 0 {
         payout_ticket.amount_paid = payout_ticket
             .amount_paid
             .checked_add(final_amount)
             .ok_or(MetaplexError::NumericalOverflowError)?;
+        payout_ticket.fee_paid = payout_ticket
+            .fee_paid
+            .checked_add(fee)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
 
         let auction_key = auction_manager.auction();
 
@@ -445,17 +578,52 @@ pub fn process_empty_payment_account(
 
         let authority_seeds = &[PREFIX.as_bytes(), auction_key.as_ref(), &[bump_seed]];
 
-        spl_token_transfer(
-            accept_payment_info.clone(),
-            destination_info.clone(),
-            final_amount,
-            auction_manager_info.clone(),
-            authority_seeds,
-            token_program_info.clone(),
-        )?;
+        if native_sol {
+            // The auction manager has to stay rent exempt under whatever it pays out.
+            let left = auction_manager_info
+                .lamports()
+                .checked_sub(remaining)
+                .ok_or(MetaplexError::NumericalOverflowError)?;
+            if left < rent.minimum_balance(auction_manager_info.data_len()) {
+                return Err(MetaplexError::NotRentExempt.into());
+            }
+
+            **auction_manager_info.lamports.borrow_mut() = left;
+            **destination_info.lamports.borrow_mut() = destination_info
+                .lamports()
+                .checked_add(final_amount)
+                .ok_or(MetaplexError::NumericalOverflowError)?;
+            if let Some(info) = fee_destination_info.filter(|_| fee > 0) {
+                **info.lamports.borrow_mut() = info
+                    .lamports()
+                    .checked_add(fee)
+                    .ok_or(MetaplexError::NumericalOverflowError)?;
+            }
+        } else {
+            if final_amount > 0 {
+                spl_token_transfer(
+                    accept_payment_info.clone(),
+                    destination_info.clone(),
+                    final_amount,
+                    auction_manager_info.clone(),
+                    authority_seeds,
+                    token_program_info.clone(),
+                )?;
+            }
+            if let Some(info) = fee_destination_info.filter(|_| fee > 0) {
+                spl_token_transfer(
+                    accept_payment_info.clone(),
+                    info.clone(),
+                    fee,
+                    auction_manager_info.clone(),
+                    authority_seeds,
+                    token_program_info.clone(),
+                )?;
+            }
+        }
     }
 
-    payout_ticket.serialize(&mut *payout_ticket_info.data.borrow_mut())?;
+    payout_ticket.save(payout_ticket_info)?;
 
     Ok(())
 }
@@ -17,7 +17,7 @@ use {
         program_option::COption,
         pubkey::Pubkey,
     },
-    metaplex_auction::processor::{AuctionData, AuctionState},
+    metaplex_auction::processor::{AuctionData, AuctionDataExtended, AuctionState},
     spl_token::state::Account,
     metaplex_token_vault::state::{Vault, VaultState},
 };
@@ -30,16 +30,36 @@ pub fn assert_common_checks(
     store_info: &AccountInfo,
     accept_payment_info: &AccountInfo,
     authority_info: &AccountInfo,
+    auction_extended_info: Option<&AccountInfo>,
 ) -> Result<(u8, Vault, AuctionData), ProgramError> {
     let vault = Vault::from_account_info(vault_info)?;
     let auction = AuctionData::from_account_info(auction_info)?;
-    let accept_payment: Account = assert_initialized(accept_payment_info)?;
     // Assert it is real
     let store = Store::from_account_info(store_info)?;
     assert_owned_by(vault_info, &store.token_vault_program)?;
     assert_owned_by(auction_info, &store.auction_program)?;
     assert_owned_by(store_info, program_id)?;
-    assert_owned_by(accept_payment_info, &store.token_program)?;
+
+    // Native SOL auctions pay bids out as lamports, which the auction manager collects itself in
+    // place of a token account.
+    let mut native_sol = false;
+    if let Some(auction_extended) = auction_extended_info {
+        assert_owned_by(auction_extended, &store.auction_program)?;
+        assert_derivation(
+            &store.auction_program,
+            auction_extended,
+            &[
+                metaplex_auction::PREFIX.as_bytes(),
+                store.auction_program.as_ref(),
+                vault_info.key.as_ref(),
+                metaplex_auction::EXTENDED.as_bytes(),
+            ],
+        )?;
+        native_sol = AuctionDataExtended::from_account_info(auction_extended)?.native_sol;
+    }
+    if native_sol != (*accept_payment_info.key == *auction_manager_info.key) {
+        return Err(MetaplexError::AcceptPaymentNativeSolMismatch.into());
+    }
 
     if auction.authority != *auction_manager_info.key && auction.authority != *authority_info.key {
         return Err(MetaplexError::AuctionAuthorityMismatch.into());
@@ -69,20 +89,25 @@ pub fn assert_common_checks(
         ],
     )?;
 
-    if auction.token_mint != accept_payment.mint {
-        return Err(MetaplexError::AuctionAcceptPaymentMintMismatch.into());
-    }
+    if !native_sol {
+        assert_owned_by(accept_payment_info, &store.token_program)?;
+        let accept_payment: Account = assert_initialized(accept_payment_info)?;
 
-    if accept_payment.owner != *auction_manager_info.key {
-        return Err(MetaplexError::AcceptPaymentOwnerMismatch.into());
-    }
+        if auction.token_mint != accept_payment.mint {
+            return Err(MetaplexError::AuctionAcceptPaymentMintMismatch.into());
+        }
 
-    if accept_payment.delegate != COption::None {
-        return Err(MetaplexError::DelegateShouldBeNone.into());
-    }
+        if accept_payment.owner != *auction_manager_info.key {
+            return Err(MetaplexError::AcceptPaymentOwnerMismatch.into());
+        }
+
+        if accept_payment.delegate != COption::None {
+            return Err(MetaplexError::DelegateShouldBeNone.into());
+        }
 
-    if accept_payment.close_authority != COption::None {
-        return Err(MetaplexError::CloseAuthorityShouldBeNone.into());
+        if accept_payment.close_authority != COption::None {
+            return Err(MetaplexError::CloseAuthorityShouldBeNone.into());
+        }
     }
 
     if vault.state != VaultState::Combined {
@@ -115,6 +140,7 @@ pub fn process_init_auction_manager_v2(
     let store_info = next_account_info(account_info_iter)?;
     let system_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
+    let auction_extended_info = next_account_info(account_info_iter).ok();
 
     let (bump_seed, _vault, _auction) = assert_common_checks(
         program_id,
@@ -124,6 +150,7 @@ pub fn process_init_auction_manager_v2(
         store_info,
         accept_payment_info,
         authority_info,
+        auction_extended_info,
     )?;
 
     let authority_seeds = &[PREFIX.as_bytes(), &auction_info.key.as_ref(), &[bump_seed]];
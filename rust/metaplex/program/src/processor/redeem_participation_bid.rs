@@ -15,7 +15,9 @@ use {
     solana_program::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
+        program::invoke,
         pubkey::Pubkey,
+        system_instruction,
     },
     metaplex_auction::processor::{AuctionData, AuctionDataExtended, BidderMetadata},
     spl_token::state::Account,
@@ -187,12 +189,13 @@ fn charge_for_participation<'a>(
     accept_payment_info: &AccountInfo<'a>,
     transfer_authority_info: &AccountInfo<'a>,
     token_program_info: &AccountInfo<'a>,
+    system_info: &AccountInfo<'a>,
     safety_deposit_config_info: &AccountInfo<'a>,
     win_index: Option<usize>,
     config: &ParticipationConfigV2,
     auction_manager_bump: u8,
     auction_manager: &mut Box<dyn AuctionManager>,
-    bidder_token: &Account,
+    bidder_token: Option<&Account>,
     bidder_metadata: &BidderMetadata,
 ) -> ProgramResult {
     let auction_key = auction_manager.auction();
@@ -211,21 +214,38 @@ fn charge_for_participation<'a>(
         }
     }
 
-    if bidder_token.amount.saturating_sub(price) < 0 as u64 {
-        return Err(MetaplexError::NotEnoughBalanceForParticipation.into());
+    if let Some(bidder_token) = bidder_token {
+        if bidder_token.amount.saturating_sub(price) < 0 as u64 {
+            return Err(MetaplexError::NotEnoughBalanceForParticipation.into());
+        }
     }
 
     if price > 0 {
         auction_manager.add_to_collected_payment(safety_deposit_config_info, price)?;
 
-        spl_token_transfer(
-            bidder_token_account_info.clone(),
-            accept_payment_info.clone(),
-            price,
-            transfer_authority_info.clone(),
-            signer_seeds,
-            token_program_info.clone(),
-        )?;
+        match bidder_token {
+            Some(_) => spl_token_transfer(
+                bidder_token_account_info.clone(),
+                accept_payment_info.clone(),
+                price,
+                transfer_authority_info.clone(),
+                signer_seeds,
+                token_program_info.clone(),
+            )?,
+            // Native SOL auctions charge the transfer authority's wallet.
+            None => invoke(
+                &system_instruction::transfer(
+                    transfer_authority_info.key,
+                    accept_payment_info.key,
+                    price,
+                ),
+                &[
+                    transfer_authority_info.clone(),
+                    accept_payment_info.clone(),
+                    system_info.clone(),
+                ],
+            )?,
+        }
     }
     Ok(())
 }
@@ -326,19 +346,25 @@ pub fn process_redeem_participation_bid<'a>(
     let config: ParticipationConfigV2 =
         auction_manager.get_participation_config(safety_deposit_config_info)?;
 
-    assert_owned_by(accept_payment_info, token_program_info.key)?;
-    assert_owned_by(bidder_token_account_info, token_program_info.key)?;
-
-    let bidder_token: Account = assert_initialized(bidder_token_account_info)?;
-
-    if bidder_token.mint != AuctionData::get_token_mint(auction_info) {
-        return Err(MetaplexError::AcceptPaymentMintMismatch.into());
-    }
-
     if *accept_payment_info.key != auction_manager.accept_payment() {
         return Err(MetaplexError::AcceptPaymentMismatch.into());
     }
 
+    // Native SOL auctions collect participation payments in the auction manager as lamports.
+    let bidder_token: Option<Account> = if *accept_payment_info.key == *auction_manager_info.key {
+        None
+    } else {
+        assert_owned_by(accept_payment_info, token_program_info.key)?;
+        assert_owned_by(bidder_token_account_info, token_program_info.key)?;
+
+        let bidder_token: Account = assert_initialized(bidder_token_account_info)?;
+
+        if bidder_token.mint != AuctionData::get_token_mint(auction_info) {
+            return Err(MetaplexError::AcceptPaymentMintMismatch.into());
+        }
+        Some(bidder_token)
+    };
+
     let mut gets_participation =
         config.non_winning_constraint != NonWinningConstraint::NoParticipationPrize;
 
@@ -413,12 +439,13 @@ pub fn process_redeem_participation_bid<'a>(
             accept_payment_info,
             transfer_authority_info,
             token_program_info,
+            system_info,
             safety_deposit_config_info,
             win_index,
             &config,
             bump_seed,
             &mut auction_manager,
-            &bidder_token,
+            bidder_token.as_ref(),
             &bidder_metadata,
         )?;
     } else {
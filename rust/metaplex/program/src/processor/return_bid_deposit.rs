@@ -0,0 +1,156 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{get_auction_manager, Store, PREFIX},
+        utils::{assert_derivation, assert_owned_by},
+    },
+    metaplex_auction::{
+        instruction::return_deposit_instruction,
+        processor::{return_deposit::ReturnDepositArgs, AuctionData},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        pubkey::Pubkey,
+    },
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn issue_return_deposit<'a>(
+    auction_program: AccountInfo<'a>,
+    auction: AccountInfo<'a>,
+    auction_extended: AccountInfo<'a>,
+    bidder_token: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    bidder: AccountInfo<'a>,
+    bidder_pot: AccountInfo<'a>,
+    bidder_pot_token_acct: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    vault: Pubkey,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &return_deposit_instruction(
+            *auction_program.key,
+            *bidder_token.key,
+            *authority.key,
+            *bidder.key,
+            *bidder_pot_token_acct.key,
+            ReturnDepositArgs { resource: vault },
+        ),
+        &[
+            auction_program,
+            bidder_token,
+            bidder_pot_token_acct,
+            bidder_pot,
+            authority,
+            auction,
+            auction_extended,
+            bidder,
+            token_program,
+        ],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn process_return_bid_deposit<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let bidder_token_info = next_account_info(account_info_iter)?;
+    let bidder_pot_token_info = next_account_info(account_info_iter)?;
+    let bidder_pot_info = next_account_info(account_info_iter)?;
+    let auction_manager_info = next_account_info(account_info_iter)?;
+    let auction_info = next_account_info(account_info_iter)?;
+    let auction_extended_info = next_account_info(account_info_iter)?;
+    let bidder_info = next_account_info(account_info_iter)?;
+    let bidder_metadata_info = next_account_info(account_info_iter)?;
+    let bid_redemption_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let auction_program_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    let auction_manager = get_auction_manager(auction_manager_info)?;
+    let auction = AuctionData::from_account_info(auction_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    assert_owned_by(auction_info, &store.auction_program)?;
+    assert_owned_by(auction_manager_info, program_id)?;
+    assert_owned_by(bidder_metadata_info, &store.auction_program)?;
+    assert_owned_by(store_info, program_id)?;
+
+    if auction_manager.store() != *store_info.key {
+        return Err(MetaplexError::AuctionManagerStoreMismatch.into());
+    }
+
+    if auction_manager.auction() != *auction_info.key {
+        return Err(MetaplexError::AuctionManagerAuctionMismatch.into());
+    }
+
+    if auction.authority != *auction_manager_info.key {
+        return Err(MetaplexError::AuctionAuthorityMismatch.into());
+    }
+
+    if store.auction_program != *auction_program_info.key {
+        return Err(MetaplexError::AuctionManagerAuctionProgramMismatch.into());
+    }
+
+    if store.token_program != *token_program_info.key {
+        return Err(MetaplexError::AuctionManagerTokenProgramMismatch.into());
+    }
+
+    assert_derivation(
+        &store.auction_program,
+        bidder_metadata_info,
+        &[
+            metaplex_auction::PREFIX.as_bytes(),
+            store.auction_program.as_ref(),
+            auction_info.key.as_ref(),
+            bidder_info.key.as_ref(),
+            "metadata".as_bytes(),
+        ],
+    )?;
+
+    // The bid redemption ticket is only written once the winner redeemed, and that is what the
+    // deposit was held back for.
+    assert_derivation(
+        program_id,
+        bid_redemption_info,
+        &[
+            PREFIX.as_bytes(),
+            auction_info.key.as_ref(),
+            bidder_metadata_info.key.as_ref(),
+        ],
+    )?;
+    if bid_redemption_info.data_is_empty() || bid_redemption_info.owner != program_id {
+        return Err(MetaplexError::BidNotRedeemed.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        auction_manager_info,
+        &[PREFIX.as_bytes(), auction_manager.auction().as_ref()],
+    )?;
+    let auction_key = auction_manager.auction();
+    let authority_seeds = &[PREFIX.as_bytes(), auction_key.as_ref(), &[bump_seed]];
+
+    issue_return_deposit(
+        auction_program_info.clone(),
+        auction_info.clone(),
+        auction_extended_info.clone(),
+        bidder_token_info.clone(),
+        auction_manager_info.clone(),
+        bidder_info.clone(),
+        bidder_pot_info.clone(),
+        bidder_pot_token_info.clone(),
+        token_program_info.clone(),
+        auction_manager.vault(),
+        authority_seeds,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use {
+    crate::{
+        state::{Store, PREFIX},
+        utils::{assert_derivation, assert_owned_by, assert_signer},
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_store_creator_allowlist<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    root: Option<[u8; 32]>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let store_info = next_account_info(account_info_iter)?;
+    let admin_wallet_info = next_account_info(account_info_iter)?;
+
+    assert_signer(admin_wallet_info)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_derivation(
+        program_id,
+        store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            admin_wallet_info.key.as_ref(),
+        ],
+    )?;
+
+    let mut store = Store::from_account_info(store_info)?;
+    store.creator_allowlist_root = root;
+    store.serialize(&mut *store_info.data.borrow_mut())?;
+    Ok(())
+}
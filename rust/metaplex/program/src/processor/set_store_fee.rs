@@ -0,0 +1,47 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{Store, MAX_STORE_FEE_BASIS_POINTS, PREFIX},
+        utils::{assert_derivation, assert_owned_by, assert_signer},
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_store_fee<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    fee_basis_points: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let store_info = next_account_info(account_info_iter)?;
+    let admin_wallet_info = next_account_info(account_info_iter)?;
+    let fee_destination_info = next_account_info(account_info_iter)?;
+
+    assert_signer(admin_wallet_info)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_derivation(
+        program_id,
+        store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            admin_wallet_info.key.as_ref(),
+        ],
+    )?;
+
+    if fee_basis_points > MAX_STORE_FEE_BASIS_POINTS {
+        return Err(MetaplexError::StoreFeeTooHigh.into());
+    }
+
+    let mut store = Store::from_account_info(store_info)?;
+    store.fee_basis_points = fee_basis_points;
+    store.fee_destination = *fee_destination_info.key;
+    store.serialize(&mut *store_info.data.borrow_mut())?;
+    Ok(())
+}
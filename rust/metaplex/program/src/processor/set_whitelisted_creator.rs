@@ -13,37 +13,22 @@ use {
     },
 };
 
-pub fn process_set_whitelisted_creator<'a>(
-    program_id: &'a Pubkey,
-    accounts: &'a [AccountInfo<'a>],
+/// Creates the WhitelistedCreator pda of `creator` for the store if it does not exist yet, and
+/// sets whether it is activated. The store is expected to be checked by the caller.
+#[allow(clippy::too_many_arguments)]
+pub fn set_whitelisted_creator<'a>(
+    program_id: &Pubkey,
+    whitelisted_creator_info: &AccountInfo<'a>,
+    creator: &Pubkey,
+    store_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
     activated: bool,
 ) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-
-    let whitelisted_creator_info = next_account_info(account_info_iter)?;
-    let admin_wallet_info = next_account_info(account_info_iter)?;
-    let payer_info = next_account_info(account_info_iter)?;
-    let creator_info = next_account_info(account_info_iter)?;
-    let store_info = next_account_info(account_info_iter)?;
-    let system_info = next_account_info(account_info_iter)?;
-    let rent_info = next_account_info(account_info_iter)?;
-
-    assert_signer(payer_info)?;
-    assert_signer(admin_wallet_info)?;
     if !whitelisted_creator_info.data_is_empty() {
         assert_owned_by(whitelisted_creator_info, program_id)?;
     }
-    assert_owned_by(store_info, program_id)?;
-
-    assert_derivation(
-        program_id,
-        store_info,
-        &[
-            PREFIX.as_bytes(),
-            program_id.as_ref(),
-            admin_wallet_info.key.as_ref(),
-        ],
-    )?;
 
     let creator_bump = assert_derivation(
         program_id,
@@ -52,7 +37,7 @@ pub fn process_set_whitelisted_creator<'a>(
             PREFIX.as_bytes(),
             program_id.as_ref(),
             store_info.key.as_ref(),
-            creator_info.key.as_ref(),
+            creator.as_ref(),
         ],
     )?;
 
@@ -68,7 +53,7 @@ pub fn process_set_whitelisted_creator<'a>(
                 PREFIX.as_bytes(),
                 program_id.as_ref(),
                 store_info.key.as_ref(),
-                creator_info.key.as_ref(),
+                creator.as_ref(),
                 &[creator_bump],
             ],
         )?;
@@ -76,9 +61,50 @@ pub fn process_set_whitelisted_creator<'a>(
 
     let mut whitelisted_creator = WhitelistedCreator::from_account_info(whitelisted_creator_info)?;
     whitelisted_creator.key = Key::WhitelistedCreatorV1;
-    whitelisted_creator.address = *creator_info.key;
+    whitelisted_creator.address = *creator;
     whitelisted_creator.activated = activated;
 
     whitelisted_creator.serialize(&mut *whitelisted_creator_info.data.borrow_mut())?;
     Ok(())
 }
+
+pub fn process_set_whitelisted_creator<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    activated: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let whitelisted_creator_info = next_account_info(account_info_iter)?;
+    let admin_wallet_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_signer(admin_wallet_info)?;
+    assert_owned_by(store_info, program_id)?;
+
+    assert_derivation(
+        program_id,
+        store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            admin_wallet_info.key.as_ref(),
+        ],
+    )?;
+
+    set_whitelisted_creator(
+        program_id,
+        whitelisted_creator_info,
+        creator_info.key,
+        store_info,
+        payer_info,
+        system_info,
+        rent_info,
+        activated,
+    )
+}
@@ -0,0 +1,62 @@
+use {
+    crate::{
+        processor::set_whitelisted_creator::set_whitelisted_creator,
+        state::PREFIX,
+        utils::{assert_derivation, assert_owned_by, assert_signer},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_whitelisted_creators<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    activated: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let admin_wallet_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_signer(admin_wallet_info)?;
+    assert_owned_by(store_info, program_id)?;
+
+    assert_derivation(
+        program_id,
+        store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            admin_wallet_info.key.as_ref(),
+        ],
+    )?;
+
+    // The rest of the accounts come in pairs of whitelisted creator pda and creator key.
+    let pairs = account_info_iter.as_slice().chunks_exact(2);
+    if pairs.len() == 0 || !pairs.remainder().is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    for pair in pairs {
+        set_whitelisted_creator(
+            program_id,
+            &pair[0],
+            pair[1].key,
+            store_info,
+            payer_info,
+            system_info,
+            rent_info,
+            activated,
+        )?;
+    }
+
+    Ok(())
+}
@@ -6,13 +6,15 @@ use {
     },
     metaplex_auction::{
         instruction::{start_auction_instruction, StartAuctionArgs},
-        processor::AuctionData,
+        processor::{AuctionData, AuctionDataExtended},
     },
     solana_program::{
         account_info::{next_account_info, AccountInfo},
+        clock::Clock,
         entrypoint::ProgramResult,
         program::invoke_signed,
         pubkey::Pubkey,
+        sysvar::Sysvar,
     },
 };
 
@@ -21,16 +23,25 @@ pub fn issue_start_auction<'a>(
     authority: AccountInfo<'a>,
     auction: AccountInfo<'a>,
     clock: AccountInfo<'a>,
+    auction_extended: Option<AccountInfo<'a>>,
     vault: Pubkey,
     signer_seeds: &[&[u8]],
 ) -> ProgramResult {
+    let mut account_infos = vec![auction_program.clone(), authority.clone(), auction, clock];
+
+    let mut auction_extended_key: Option<Pubkey> = None;
+    if let Some(auction_extended_account) = auction_extended {
+        auction_extended_key = Some(*auction_extended_account.key);
+        account_infos.push(auction_extended_account);
+    }
     invoke_signed(
         &start_auction_instruction(
             *auction_program.key,
             *authority.key,
+            auction_extended_key,
             StartAuctionArgs { resource: vault },
         ),
-        &[auction_program, authority, auction, clock],
+        account_infos.as_ref(),
         &[&signer_seeds],
     )?;
 
@@ -45,6 +56,7 @@ pub fn process_start_auction(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     let store_info = next_account_info(account_info_iter)?;
     let auction_program_info = next_account_info(account_info_iter)?;
     let clock_info = next_account_info(account_info_iter)?;
+    let auction_extended_info = next_account_info(account_info_iter).ok();
 
     let mut auction_manager = get_auction_manager(auction_manager_info)?;
     let auction = AuctionData::from_account_info(auction_info)?;
@@ -54,7 +66,20 @@ pub fn process_start_auction(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
         return Err(MetaplexError::AuctionAuthorityMismatch.into());
     }
 
-    assert_authority_correct(&auction_manager.authority(), authority_info)?;
+    // Once its scheduled start time has passed, anyone may start the auction. The auction
+    // program checks the extended account belongs to the auction.
+    let mut scheduled_start_passed = false;
+    if let Some(auction_extended) = auction_extended_info {
+        assert_owned_by(auction_extended, &store.auction_program)?;
+        let clock = Clock::from_account_info(clock_info)?;
+        let auction_extended = AuctionDataExtended::from_account_info(auction_extended)?;
+        if let Some(start_at) = auction_extended.start_at {
+            scheduled_start_passed = clock.unix_timestamp >= start_at;
+        }
+    }
+    if !scheduled_start_passed {
+        assert_authority_correct(&auction_manager.authority(), authority_info)?;
+    }
     assert_owned_by(auction_info, &store.auction_program)?;
     assert_owned_by(auction_manager_info, program_id)?;
     assert_owned_by(store_info, program_id)?;
@@ -85,6 +110,7 @@ pub fn process_start_auction(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
         auction_manager_info.clone(),
         auction_info.clone(),
         clock_info.clone(),
+        auction_extended_info.map_or(None, |acc| Some(acc.clone())),
         auction_manager.vault(),
         authority_seeds,
     )?;
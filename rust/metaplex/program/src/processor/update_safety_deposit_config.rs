@@ -0,0 +1,249 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            AuctionManager, AuctionManagerStatus, AuctionManagerV2, AuctionWinnerTokenTypeTracker,
+            SafetyDepositConfig, Store, WinningConfigType, PREFIX, TOTALS,
+        },
+        utils::{
+            assert_authority_correct, assert_derivation, assert_initialized, assert_owned_by,
+            assert_store_safety_vault_manager_match,
+        },
+    },
+    metaplex_token_metadata::state::{MasterEditionV2, Metadata},
+    metaplex_token_vault::state::{SafetyDepositBox, Vault},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+};
+
+/// The box was validated once already, so only the amounts that can change with the config are
+/// checked again here.
+fn assert_new_amounts_supplied(
+    winning_config_type: WinningConfigType,
+    total_amount_requested: u64,
+    safety_deposit_token_store_info: &AccountInfo,
+    edition_info: &AccountInfo,
+    metadata: &Metadata,
+    store: &Store,
+) -> ProgramResult {
+    let safety_deposit_token_store: Account = assert_initialized(safety_deposit_token_store_info)?;
+
+    match winning_config_type {
+        WinningConfigType::FullRightsTransfer => {
+            if total_amount_requested != 1 {
+                return Err(MetaplexError::NotEnoughTokensToSupplyWinners.into());
+            }
+        }
+        WinningConfigType::TokenOnlyTransfer => {
+            if safety_deposit_token_store.amount < total_amount_requested {
+                return Err(MetaplexError::NotEnoughTokensToSupplyWinners.into());
+            }
+        }
+        WinningConfigType::PrintingV2 => {
+            let (edition_key, _) = Pubkey::find_program_address(
+                &[
+                    metaplex_token_metadata::state::PREFIX.as_bytes(),
+                    store.token_metadata_program.as_ref(),
+                    metadata.mint.as_ref(),
+                    metaplex_token_metadata::state::EDITION.as_bytes(),
+                ],
+                &store.token_metadata_program,
+            );
+            if edition_key != *edition_info.key {
+                return Err(MetaplexError::InvalidEditionAddress.into());
+            }
+
+            let master_edition = MasterEditionV2::from_account_info(edition_info)?;
+            if let Some(max) = master_edition.max_supply {
+                let amount_available = max
+                    .checked_sub(master_edition.supply)
+                    .ok_or(MetaplexError::NumericalOverflowError)?;
+                if amount_available < total_amount_requested {
+                    return Err(MetaplexError::NotEnoughTokensToSupplyWinners.into());
+                }
+            }
+        }
+        WinningConfigType::PrintingV1 => {
+            return Err(MetaplexError::PrintingV1NotAllowedWithAuctionManagerV2.into())
+        }
+        WinningConfigType::Participation => {}
+    }
+
+    Ok(())
+}
+
+pub fn process_update_safety_deposit_config<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    mut safety_deposit_config: SafetyDepositConfig,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let safety_deposit_config_info = next_account_info(account_info_iter)?;
+    let auction_token_tracker_info = next_account_info(account_info_iter)?;
+    let auction_manager_info = next_account_info(account_info_iter)?;
+    let safety_deposit_info = next_account_info(account_info_iter)?;
+    let safety_deposit_token_store_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(safety_deposit_config_info, program_id)?;
+    assert_owned_by(auction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let mut auction_manager = AuctionManagerV2::from_account_info(auction_manager_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    // Configs are final once the auction starts.
+    if auction_manager.status() != AuctionManagerStatus::Initialized
+        && auction_manager.status() != AuctionManagerStatus::Validated
+    {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    assert_authority_correct(&auction_manager.authority(), authority_info)?;
+
+    if auction_manager.store() != *store_info.key {
+        return Err(MetaplexError::AuctionManagerStoreMismatch.into());
+    }
+
+    assert_owned_by(safety_deposit_info, &store.token_vault_program)?;
+    assert_owned_by(safety_deposit_token_store_info, &store.token_program)?;
+    assert_owned_by(metadata_info, &store.token_metadata_program)?;
+    assert_store_safety_vault_manager_match(
+        &auction_manager.vault(),
+        safety_deposit_info,
+        vault_info,
+        &store.token_vault_program,
+    )?;
+
+    assert_derivation(
+        program_id,
+        safety_deposit_config_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_manager_info.key.as_ref(),
+            safety_deposit_info.key.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        auction_token_tracker_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_manager_info.key.as_ref(),
+            TOTALS.as_bytes(),
+        ],
+    )?;
+
+    let safety_deposit = SafetyDepositBox::from_account_info(safety_deposit_info)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    let vault = Vault::from_account_info(vault_info)?;
+    let old_config = SafetyDepositConfig::from_account_info(safety_deposit_config_info)?;
+
+    if safety_deposit.store != *safety_deposit_token_store_info.key {
+        return Err(MetaplexError::SafetyDepositBoxStoreMismatch.into());
+    }
+
+    if safety_deposit.token_mint != metadata.mint {
+        return Err(MetaplexError::SafetyDepositBoxMetadataMismatch.into());
+    }
+
+    if safety_deposit_config.order != old_config.order
+        || safety_deposit_config.order != safety_deposit.order as u64
+    {
+        return Err(MetaplexError::SafetyDepositConfigOrderMismatch.into());
+    }
+
+    // The type decided what validation did with the box, like taking over the metadata for a full
+    // rights transfer, so changing it means decommissioning.
+    if safety_deposit_config.winning_config_type != old_config.winning_config_type {
+        return Err(MetaplexError::SafetyDepositConfigTypeMismatch.into());
+    }
+
+    if safety_deposit_config.winning_config_type != WinningConfigType::Participation
+        && safety_deposit_config.participation_config.is_some()
+    {
+        return Err(MetaplexError::InvalidOperation.into());
+    }
+
+    // There is no realloc, the new config has to fit where the old one was.
+    if safety_deposit_config.created_size() > safety_deposit_config_info.data_len() {
+        return Err(MetaplexError::SafetyDepositConfigTooLarge.into());
+    }
+
+    // The box is validated again here, so it comes out of the count, and out of has_participation
+    // if it is the participation prize, until the new config passes.
+    auction_manager.state.safety_config_items_validated = auction_manager
+        .state
+        .safety_config_items_validated
+        .checked_sub(1)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    if old_config.winning_config_type == WinningConfigType::Participation {
+        auction_manager.state.has_participation = false;
+    }
+    auction_manager.state.status = AuctionManagerStatus::Initialized;
+
+    let total_amount_requested = safety_deposit_config
+        .amount_ranges
+        .iter()
+        .map(|t| t.0 * t.1)
+        .sum();
+
+    assert_new_amounts_supplied(
+        safety_deposit_config.winning_config_type,
+        total_amount_requested,
+        safety_deposit_token_store_info,
+        edition_info,
+        &metadata,
+        &store,
+    )?;
+
+    // Nothing is redeemed before the auction starts, but the state is the program's to keep.
+    safety_deposit_config.participation_state = old_config.participation_state;
+
+    if safety_deposit_config.winning_config_type == WinningConfigType::Participation {
+        if auction_manager.state.has_participation {
+            return Err(MetaplexError::AlreadyHasOneParticipationPrize.into());
+        }
+        auction_manager.state.has_participation = true;
+    }
+
+    auction_manager.state.safety_config_items_validated = auction_manager
+        .state
+        .safety_config_items_validated
+        .checked_add(1)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    if auction_manager.state.safety_config_items_validated == vault.token_type_count as u64 {
+        auction_manager.state.status = AuctionManagerStatus::Validated
+    }
+    auction_manager.save(auction_manager_info)?;
+
+    // Validation counted this box's ranges in the tracker, so swap them for the new ones.
+    if safety_deposit_config.winning_config_type != WinningConfigType::Participation {
+        let mut auction_token_tracker =
+            AuctionWinnerTokenTypeTracker::from_account_info(auction_token_tracker_info)?;
+        auction_token_tracker
+            .subtract_one_where_positive_ranges_occur(&mut old_config.amount_ranges.clone())?;
+        auction_token_tracker.add_one_where_positive_ranges_occur(
+            &mut safety_deposit_config.amount_ranges.clone(),
+        )?;
+        auction_token_tracker.save(auction_token_tracker_info);
+    }
+
+    // A shorter config would leave the tail of the old one behind.
+    for byte in safety_deposit_config_info.data.borrow_mut().iter_mut() {
+        *byte = 0;
+    }
+    safety_deposit_config.create(safety_deposit_config_info, auction_manager_info.key)?;
+
+    Ok(())
+}
@@ -17,6 +17,7 @@ use {
         rent::Rent,
         sysvar::Sysvar,
     },
+    std::cmp,
 };
 
 pub fn process_withdraw_master_edition<'a>(
@@ -43,7 +44,7 @@ pub fn process_withdraw_master_edition<'a>(
 
     let rent = &Rent::from_account_info(&rent_info)?;
 
-    let auction_manager = get_auction_manager(auction_manager_info)?;
+    let mut auction_manager = get_auction_manager(auction_manager_info)?;
     let auction = AuctionData::from_account_info(auction_info)?;
     let auction_data_extended = AuctionDataExtended::from_account_info(auction_extended_info)?;
 
@@ -95,7 +96,7 @@ pub fn process_withdraw_master_edition<'a>(
         return Err(MetaplexError::AuctionManagerStoreMismatch.into());
     }
 
-    if auction.state != AuctionState::Ended {
+    if auction.state != AuctionState::Ended && auction.state != AuctionState::Cancelled {
         return Err(MetaplexError::AuctionHasNotEnded.into());
     }
 
@@ -115,7 +116,9 @@ pub fn process_withdraw_master_edition<'a>(
         &safety_deposit_box.token_mint,
     )?;
 
-    if prize_tracking_ticket_info.data_is_empty() {
+    if auction.state == AuctionState::Cancelled {
+        // Nobody can redeem from a cancelled auction, so there is nothing to wait for.
+    } else if prize_tracking_ticket_info.data_is_empty() {
         // Nobody has redeemed yet, we need to figure out if SOMEONE could and if we should
         // stop a withdrawal.
 
@@ -124,9 +127,12 @@ pub fn process_withdraw_master_edition<'a>(
                 safety_deposit_box.order as u64,
                 safety_deposit_config_info,
             )?;
-        if auction_data_extended.total_uncancelled_bids
-            > max_bids_allowed_before_removal_is_stopped as u64
-        {
+        // Accepted offers win without ever counting as bids.
+        let bids = cmp::max(
+            auction_data_extended.total_uncancelled_bids,
+            auction.num_winners(),
+        );
+        if bids > max_bids_allowed_before_removal_is_stopped as u64 {
             return Err(MetaplexError::NotAllBidsClaimed.into());
         }
     } else {
@@ -179,5 +185,11 @@ pub fn process_withdraw_master_edition<'a>(
         auction_auth_seeds,
     )?;
 
+    // The prize is gone, so no offer can be accepted anymore.
+    if !auction_manager.offers_closed() {
+        auction_manager.close_offers();
+        auction_manager.save(auction_manager_info)?;
+    }
+
     Ok(())
 }
@@ -48,10 +48,15 @@ pub const MAX_AUCTION_MANAGER_V2_SIZE: usize = 1 + //key
 1 + // has participation
 1 + //status
 8 + // winning configs validated
-200; // padding
+1 + // offers closed
+199; // padding
 pub const MAX_STORE_SIZE: usize = 2 + 32 + 32 + 32 + 32 + 100;
 pub const MAX_WHITELISTED_CREATOR_SIZE: usize = 2 + 32 + 10;
-pub const MAX_PAYOUT_TICKET_SIZE: usize = 1 + 32 + 8;
+pub const MAX_PAYOUT_TICKET_SIZE: usize = 1 + 32 + 8 + 8;
+/// Payout tickets created before the store fee existed have no room for `fee_paid`.
+pub const LEGACY_PAYOUT_TICKET_SIZE: usize = 1 + 32 + 8;
+/// Highest fee a store can take from primary sales, 10%.
+pub const MAX_STORE_FEE_BASIS_POINTS: u16 = 1000;
 pub const MAX_BID_REDEMPTION_TICKET_SIZE: usize = 3;
 pub const MAX_AUTHORITY_LOOKUP_SIZE: usize = 33;
 pub const MAX_PRIZE_TRACKING_TICKET_SIZE: usize = 1 + 32 + 8 + 8 + 8 + 50;
@@ -128,6 +133,9 @@ pub trait AuctionManager {
     fn accept_payment(&self) -> Pubkey;
     fn status(&self) -> AuctionManagerStatus;
     fn set_status(&mut self, status: AuctionManagerStatus);
+    /// Whether offers on an auction that ended without a bid can no longer be accepted.
+    fn offers_closed(&self) -> bool;
+    fn close_offers(&mut self);
     fn configs_validated(&self) -> u64;
     fn set_configs_validated(&mut self, new_configs_validated: u64);
     fn save(&self, account: &AccountInfo) -> ProgramResult;
@@ -266,6 +274,7 @@ impl AuctionManager for AuctionManagerV2 {
     ) {
         let mut data = account.data.borrow_mut();
         data[161] = self.state.status as u8;
+        data[179] = self.state.offers_closed as u8;
     }
 
     fn common_winning_index_checks(
@@ -343,6 +352,14 @@ impl AuctionManager for AuctionManagerV2 {
         self.state.status = status
     }
 
+    fn offers_closed(&self) -> bool {
+        self.state.offers_closed
+    }
+
+    fn close_offers(&mut self) {
+        self.state.offers_closed = true
+    }
+
     fn configs_validated(&self) -> u64 {
         self.state.safety_config_items_validated
     }
@@ -543,6 +560,8 @@ pub struct AuctionManagerStateV2 {
     pub bids_pushed_to_accept_payment: u64,
 
     pub has_participation: bool,
+    /// Set once the auctioneer takes back a prize, after which no offer can be accepted
+    pub offers_closed: bool,
 }
 
 #[repr(C)]
@@ -655,18 +674,36 @@ pub struct PayoutTicket {
     pub key: Key,
     pub recipient: Pubkey,
     pub amount_paid: u64,
+    /// Store fee taken out of this payout so far.
+    pub fee_paid: u64,
 }
 
 impl PayoutTicket {
     pub fn from_account_info(a: &AccountInfo) -> Result<PayoutTicket, ProgramError> {
-        let pt: PayoutTicket = try_from_slice_checked(
-            &a.data.borrow_mut(),
-            Key::PayoutTicketV1,
-            MAX_PAYOUT_TICKET_SIZE,
-        )?;
+        let data = a.data.borrow();
+        if data.len() == LEGACY_PAYOUT_TICKET_SIZE {
+            let mut padded = [0u8; MAX_PAYOUT_TICKET_SIZE];
+            padded[..LEGACY_PAYOUT_TICKET_SIZE].copy_from_slice(&data);
+            return try_from_slice_checked(&padded, Key::PayoutTicketV1, MAX_PAYOUT_TICKET_SIZE);
+        }
+
+        let pt: PayoutTicket =
+            try_from_slice_checked(&data, Key::PayoutTicketV1, MAX_PAYOUT_TICKET_SIZE)?;
 
         Ok(pt)
     }
+
+    pub fn is_legacy(a: &AccountInfo) -> bool {
+        a.data_len() == LEGACY_PAYOUT_TICKET_SIZE
+    }
+
+    pub fn save(&self, a: &AccountInfo) -> ProgramResult {
+        let bytes = self.try_to_vec()?;
+        let mut data = a.data.borrow_mut();
+        let len = data.len();
+        data.copy_from_slice(&bytes[..len]);
+        Ok(())
+    }
 }
 
 #[repr(C)]
@@ -722,6 +759,14 @@ pub struct Store {
     pub token_vault_program: Pubkey,
     pub token_metadata_program: Pubkey,
     pub token_program: Pubkey,
+    /// Cut of every primary sale payout that goes to the store, capped at
+    /// MAX_STORE_FEE_BASIS_POINTS.
+    pub fee_basis_points: u16,
+    /// Wallet the store fee is paid to.
+    pub fee_destination: Pubkey,
+    /// Merkle root of creators admitted to the store on top of its WhitelistedCreators. Leaves are
+    /// the hash of the creator key, and a creator in the tree claims their own WhitelistedCreator.
+    pub creator_allowlist_root: Option<[u8; 32]>,
 }
 
 impl Store {
@@ -1332,6 +1377,50 @@ impl AuctionWinnerTokenTypeTracker {
         &mut self,
         amount_ranges: &mut Vec<AmountRange>,
     ) -> ProgramResult {
+        self.merge_positive_ranges(amount_ranges, true)
+    }
+
+    /// Undoes add_one_where_positive_ranges_occur for a safety deposit config that is being
+    /// rewritten, taking one unique token type back off every range where it gave out tokens.
+    /// Neighbouring ranges left with the same count are joined back up so the tracker doesn't
+    /// fragment with every update.
+    pub fn subtract_one_where_positive_ranges_occur(
+        &mut self,
+        amount_ranges: &mut Vec<AmountRange>,
+    ) -> ProgramResult {
+        self.merge_positive_ranges(amount_ranges, false)?;
+
+        let mut joined: Vec<AmountRange> = vec![];
+        for range in &self.amount_ranges {
+            match joined.last_mut() {
+                Some(last) if last.0 == range.0 => {
+                    last.1 = last
+                        .1
+                        .checked_add(range.1)
+                        .ok_or(MetaplexError::NumericalOverflowError)?;
+                }
+                _ => joined.push(*range),
+            }
+        }
+        self.amount_ranges = joined;
+
+        Ok(())
+    }
+
+    fn merge_positive_ranges(
+        &mut self,
+        amount_ranges: &mut Vec<AmountRange>,
+        adding: bool,
+    ) -> ProgramResult {
+        let shift = |amount: u64, by: u64| -> Result<u64, ProgramError> {
+            let shifted = if adding {
+                amount.checked_add(by)
+            } else {
+                amount.checked_sub(by)
+            };
+            shifted.ok_or_else(|| MetaplexError::NumericalOverflowError.into())
+        };
+
         let mut new_range: Vec<AmountRange> = vec![];
 
         if self.amount_ranges.len() == 0 {
@@ -1339,12 +1428,12 @@ impl AuctionWinnerTokenTypeTracker {
                 .iter()
                 .map(|x| {
                     if x.0 > 0 {
-                        return AmountRange(1, x.1);
+                        return Ok(AmountRange(shift(0, 1)?, x.1));
                     } else {
-                        return AmountRange(0, x.1);
+                        return Ok(AmountRange(0, x.1));
                     }
                 })
-                .collect();
+                .collect::<Result<Vec<AmountRange>, ProgramError>>()?;
             return Ok(());
         } else if amount_ranges.len() == 0 {
             return Ok(());
@@ -1368,7 +1457,7 @@ impl AuctionWinnerTokenTypeTracker {
             }
 
             if my_ctr == self.amount_ranges.len() {
-                new_range.push(AmountRange(to_add, amount_ranges[their_ctr].1));
+                new_range.push(AmountRange(shift(0, to_add)?, amount_ranges[their_ctr].1));
                 their_ctr += 1;
             } else if their_ctr == amount_ranges.len() {
                 new_range.push(self.amount_ranges[my_ctr]);
@@ -1380,10 +1469,7 @@ impl AuctionWinnerTokenTypeTracker {
                     .ok_or(MetaplexError::NumericalOverflowError)?;
 
                 new_range.push(AmountRange(
-                    self.amount_ranges[my_ctr]
-                        .0
-                        .checked_add(to_add)
-                        .ok_or(MetaplexError::NumericalOverflowError)?,
+                    shift(self.amount_ranges[my_ctr].0, to_add)?,
                     amount_ranges[their_ctr].1,
                 ));
 
@@ -1396,10 +1482,7 @@ impl AuctionWinnerTokenTypeTracker {
                     .ok_or(MetaplexError::NumericalOverflowError)?;
 
                 new_range.push(AmountRange(
-                    self.amount_ranges[my_ctr]
-                        .0
-                        .checked_add(to_add)
-                        .ok_or(MetaplexError::NumericalOverflowError)?,
+                    shift(self.amount_ranges[my_ctr].0, to_add)?,
                     self.amount_ranges[my_ctr].1,
                 ));
 
@@ -1407,10 +1490,7 @@ impl AuctionWinnerTokenTypeTracker {
                 // dont increment their_ctr since they still have length to give
             } else if amount_ranges[their_ctr].1 == self.amount_ranges[my_ctr].1 {
                 new_range.push(AmountRange(
-                    self.amount_ranges[my_ctr]
-                        .0
-                        .checked_add(to_add)
-                        .ok_or(MetaplexError::NumericalOverflowError)?,
+                    shift(self.amount_ranges[my_ctr].0, to_add)?,
                     self.amount_ranges[my_ctr].1,
                 ));
                 // Move them both in this degen case
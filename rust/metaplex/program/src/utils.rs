@@ -24,6 +24,7 @@ use {
         account_info::AccountInfo,
         borsh::try_from_slice_unchecked,
         entrypoint::ProgramResult,
+        hash,
         log::sol_log_compute_units,
         msg,
         program::{invoke, invoke_signed},
@@ -166,6 +167,20 @@ pub fn assert_at_least_one_creator_matches_or_store_public_and_all_verified(
     Err(MetaplexError::InvalidWhitelistedCreator.into())
 }
 
+/// Walks a Merkle proof up from `leaf`, hashing each pair smallest first, and checks it reaches
+/// `root`.
+pub fn verify_merkle_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let mut node = leaf;
+    for sibling in proof {
+        node = if node <= *sibling {
+            hash::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hash::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+    node == *root
+}
+
 pub fn assert_authority_correct(
     auction_manager_authority: &Pubkey,
     authority_info: &AccountInfo,
@@ -185,7 +200,8 @@ pub fn assert_auction_is_ended_or_valid_instant_sale(
     bidder_metadata_info: &AccountInfo,
     win_index: Option<usize>,
 ) -> ProgramResult {
-    if AuctionData::get_state(auction_info)? == AuctionState::Ended {
+    let state = AuctionData::get_state(auction_info)?;
+    if state == AuctionState::Ended || state == AuctionState::Cancelled {
         return Ok(());
     }
 
@@ -555,6 +571,9 @@ pub fn common_redeem_checks(
         if *bidder_info.key != auction_manager.authority() {
             return Err(MetaplexError::MustBeAuctioneer.into());
         }
+
+        // Once the auctioneer starts taking prizes back, offers can no longer be accepted.
+        auction_manager.close_offers();
     } else {
         let bidder_metadata_data = bidder_metadata_info.data.borrow();
         if bidder_metadata_data[80] == 0 {
@@ -670,6 +689,13 @@ pub fn common_redeem_checks(
         win_index,
     )?;
 
+    // Nobody wins a cancelled auction, only the auctioneer may take back the unused prizes.
+    if overwrite_win_index.is_none()
+        && AuctionData::get_state(auction_info)? == AuctionState::Cancelled
+    {
+        return Err(MetaplexError::AuctionCancelled.into());
+    }
+
     // No-op if already set.
     auction_manager.set_status(AuctionManagerStatus::Disbursing);
 
@@ -0,0 +1,1349 @@
+use metaplex::{
+    instruction::{self, EmptyPaymentAccountArgs, EndAuctionArgs, MetaplexInstruction},
+    state::{
+        AmountRange, SafetyDepositConfig, TupleNumericType, WinningConfigType, PREFIX, TOTALS,
+    },
+};
+use metaplex_auction::processor::{
+    AuctionDataExtended, CommitBidArgs, CreateAuctionArgsV3, PlaceBidArgs, PriceFloor,
+    RevealBidArgs, WinnerLimit,
+};
+use metaplex_token_metadata::state::Creator;
+use solana_program::{
+    borsh::try_from_slice_unchecked,
+    clock::{Clock, UnixTimestamp},
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_instruction, system_program, sysvar,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+    transport::TransportError,
+};
+use std::str::FromStr;
+
+use borsh::BorshSerialize;
+
+/// Everything an auction of a single item run through the auction manager needs.
+pub struct AuctionFixture {
+    pub store: Pubkey,
+    pub vault: Pubkey,
+    pub auction: Pubkey,
+    pub auction_extended: Pubkey,
+    pub auction_manager: Pubkey,
+    pub accept_payment: Pubkey,
+    pub metadata: Pubkey,
+    pub safety_deposit_box: Pubkey,
+    pub safety_deposit_token_store: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_mint_authority: Keypair,
+}
+
+pub async fn get_account(banks_client: &mut BanksClient, pubkey: &Pubkey) -> Account {
+    banks_client
+        .get_account(*pubkey)
+        .await
+        .expect("account not found")
+        .expect("account empty")
+}
+
+pub async fn get_token_balance(banks_client: &mut BanksClient, token: &Pubkey) -> u64 {
+    let token_account = get_account(banks_client, token).await;
+    let account_info: spl_token::state::Account =
+        spl_token::state::Account::unpack_from_slice(token_account.data.as_slice()).unwrap();
+    account_info.amount
+}
+
+/// Warps ahead until the clock reaches `unix_timestamp`. Returns a blockhash from after the warp.
+pub async fn warp_to_timestamp(
+    context: &mut ProgramTestContext,
+    unix_timestamp: UnixTimestamp,
+) -> Hash {
+    loop {
+        let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        if clock.unix_timestamp >= unix_timestamp {
+            return context.banks_client.get_recent_blockhash().await.unwrap();
+        }
+        context.warp_to_slot(clock.slot + 100).unwrap();
+    }
+}
+
+pub fn store_pubkey(admin: &Pubkey) -> Pubkey {
+    let program_id = metaplex::id();
+    let seeds = &[PREFIX.as_bytes(), program_id.as_ref(), admin.as_ref()];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn whitelisted_creator_pubkey(store: &Pubkey, creator: &Pubkey) -> Pubkey {
+    let program_id = metaplex::id();
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        store.as_ref(),
+        creator.as_ref(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn safety_deposit_config_pubkey(
+    auction_manager: &Pubkey,
+    safety_deposit_box: &Pubkey,
+) -> Pubkey {
+    let program_id = metaplex::id();
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        auction_manager.as_ref(),
+        safety_deposit_box.as_ref(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn auction_token_tracker_pubkey(auction_manager: &Pubkey) -> Pubkey {
+    let program_id = metaplex::id();
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        auction_manager.as_ref(),
+        TOTALS.as_bytes(),
+    ];
+    Pubkey::find_program_address(seeds, &program_id).0
+}
+
+pub fn associated_token_pubkey(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let token_program_id = spl_token::id();
+    let seeds = &[wallet.as_ref(), token_program_id.as_ref(), mint.as_ref()];
+    Pubkey::find_program_address(seeds, &associated_token_program_id()).0
+}
+
+fn associated_token_program_id() -> Pubkey {
+    Pubkey::from_str("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL").unwrap()
+}
+
+pub fn token_only_transfer_config(order: u64) -> SafetyDepositConfig {
+    SafetyDepositConfig {
+        key: metaplex::state::Key::SafetyDepositConfigV1,
+        auction_manager: Pubkey::default(),
+        order,
+        winning_config_type: WinningConfigType::TokenOnlyTransfer,
+        amount_type: TupleNumericType::U8,
+        length_type: TupleNumericType::U8,
+        amount_ranges: vec![AmountRange(1, 1)],
+        participation_config: None,
+        participation_state: None,
+    }
+}
+
+pub async fn fund(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    wallet: &Pubkey,
+    lamports: u64,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            wallet,
+            lamports,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_mint(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    mint: &Keypair,
+    manager: &Pubkey,
+) -> Result<(), TransportError> {
+    let rent = banks_client.get_rent().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                manager,
+                Some(manager),
+                0,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+        &[payer, mint],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_token_account(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    account: &Keypair,
+    mint: &Pubkey,
+    manager: &Pubkey,
+) -> Result<(), TransportError> {
+    let rent = banks_client.get_rent().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &account.pubkey(),
+                mint,
+                manager,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+        &[payer, account],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn create_associated_token_account(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Pubkey, TransportError> {
+    let associated_token = associated_token_pubkey(wallet, mint);
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: associated_token_program_id(),
+            accounts: vec![
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new(associated_token, false),
+                AccountMeta::new_readonly(*wallet, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![],
+        }],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(associated_token)
+}
+
+pub async fn mint_tokens(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    mint: &Pubkey,
+    account: &Pubkey,
+    mint_authority: &Keypair,
+    amount: u64,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[spl_token::instruction::mint_to(
+            &spl_token::id(),
+            mint,
+            account,
+            &mint_authority.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+        &[payer, mint_authority],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn set_store(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    public: bool,
+) -> Result<Pubkey, TransportError> {
+    let store = store_pubkey(&payer.pubkey());
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_set_store_instruction(
+            metaplex::id(),
+            store,
+            payer.pubkey(),
+            payer.pubkey(),
+            public,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(store)
+}
+
+pub async fn set_store_fee(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    store: &Pubkey,
+    fee_destination: &Pubkey,
+    fee_basis_points: u16,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_set_store_fee_instruction(
+            metaplex::id(),
+            *store,
+            payer.pubkey(),
+            *fee_destination,
+            fee_basis_points,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn set_whitelisted_creators(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    store: &Pubkey,
+    creators: &[Pubkey],
+    activated: bool,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_set_whitelisted_creators_instruction(
+            metaplex::id(),
+            *store,
+            payer.pubkey(),
+            payer.pubkey(),
+            creators,
+            activated,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn close_whitelisted_creator(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    store: &Pubkey,
+    creator: &Pubkey,
+    destination: &Pubkey,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_close_whitelisted_creator_instruction(
+            metaplex::id(),
+            whitelisted_creator_pubkey(store, creator),
+            payer.pubkey(),
+            *store,
+            *destination,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn set_store_creator_allowlist(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    store: &Pubkey,
+    root: Option<[u8; 32]>,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_set_store_creator_allowlist_instruction(
+            metaplex::id(),
+            *store,
+            payer.pubkey(),
+            root,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+/// Claims a whitelisted creator from the store's allowlist, paid for by `claimer`.
+pub async fn claim_whitelisted_creator(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    claimer: &Keypair,
+    store: &Pubkey,
+    creator: &Pubkey,
+    proof: Vec<[u8; 32]>,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_claim_whitelisted_creator_instruction(
+            metaplex::id(),
+            whitelisted_creator_pubkey(store, creator),
+            claimer.pubkey(),
+            *creator,
+            *store,
+            proof,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, claimer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+/// Mints `supply` tokens to the payer and gives them metadata with the payer as its only,
+/// verified, creator.
+pub async fn create_metadata(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    mint: &Keypair,
+    token: &Keypair,
+    supply: u64,
+    seller_fee_basis_points: u16,
+) -> Result<Pubkey, TransportError> {
+    let program_id = metaplex_token_metadata::id();
+    let mint_pubkey = mint.pubkey();
+    let seeds = &[
+        metaplex_token_metadata::state::PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_pubkey.as_ref(),
+    ];
+    let (metadata, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    create_mint(banks_client, payer, recent_blockhash, mint, &payer.pubkey()).await?;
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        token,
+        &mint.pubkey(),
+        &payer.pubkey(),
+    )
+    .await?;
+    mint_tokens(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &mint.pubkey(),
+        &token.pubkey(),
+        payer,
+        supply,
+    )
+    .await?;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            metaplex_token_metadata::instruction::create_metadata_accounts(
+                program_id,
+                metadata,
+                mint.pubkey(),
+                payer.pubkey(),
+                payer.pubkey(),
+                payer.pubkey(),
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                Some(vec![Creator {
+                    address: payer.pubkey(),
+                    verified: true,
+                    share: 100,
+                }]),
+                seller_fee_basis_points,
+                true,
+                true,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(metadata)
+}
+
+/// Puts `amount` of the token in a new vault owned by the payer and combines it. Returns the
+/// vault, its safety deposit box, and the box's token store.
+pub async fn create_combined_vault(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    token: &Pubkey,
+    token_mint: &Pubkey,
+    amount: u64,
+) -> Result<(Pubkey, Pubkey, Pubkey), TransportError> {
+    let program_id = metaplex_token_vault::id();
+    let rent = banks_client.get_rent().await.unwrap();
+
+    let external_price = Keypair::new();
+    let price_mint = Keypair::new();
+    create_mint(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &price_mint,
+        &payer.pubkey(),
+    )
+    .await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &external_price.pubkey(),
+                rent.minimum_balance(metaplex_token_vault::state::MAX_EXTERNAL_ACCOUNT_SIZE),
+                metaplex_token_vault::state::MAX_EXTERNAL_ACCOUNT_SIZE as u64,
+                &program_id,
+            ),
+            metaplex_token_vault::instruction::create_update_external_price_account_instruction(
+                program_id,
+                external_price.pubkey(),
+                0,
+                price_mint.pubkey(),
+                true,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[payer, &external_price],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+
+    let vault = Keypair::new();
+    let vault_pubkey = vault.pubkey();
+    let seeds = &[
+        metaplex_token_vault::state::PREFIX.as_bytes(),
+        program_id.as_ref(),
+        vault_pubkey.as_ref(),
+    ];
+    let (vault_mint_authority, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let fraction_mint = Keypair::new();
+    let redeem_treasury = Keypair::new();
+    let fraction_treasury = Keypair::new();
+    create_mint(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &fraction_mint,
+        &vault_mint_authority,
+    )
+    .await?;
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &redeem_treasury,
+        &price_mint.pubkey(),
+        &vault_mint_authority,
+    )
+    .await?;
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &fraction_treasury,
+        &fraction_mint.pubkey(),
+        &vault_mint_authority,
+    )
+    .await?;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &vault.pubkey(),
+                rent.minimum_balance(metaplex_token_vault::state::MAX_VAULT_SIZE),
+                metaplex_token_vault::state::MAX_VAULT_SIZE as u64,
+                &program_id,
+            ),
+            metaplex_token_vault::instruction::create_init_vault_instruction(
+                program_id,
+                fraction_mint.pubkey(),
+                redeem_treasury.pubkey(),
+                fraction_treasury.pubkey(),
+                vault.pubkey(),
+                payer.pubkey(),
+                external_price.pubkey(),
+                false,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[payer, &vault],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+
+    let seeds = &[
+        metaplex_token_vault::state::PREFIX.as_bytes(),
+        vault_pubkey.as_ref(),
+        token_mint.as_ref(),
+    ];
+    let (safety_deposit_box, _) = Pubkey::find_program_address(seeds, &program_id);
+    let safety_deposit_token_store = Keypair::new();
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &safety_deposit_token_store,
+        token_mint,
+        &vault_mint_authority,
+    )
+    .await?;
+
+    let outstanding_shares = Keypair::new();
+    let paying_account = Keypair::new();
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &outstanding_shares,
+        &fraction_mint.pubkey(),
+        &payer.pubkey(),
+    )
+    .await?;
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &paying_account,
+        &price_mint.pubkey(),
+        &payer.pubkey(),
+    )
+    .await?;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            metaplex_token_vault::instruction::create_add_token_to_inactive_vault_instruction(
+                program_id,
+                safety_deposit_box,
+                *token,
+                safety_deposit_token_store.pubkey(),
+                vault.pubkey(),
+                payer.pubkey(),
+                payer.pubkey(),
+                payer.pubkey(),
+                amount,
+            ),
+            metaplex_token_vault::instruction::create_activate_vault_instruction(
+                program_id,
+                vault.pubkey(),
+                fraction_mint.pubkey(),
+                fraction_treasury.pubkey(),
+                vault_mint_authority,
+                payer.pubkey(),
+                1,
+            ),
+            metaplex_token_vault::instruction::create_combine_vault_instruction(
+                program_id,
+                vault.pubkey(),
+                outstanding_shares.pubkey(),
+                paying_account.pubkey(),
+                fraction_mint.pubkey(),
+                fraction_treasury.pubkey(),
+                redeem_treasury.pubkey(),
+                payer.pubkey(),
+                payer.pubkey(),
+                payer.pubkey(),
+                vault_mint_authority,
+                external_price.pubkey(),
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+
+    Ok((
+        vault.pubkey(),
+        safety_deposit_box,
+        safety_deposit_token_store.pubkey(),
+    ))
+}
+
+/// Builds a single winner auction on a fresh vault holding one token of a fresh mint, with
+/// `seller_fee_basis_points` royalties on the token's metadata, and validates it under an auction
+/// manager on `store`.
+pub async fn setup_managed_auction(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    store: &Pubkey,
+    seller_fee_basis_points: u16,
+) -> Result<AuctionFixture, TransportError> {
+    setup_configured_auction(
+        banks_client,
+        payer,
+        recent_blockhash,
+        store,
+        seller_fee_basis_points,
+        1,
+        |_| {},
+    )
+    .await
+}
+
+/// Like setup_managed_auction, but with a token of the item for each of `winners` winners, and
+/// the auction created from the args `configure` leaves behind.
+#[allow(clippy::too_many_arguments)]
+pub async fn setup_configured_auction(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    store: &Pubkey,
+    seller_fee_basis_points: u16,
+    winners: usize,
+    configure: impl FnOnce(&mut CreateAuctionArgsV3),
+) -> Result<AuctionFixture, TransportError> {
+    let auction_program_id = metaplex_auction::id();
+
+    let item_mint = Keypair::new();
+    let item_token = Keypair::new();
+    let metadata = create_metadata(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &item_mint,
+        &item_token,
+        winners as u64,
+        seller_fee_basis_points,
+    )
+    .await?;
+
+    let token_mint = Keypair::new();
+    let token_mint_authority = Keypair::new();
+    create_mint(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &token_mint,
+        &token_mint_authority.pubkey(),
+    )
+    .await?;
+
+    // The auction manager is derived from the auction, which is derived from the vault, so the
+    // vault stays with the payer until the auction manager exists to take it over.
+    let (vault, safety_deposit_box, safety_deposit_token_store) = create_combined_vault(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &item_token.pubkey(),
+        &item_mint.pubkey(),
+        winners as u64,
+    )
+    .await?;
+
+    let seeds = &[
+        metaplex_auction::PREFIX.as_bytes(),
+        auction_program_id.as_ref(),
+        vault.as_ref(),
+    ];
+    let (auction, _) = Pubkey::find_program_address(seeds, &auction_program_id);
+    let seeds = &[
+        metaplex_auction::PREFIX.as_bytes(),
+        auction_program_id.as_ref(),
+        vault.as_ref(),
+        metaplex_auction::EXTENDED.as_bytes(),
+    ];
+    let (auction_extended, _) = Pubkey::find_program_address(seeds, &auction_program_id);
+    let seeds = &[PREFIX.as_bytes(), auction.as_ref()];
+    let (auction_manager, _) = Pubkey::find_program_address(seeds, &metaplex::id());
+
+    let accept_payment = Keypair::new();
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &accept_payment,
+        &token_mint.pubkey(),
+        &auction_manager,
+    )
+    .await?;
+
+    let mut args = CreateAuctionArgsV3 {
+        winners: WinnerLimit::Capped(winners),
+        end_auction_at: None,
+        end_auction_gap: None,
+        token_mint: token_mint.pubkey(),
+        authority: auction_manager,
+        resource: vault,
+        price_floor: PriceFloor::None([0; 32]),
+        tick_size: None,
+        gap_tick_size_percentage: None,
+        instant_sale_price: None,
+        name: None,
+        sealed_bid: None,
+        dutch_auction: None,
+        reserve_price: None,
+        anti_snipe: None,
+        uniform_price: false,
+        bidder_gate: None,
+        bid_deposit: None,
+        start_at: None,
+        native_sol: false,
+    };
+    configure(&mut args);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            metaplex_auction::instruction::create_auction_instruction_v3(
+                auction_program_id,
+                payer.pubkey(),
+                args,
+            ),
+            instruction::create_init_auction_manager_v2_instruction(
+                metaplex::id(),
+                auction_manager,
+                vault,
+                auction,
+                payer.pubkey(),
+                payer.pubkey(),
+                accept_payment.pubkey(),
+                *store,
+                TupleNumericType::U8,
+                TupleNumericType::U8,
+                1,
+            ),
+            metaplex_token_vault::instruction::create_set_authority_instruction(
+                metaplex_token_vault::id(),
+                vault,
+                payer.pubkey(),
+                auction_manager,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+
+    let mut config = token_only_transfer_config(0);
+    config.amount_ranges = vec![AmountRange(1, winners as u64)];
+    validate_safety_deposit_box(
+        banks_client,
+        payer,
+        recent_blockhash,
+        store,
+        auction,
+        auction_manager,
+        metadata,
+        safety_deposit_box,
+        safety_deposit_token_store,
+        &item_mint.pubkey(),
+        vault,
+        config,
+    )
+    .await?;
+
+    Ok(AuctionFixture {
+        store: *store,
+        vault,
+        auction,
+        auction_extended,
+        auction_manager,
+        accept_payment: accept_payment.pubkey(),
+        metadata,
+        safety_deposit_box,
+        safety_deposit_token_store,
+        token_mint: token_mint.pubkey(),
+        token_mint_authority,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn validate_safety_deposit_box(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    store: &Pubkey,
+    auction: Pubkey,
+    auction_manager: Pubkey,
+    metadata: Pubkey,
+    safety_deposit_box: Pubkey,
+    safety_deposit_token_store: Pubkey,
+    item_mint: &Pubkey,
+    vault: Pubkey,
+    config: SafetyDepositConfig,
+) -> Result<(), TransportError> {
+    let seeds = &[PREFIX.as_bytes(), auction.as_ref(), metadata.as_ref()];
+    let (original_authority_lookup, _) = Pubkey::find_program_address(seeds, &metaplex::id());
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            instruction::create_validate_safety_deposit_box_v2_instruction(
+                metaplex::id(),
+                auction_manager,
+                metadata,
+                original_authority_lookup,
+                system_program::id(),
+                *store,
+                safety_deposit_box,
+                safety_deposit_token_store,
+                *item_mint,
+                system_program::id(),
+                vault,
+                payer.pubkey(),
+                payer.pubkey(),
+                payer.pubkey(),
+                config,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn update_safety_deposit_config(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    fixture: &AuctionFixture,
+    config: SafetyDepositConfig,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            instruction::create_update_safety_deposit_config_instruction(
+                metaplex::id(),
+                fixture.auction_manager,
+                fixture.safety_deposit_box,
+                fixture.safety_deposit_token_store,
+                fixture.vault,
+                fixture.store,
+                fixture.metadata,
+                system_program::id(),
+                payer.pubkey(),
+                config,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn start_auction(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    fixture: &AuctionFixture,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_start_auction_instruction(
+            metaplex::id(),
+            fixture.auction_manager,
+            fixture.auction,
+            payer.pubkey(),
+            fixture.store,
+            Some(fixture.auction_extended),
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn end_auction(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    fixture: &AuctionFixture,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_end_auction_instruction(
+            metaplex::id(),
+            fixture.auction_manager,
+            fixture.auction,
+            fixture.auction_extended,
+            payer.pubkey(),
+            fixture.store,
+            EndAuctionArgs { reveal: None },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+/// Mints `amount`, and the auction's deposit if it takes one, to a new bidder and places it as a
+/// bid. Returns the bidder and the token account the bid is escrowed in.
+pub async fn place_bid(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    fixture: &AuctionFixture,
+    amount: u64,
+) -> Result<(Keypair, Pubkey), TransportError> {
+    let auction_extended: AuctionDataExtended = try_from_slice_unchecked(
+        &get_account(banks_client, &fixture.auction_extended)
+            .await
+            .data,
+    )
+    .unwrap();
+    let deposit = auction_extended
+        .bid_deposit
+        .map_or(0, |deposit| deposit.amount);
+
+    let bidder = Keypair::new();
+    let bidder_pot_token = Keypair::new();
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &bidder,
+        &fixture.token_mint,
+        &payer.pubkey(),
+    )
+    .await?;
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &bidder_pot_token,
+        &fixture.token_mint,
+        &fixture.auction,
+    )
+    .await?;
+    mint_tokens(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &fixture.token_mint,
+        &bidder.pubkey(),
+        &fixture.token_mint_authority,
+        amount + deposit,
+    )
+    .await?;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[metaplex_auction::instruction::place_bid_instruction(
+            metaplex_auction::id(),
+            bidder.pubkey(),
+            bidder.pubkey(),
+            bidder_pot_token.pubkey(),
+            fixture.token_mint,
+            payer.pubkey(),
+            payer.pubkey(),
+            PlaceBidArgs {
+                amount,
+                resource: fixture.vault,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok((bidder, bidder_pot_token.pubkey()))
+}
+
+/// Commits a sealed bid of `amount` from a new bidder, funded from a token account of the
+/// bidder's own that the part of the bid over its price is refunded to. Returns the bidder, that
+/// token account, and the bid's pot token account.
+pub async fn commit_bid(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    fixture: &AuctionFixture,
+    amount: u64,
+    salt: u64,
+) -> Result<(Keypair, Pubkey, Pubkey), TransportError> {
+    let bidder = Keypair::new();
+    let bidder_token = Keypair::new();
+    let bidder_pot_token = Keypair::new();
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &bidder_token,
+        &fixture.token_mint,
+        &bidder.pubkey(),
+    )
+    .await?;
+    create_token_account(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &bidder_pot_token,
+        &fixture.token_mint,
+        &fixture.auction,
+    )
+    .await?;
+    mint_tokens(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &fixture.token_mint,
+        &bidder_token.pubkey(),
+        &fixture.token_mint_authority,
+        amount,
+    )
+    .await?;
+
+    let commitment = solana_program::hash::hashv(&[
+        bidder.pubkey().as_ref(),
+        &amount.to_be_bytes(),
+        &salt.to_be_bytes(),
+    ]);
+    let transaction = Transaction::new_signed_with_payer(
+        &[metaplex_auction::instruction::commit_bid_instruction(
+            metaplex_auction::id(),
+            bidder.pubkey(),
+            bidder_token.pubkey(),
+            bidder_pot_token.pubkey(),
+            fixture.token_mint,
+            bidder.pubkey(),
+            payer.pubkey(),
+            None,
+            CommitBidArgs {
+                deposit: amount,
+                commitment,
+                resource: fixture.vault,
+                allowlist_proof: vec![],
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[&bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok((bidder, bidder_token.pubkey(), bidder_pot_token.pubkey()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn reveal_bid(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    fixture: &AuctionFixture,
+    bidder: &Keypair,
+    bidder_token: &Pubkey,
+    bidder_pot_token: &Pubkey,
+    amount: u64,
+    salt: u64,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[metaplex_auction::instruction::reveal_bid_instruction(
+            metaplex_auction::id(),
+            bidder.pubkey(),
+            *bidder_token,
+            *bidder_pot_token,
+            fixture.token_mint,
+            RevealBidArgs {
+                amount,
+                salt,
+                resource: fixture.vault,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[bidder, payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+/// Claims the bid for the auction manager, refunding what the winner bid over their price to
+/// `bidder_token` when given.
+pub async fn claim_bid(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    fixture: &AuctionFixture,
+    bidder: &Pubkey,
+    bidder_pot_token: &Pubkey,
+    bidder_token: Option<&Pubkey>,
+) -> Result<(), TransportError> {
+    claim_bid_into(
+        banks_client,
+        payer,
+        recent_blockhash,
+        fixture,
+        &fixture.accept_payment,
+        bidder,
+        bidder_pot_token,
+        bidder_token,
+    )
+    .await
+}
+
+/// Claims the bid into `destination` rather than the accept payment account, which is where a
+/// forfeited deposit goes.
+#[allow(clippy::too_many_arguments)]
+pub async fn claim_bid_into(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    fixture: &AuctionFixture,
+    destination: &Pubkey,
+    bidder: &Pubkey,
+    bidder_pot_token: &Pubkey,
+    bidder_token: Option<&Pubkey>,
+) -> Result<(), TransportError> {
+    let auction_program_id = metaplex_auction::id();
+    let seeds = &[
+        metaplex_auction::PREFIX.as_bytes(),
+        auction_program_id.as_ref(),
+        fixture.auction.as_ref(),
+        bidder.as_ref(),
+    ];
+    let (bidder_pot, _) = Pubkey::find_program_address(seeds, &auction_program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(*destination, false),
+        AccountMeta::new(*bidder_pot_token, false),
+        AccountMeta::new(bidder_pot, false),
+        AccountMeta::new(fixture.auction_manager, false),
+        AccountMeta::new_readonly(fixture.auction, false),
+        AccountMeta::new_readonly(*bidder, false),
+        AccountMeta::new_readonly(fixture.token_mint, false),
+        AccountMeta::new_readonly(fixture.vault, false),
+        AccountMeta::new_readonly(fixture.store, false),
+        AccountMeta::new_readonly(auction_program_id, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(fixture.auction_extended, false),
+    ];
+    if let Some(bidder_token) = bidder_token {
+        accounts.push(AccountMeta::new(*bidder_token, false));
+    }
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: metaplex::id(),
+            accounts,
+            data: MetaplexInstruction::ClaimBid.try_to_vec().unwrap(),
+        }],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+/// Pays the creator at `creator_index` (or the auctioneer for None) their cut of the bid at
+/// `winning_config_index`.
+/// Returns the payout ticket.
+#[allow(clippy::too_many_arguments)]
+pub async fn empty_payment_account(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    fixture: &AuctionFixture,
+    winning_config_index: u8,
+    destination: &Pubkey,
+    recipient: &Pubkey,
+    creator_index: Option<u8>,
+    fee_destination: &Pubkey,
+) -> Result<Pubkey, TransportError> {
+    let winning_config_index_key = winning_config_index.to_string();
+    let creator_index_key = match creator_index {
+        Some(index) => index.to_string(),
+        None => "auctioneer".to_owned(),
+    };
+    let seeds = &[
+        PREFIX.as_bytes(),
+        fixture.auction_manager.as_ref(),
+        winning_config_index_key.as_bytes(),
+        "0".as_bytes(),
+        creator_index_key.as_bytes(),
+        fixture.safety_deposit_box.as_ref(),
+        recipient.as_ref(),
+    ];
+    let (payout_ticket, _) = Pubkey::find_program_address(seeds, &metaplex::id());
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: metaplex::id(),
+            accounts: vec![
+                AccountMeta::new(fixture.accept_payment, false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new(fixture.auction_manager, false),
+                AccountMeta::new(payout_ticket, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(fixture.metadata, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(fixture.safety_deposit_box, false),
+                AccountMeta::new_readonly(fixture.store, false),
+                AccountMeta::new_readonly(fixture.vault, false),
+                AccountMeta::new_readonly(fixture.auction, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+                AccountMeta::new_readonly(
+                    auction_token_tracker_pubkey(&fixture.auction_manager),
+                    false,
+                ),
+                AccountMeta::new_readonly(
+                    safety_deposit_config_pubkey(
+                        &fixture.auction_manager,
+                        &fixture.safety_deposit_box,
+                    ),
+                    false,
+                ),
+                AccountMeta::new_readonly(fixture.auction_extended, false),
+                AccountMeta::new(*fee_destination, false),
+            ],
+            data: MetaplexInstruction::EmptyPaymentAccount(EmptyPaymentAccountArgs {
+                winning_config_index: Some(winning_config_index),
+                winning_config_item_index: Some(0),
+                creator_index,
+            })
+            .try_to_vec()
+            .unwrap(),
+        }],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(payout_ticket)
+}
@@ -0,0 +1,961 @@
+#![allow(warnings)]
+
+use metaplex::{
+    error::MetaplexError,
+    state::{
+        AmountRange, AuctionManagerStatus, AuctionManagerV2, PayoutTicket, WhitelistedCreator,
+    },
+};
+use metaplex_auction::processor::{AuctionData, BidDeposit, SealedBidSettings};
+use solana_program::{
+    account_info::AccountInfo, borsh::try_from_slice_unchecked, entrypoint::ProgramResult, hash,
+    instruction::InstructionError, pubkey::Pubkey,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+mod helpers;
+
+// The metaplex and token metadata processors tie the account slice to the lifetime of the
+// accounts in it, which the test runtime does not hand out, so they're wrapped to match.
+fn process_metaplex_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let accounts: &[AccountInfo] = unsafe { std::mem::transmute(accounts) };
+    metaplex::processor::process_instruction(program_id, accounts, input)
+}
+
+fn process_token_metadata_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let accounts: &[AccountInfo] = unsafe { std::mem::transmute(accounts) };
+    metaplex_token_metadata::processor::process_instruction(program_id, accounts, input)
+}
+
+fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "metaplex",
+        metaplex::id(),
+        processor!(process_metaplex_instruction),
+    );
+    program_test.add_program(
+        "metaplex_auction",
+        metaplex_auction::id(),
+        processor!(metaplex_auction::processor::process_instruction),
+    );
+    program_test.add_program(
+        "metaplex_token_vault",
+        metaplex_token_vault::id(),
+        processor!(metaplex_token_vault::processor::process_instruction),
+    );
+    program_test.add_program(
+        "metaplex_token_metadata",
+        metaplex_token_metadata::id(),
+        processor!(process_token_metadata_instruction),
+    );
+    program_test
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_store_fee_split() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let store = helpers::set_store(&mut banks_client, &payer, &recent_blockhash, true)
+        .await
+        .unwrap();
+    let fixture =
+        helpers::setup_managed_auction(&mut banks_client, &payer, &recent_blockhash, &store, 0)
+            .await
+            .unwrap();
+
+    // The store takes 5% of the primary sale, paid to a token account of its fee wallet.
+    let fee_wallet = Keypair::new();
+    let fee_account = Keypair::new();
+    helpers::create_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &fee_account,
+        &fixture.token_mint,
+        &fee_wallet.pubkey(),
+    )
+    .await
+    .unwrap();
+    helpers::set_store_fee(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        &fee_wallet.pubkey(),
+        500,
+    )
+    .await
+    .unwrap();
+
+    let creator_account = helpers::create_associated_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &payer.pubkey(),
+        &fixture.token_mint,
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(&mut banks_client, &payer, &recent_blockhash, &fixture)
+        .await
+        .unwrap();
+    let (bidder, bidder_pot_token) =
+        helpers::place_bid(&mut banks_client, &payer, &recent_blockhash, &fixture, 1000)
+            .await
+            .unwrap();
+    helpers::end_auction(&mut banks_client, &payer, &recent_blockhash, &fixture)
+        .await
+        .unwrap();
+    helpers::claim_bid(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &fixture,
+        &bidder.pubkey(),
+        &bidder_pot_token,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &fixture.accept_payment).await,
+        1000
+    );
+
+    // The sole creator gets the whole primary sale less the store fee.
+    let payout_ticket = helpers::empty_payment_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &fixture,
+        0,
+        &creator_account,
+        &payer.pubkey(),
+        Some(0),
+        &fee_account.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &creator_account).await,
+        950
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &fee_account.pubkey()).await,
+        50
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &fixture.accept_payment).await,
+        0
+    );
+
+    let payout_ticket: PayoutTicket = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &payout_ticket)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(payout_ticket.recipient, payer.pubkey());
+    assert_eq!(payout_ticket.amount_paid, 950);
+    assert_eq!(payout_ticket.fee_paid, 50);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_store_fee_capped() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let store = helpers::set_store(&mut banks_client, &payer, &recent_blockhash, true)
+        .await
+        .unwrap();
+    let fee_wallet = Keypair::new();
+
+    let err = helpers::set_store_fee(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        &fee_wallet.pubkey(),
+        1001,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MetaplexError::StoreFeeTooHigh as u32)
+        )
+    );
+
+    // The cap itself is allowed.
+    helpers::set_store_fee(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        &fee_wallet.pubkey(),
+        1000,
+    )
+    .await
+    .unwrap();
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_update_safety_deposit_config() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let store = helpers::set_store(&mut banks_client, &payer, &recent_blockhash, true)
+        .await
+        .unwrap();
+    let fixture =
+        helpers::setup_managed_auction(&mut banks_client, &payer, &recent_blockhash, &store, 0)
+            .await
+            .unwrap();
+
+    // The vault's only box is validated, so the auction manager is ready to start.
+    let auction_manager: AuctionManagerV2 = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &fixture.auction_manager)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(
+        auction_manager.state.status,
+        AuctionManagerStatus::Validated
+    );
+    assert_eq!(auction_manager.state.safety_config_items_validated, 1);
+
+    // A config the box can't supply fails validation, and leaves everything as it was.
+    let mut config = helpers::token_only_transfer_config(0);
+    config.amount_ranges = vec![AmountRange(2, 1)];
+    let err = helpers::update_safety_deposit_config(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &fixture,
+        config,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MetaplexError::NotEnoughTokensToSupplyWinners as u32)
+        )
+    );
+
+    // A config that passes counts the box again, so the auction manager is still Validated.
+    let config = helpers::token_only_transfer_config(0);
+    helpers::update_safety_deposit_config(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &fixture,
+        config,
+    )
+    .await
+    .unwrap();
+
+    let auction_manager: AuctionManagerV2 = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &fixture.auction_manager)
+            .await
+            .data,
+    )
+    .unwrap();
+    assert_eq!(
+        auction_manager.state.status,
+        AuctionManagerStatus::Validated
+    );
+    assert_eq!(auction_manager.state.safety_config_items_validated, 1);
+    assert!(!auction_manager.state.has_participation);
+
+    helpers::start_auction(&mut banks_client, &payer, &recent_blockhash, &fixture)
+        .await
+        .unwrap();
+
+    // Once the auction is running the configs are final.
+    let mut config = helpers::token_only_transfer_config(0);
+    config.amount_ranges = vec![AmountRange(1, 1), AmountRange(0, 1)];
+    let err = helpers::update_safety_deposit_config(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &fixture,
+        config,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MetaplexError::InvalidStatus as u32)
+        )
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_set_and_close_whitelisted_creators() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let store = helpers::set_store(&mut banks_client, &payer, &recent_blockhash, false)
+        .await
+        .unwrap();
+    let creators: Vec<Pubkey> = (0..3).map(|_| Keypair::new().pubkey()).collect();
+
+    helpers::set_whitelisted_creators(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        &creators,
+        true,
+    )
+    .await
+    .unwrap();
+    for creator in &creators {
+        let whitelisted_creator = get_whitelisted_creator(&mut banks_client, &store, creator).await;
+        assert_eq!(whitelisted_creator.address, *creator);
+        assert!(whitelisted_creator.activated);
+    }
+
+    // Existing entries are updated in place.
+    helpers::set_whitelisted_creators(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        &creators[1..],
+        false,
+    )
+    .await
+    .unwrap();
+    assert!(
+        get_whitelisted_creator(&mut banks_client, &store, &creators[0])
+            .await
+            .activated
+    );
+    for creator in &creators[1..] {
+        assert!(
+            !get_whitelisted_creator(&mut banks_client, &store, creator)
+                .await
+                .activated
+        );
+    }
+
+    // Without an allowlist any entry can be closed, and its rent goes to the destination.
+    let destination = Keypair::new().pubkey();
+    let whitelisted_creator = helpers::whitelisted_creator_pubkey(&store, &creators[1]);
+    let rent = helpers::get_account(&mut banks_client, &whitelisted_creator)
+        .await
+        .lamports;
+    helpers::close_whitelisted_creator(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        &creators[1],
+        &destination,
+    )
+    .await
+    .unwrap();
+    assert!(banks_client
+        .get_account(whitelisted_creator)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        helpers::get_account(&mut banks_client, &destination)
+            .await
+            .lamports,
+        rent
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_claim_whitelisted_creator() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let store = helpers::set_store(&mut banks_client, &payer, &recent_blockhash, false)
+        .await
+        .unwrap();
+    let creator = Keypair::new();
+    let other_creator = Keypair::new();
+    let outsider = Keypair::new();
+    for wallet in &[&creator, &other_creator, &outsider] {
+        helpers::fund(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &wallet.pubkey(),
+            1_000_000_000,
+        )
+        .await
+        .unwrap();
+    }
+
+    // A two leaf tree, so each creator's proof is the other's leaf.
+    let leaf = hash::hashv(&[creator.pubkey().as_ref()]).to_bytes();
+    let other_leaf = hash::hashv(&[other_creator.pubkey().as_ref()]).to_bytes();
+    let root = if leaf <= other_leaf {
+        hash::hashv(&[&leaf, &other_leaf]).to_bytes()
+    } else {
+        hash::hashv(&[&other_leaf, &leaf]).to_bytes()
+    };
+
+    let err = helpers::claim_whitelisted_creator(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &creator,
+        &store,
+        &creator.pubkey(),
+        vec![other_leaf],
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MetaplexError::StoreHasNoCreatorAllowlist as u32)
+        )
+    );
+
+    helpers::set_store_creator_allowlist(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        Some(root),
+    )
+    .await
+    .unwrap();
+
+    let err = helpers::claim_whitelisted_creator(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &outsider,
+        &store,
+        &outsider.pubkey(),
+        vec![other_leaf],
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MetaplexError::CreatorNotInAllowlist as u32)
+        )
+    );
+
+    helpers::claim_whitelisted_creator(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &other_creator,
+        &store,
+        &creator.pubkey(),
+        vec![other_leaf],
+    )
+    .await
+    .unwrap();
+    let whitelisted_creator =
+        get_whitelisted_creator(&mut banks_client, &store, &creator.pubkey()).await;
+    assert_eq!(whitelisted_creator.address, creator.pubkey());
+    assert!(whitelisted_creator.activated);
+
+    // Once the admin deactivates the creator, the entry has to stay while the allowlist is set,
+    // or the creator could claim it again with the same proof.
+    helpers::set_whitelisted_creators(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        &[creator.pubkey()],
+        false,
+    )
+    .await
+    .unwrap();
+    let err = helpers::close_whitelisted_creator(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        &creator.pubkey(),
+        &payer.pubkey(),
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MetaplexError::CannotCloseDeactivatedCreator as u32)
+        )
+    );
+
+    let err = helpers::claim_whitelisted_creator(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &outsider,
+        &store,
+        &creator.pubkey(),
+        vec![other_leaf],
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MetaplexError::AlreadyInitialized as u32)
+        )
+    );
+    assert!(
+        !get_whitelisted_creator(&mut banks_client, &store, &creator.pubkey())
+            .await
+            .activated
+    );
+
+    // With the allowlist off it can be closed.
+    helpers::set_store_creator_allowlist(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        None,
+    )
+    .await
+    .unwrap();
+    helpers::close_whitelisted_creator(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        &creator.pubkey(),
+        &creator.pubkey(),
+    )
+    .await
+    .unwrap();
+    assert!(banks_client
+        .get_account(helpers::whitelisted_creator_pubkey(
+            &store,
+            &creator.pubkey()
+        ))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+async fn get_whitelisted_creator(
+    banks_client: &mut BanksClient,
+    store: &Pubkey,
+    creator: &Pubkey,
+) -> WhitelistedCreator {
+    try_from_slice_unchecked(
+        &helpers::get_account(
+            banks_client,
+            &helpers::whitelisted_creator_pubkey(store, creator),
+        )
+        .await
+        .data,
+    )
+    .unwrap()
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_vickrey_payouts() {
+    let mut context = program_test().start_with_context().await;
+    let mut banks_client = context.banks_client.clone();
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    let recent_blockhash = context.last_blockhash;
+
+    let store = helpers::set_store(&mut banks_client, &payer, &recent_blockhash, true)
+        .await
+        .unwrap();
+    // Two winners take a token each, and bids are revealed in the last ten minutes of the hour.
+    let fixture = helpers::setup_configured_auction(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        0,
+        2,
+        |args| {
+            args.end_auction_at = Some(3600);
+            args.sealed_bid = Some(SealedBidSettings {
+                reveal_period: 600,
+                vickrey: true,
+            });
+        },
+    )
+    .await
+    .unwrap();
+    let creator_account = helpers::create_associated_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &payer.pubkey(),
+        &fixture.token_mint,
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(&mut banks_client, &payer, &recent_blockhash, &fixture)
+        .await
+        .unwrap();
+    let mut bids = vec![];
+    for amount in &[1000, 800, 500] {
+        bids.push(
+            helpers::commit_bid(
+                &mut banks_client,
+                &payer,
+                &recent_blockhash,
+                &fixture,
+                *amount,
+                42,
+            )
+            .await
+            .unwrap(),
+        );
+    }
+
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &fixture.auction)
+            .await
+            .data,
+    )
+    .unwrap();
+    let ended_at = auction.ended_at.unwrap();
+    let recent_blockhash = helpers::warp_to_timestamp(&mut context, ended_at - 599).await;
+    for ((bidder, bidder_token, bidder_pot_token), amount) in bids.iter().zip(&[1000, 800, 500]) {
+        helpers::reveal_bid(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &fixture,
+            bidder,
+            bidder_token,
+            bidder_pot_token,
+            *amount,
+            42,
+        )
+        .await
+        .unwrap();
+    }
+
+    // Both winners pay the highest losing bid and get the rest of their bid back.
+    // A timed auction is moved to Ended by the first bid after its end.
+    let recent_blockhash = helpers::warp_to_timestamp(&mut context, ended_at + 1).await;
+    helpers::place_bid(&mut banks_client, &payer, &recent_blockhash, &fixture, 1)
+        .await
+        .unwrap();
+    for (bidder, bidder_token, bidder_pot_token) in &bids[..2] {
+        helpers::claim_bid(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &fixture,
+            &bidder.pubkey(),
+            bidder_pot_token,
+            Some(bidder_token),
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &fixture.accept_payment).await,
+        1000
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &bids[0].1).await,
+        500
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &bids[1].1).await,
+        300
+    );
+
+    // Each winning config pays out only what its winner was charged.
+    for winning_config_index in 0..2 {
+        helpers::empty_payment_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &fixture,
+            winning_config_index,
+            &creator_account,
+            &payer.pubkey(),
+            Some(0),
+            &creator_account,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            helpers::get_token_balance(&mut banks_client, &creator_account).await,
+            500 * (winning_config_index as u64 + 1)
+        );
+    }
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &fixture.accept_payment).await,
+        0
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_uniform_price_payouts() {
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    let store = helpers::set_store(&mut banks_client, &payer, &recent_blockhash, true)
+        .await
+        .unwrap();
+    let fixture = helpers::setup_configured_auction(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        0,
+        2,
+        |args| args.uniform_price = true,
+    )
+    .await
+    .unwrap();
+    let creator_account = helpers::create_associated_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &payer.pubkey(),
+        &fixture.token_mint,
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(&mut banks_client, &payer, &recent_blockhash, &fixture)
+        .await
+        .unwrap();
+    let mut bids = vec![];
+    for amount in &[1000, 600] {
+        let (bidder, bidder_pot_token) = helpers::place_bid(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &fixture,
+            *amount,
+        )
+        .await
+        .unwrap();
+        let refund = Keypair::new();
+        helpers::create_token_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &refund,
+            &fixture.token_mint,
+            &bidder.pubkey(),
+        )
+        .await
+        .unwrap();
+        bids.push((bidder, bidder_pot_token, refund.pubkey()));
+    }
+    helpers::end_auction(&mut banks_client, &payer, &recent_blockhash, &fixture)
+        .await
+        .unwrap();
+
+    // Both winners pay the lower winning bid, and the higher bidder gets the rest back.
+    for (bidder, bidder_pot_token, refund) in &bids {
+        helpers::claim_bid(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &fixture,
+            &bidder.pubkey(),
+            bidder_pot_token,
+            Some(refund),
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &fixture.accept_payment).await,
+        1200
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &bids[0].2).await,
+        400
+    );
+
+    // Every payout ticket can be emptied, each for the clearing price.
+    for winning_config_index in 0..2 {
+        helpers::empty_payment_account(
+            &mut banks_client,
+            &payer,
+            &recent_blockhash,
+            &fixture,
+            winning_config_index,
+            &creator_account,
+            &payer.pubkey(),
+            Some(0),
+            &creator_account,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            helpers::get_token_balance(&mut banks_client, &creator_account).await,
+            600 * (winning_config_index as u64 + 1)
+        );
+    }
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &fixture.accept_payment).await,
+        0
+    );
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_forfeited_deposit_goes_to_auctioneer() {
+    let mut context = program_test().start_with_context().await;
+    let mut banks_client = context.banks_client.clone();
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    let recent_blockhash = context.last_blockhash;
+
+    let store = helpers::set_store(&mut banks_client, &payer, &recent_blockhash, true)
+        .await
+        .unwrap();
+    let fixture = helpers::setup_configured_auction(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &store,
+        0,
+        1,
+        |args| {
+            args.bid_deposit = Some(BidDeposit {
+                amount: 100,
+                redeem_period: 60,
+            })
+        },
+    )
+    .await
+    .unwrap();
+    // The payer is the auction manager authority as well as the sole creator.
+    let auctioneer_account = helpers::create_associated_token_account(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &payer.pubkey(),
+        &fixture.token_mint,
+    )
+    .await
+    .unwrap();
+
+    helpers::start_auction(&mut banks_client, &payer, &recent_blockhash, &fixture)
+        .await
+        .unwrap();
+    let (bidder, bidder_pot_token) =
+        helpers::place_bid(&mut banks_client, &payer, &recent_blockhash, &fixture, 1000)
+            .await
+            .unwrap();
+    helpers::end_auction(&mut banks_client, &payer, &recent_blockhash, &fixture)
+        .await
+        .unwrap();
+    helpers::claim_bid(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &fixture,
+        &bidder.pubkey(),
+        &bidder_pot_token,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // The winner never got their deposit back, so claiming again forfeits it to the auctioneer.
+    let auction: AuctionData = try_from_slice_unchecked(
+        &helpers::get_account(&mut banks_client, &fixture.auction)
+            .await
+            .data,
+    )
+    .unwrap();
+    let recent_blockhash =
+        helpers::warp_to_timestamp(&mut context, auction.ended_at.unwrap() + 61).await;
+
+    // Nothing would ever pay it out of accept payment.
+    let err = helpers::claim_bid(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &fixture,
+        &bidder.pubkey(),
+        &bidder_pot_token,
+        None,
+    )
+    .await
+    .unwrap_err()
+    .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MetaplexError::IncorrectOwner as u32)
+        )
+    );
+
+    helpers::claim_bid_into(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &fixture,
+        &auctioneer_account,
+        &bidder.pubkey(),
+        &bidder_pot_token,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &auctioneer_account).await,
+        100
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &fixture.accept_payment).await,
+        1000
+    );
+    assert_eq!(
+        helpers::get_token_balance(&mut banks_client, &bidder_pot_token).await,
+        0
+    );
+}
@@ -1,16 +1,24 @@
 pub mod utils;
 
 use {
-    crate::utils::{assert_initialized, assert_owned_by, spl_token_transfer, TokenTransferParams},
+    crate::utils::{
+        assert_initialized, assert_owned_by, close_account, spl_token_transfer,
+        verify_merkle_proof, TokenTransferParams,
+    },
     anchor_lang::{
-        prelude::*, solana_program::system_program, AnchorDeserialize, AnchorSerialize,
-        Discriminator, Key,
+        prelude::*,
+        solana_program::{hash::hashv, program::invoke_signed, system_program},
+        AnchorDeserialize, AnchorSerialize, Discriminator, Key,
     },
     arrayref::array_ref,
     metaplex_token_metadata::{
-        instruction::{create_master_edition, create_metadata_accounts, update_metadata_accounts},
+        instruction::{
+            create_master_edition, create_metadata_accounts, freeze_delegated_account,
+            thaw_delegated_account, update_metadata_accounts, verify_collection,
+        },
         state::{
-            MAX_CREATOR_LEN, MAX_CREATOR_LIMIT, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+            Metadata, MAX_CREATOR_LEN, MAX_CREATOR_LIMIT, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
+            MAX_URI_LENGTH,
         },
     },
     spl_token::state::{Account, Mint},
@@ -18,16 +26,17 @@ use {
 };
 
 const PREFIX: &str = "candy_machine";
+const ESCROW: &str = "escrow";
 #[program]
 pub mod nft_candy_machine {
-    use anchor_lang::solana_program::{
-        program::{invoke, invoke_signed},
-        system_instruction,
-    };
+    use anchor_lang::solana_program::{program::invoke, system_instruction};
 
     use super::*;
 
-    pub fn mint_nft<'info>(ctx: Context<'_, '_, '_, 'info, MintNFT<'info>>) -> ProgramResult {
+    pub fn mint_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintNFT<'info>>,
+        payment_option_index: Option<u8>,
+    ) -> ProgramResult {
         let candy_machine = &mut ctx.accounts.candy_machine;
         let config = &ctx.accounts.config;
         let clock = &ctx.accounts.clock;
@@ -47,11 +56,44 @@ pub mod nft_candy_machine {
             }
         }
 
+        if candy_machine.end_condition_reached(clock.unix_timestamp) {
+            return Err(ErrorCode::CandyMachineEnded.into());
+        }
+
         if candy_machine.items_redeemed >= candy_machine.data.items_available {
             return Err(ErrorCode::CandyMachineEmpty.into());
         }
 
-        if let Some(mint) = candy_machine.token_mint {
+        // No index means the machine's own price, token mint and wallet.
+        let (token_mint, price, wallet) = match payment_option_index {
+            Some(i) => match candy_machine.payment_options.get(i as usize) {
+                Some(option) => (option.token_mint, option.price, option.wallet),
+                None => return Err(ErrorCode::InvalidPaymentOption.into()),
+            },
+            None => (
+                candy_machine.token_mint,
+                candy_machine.data.price,
+                candy_machine.wallet,
+            ),
+        };
+
+        // Freeze sales hold payments in escrow until the outcome of the sale is known.
+        let wallet = if candy_machine.freeze_settings.is_some() {
+            get_escrow_address(&candy_machine.key(), ctx.program_id).0
+        } else {
+            wallet
+        };
+
+        if *ctx.accounts.wallet.key != wallet {
+            return Err(ErrorCode::WalletMismatch.into());
+        }
+
+        // The authority would only pay into its own freeze sale, so its mints are neither paid
+        // for, frozen nor counted towards the sale.
+        let authority_freeze_mint = candy_machine.freeze_settings.is_some()
+            && *ctx.accounts.payer.key == candy_machine.authority;
+
+        if let Some(mint) = token_mint {
             let token_account_info = &ctx.remaining_accounts[0];
             let transfer_authority_info = &ctx.remaining_accounts[1];
             let token_account: Account = assert_initialized(&token_account_info)?;
@@ -62,7 +104,7 @@ pub mod nft_candy_machine {
                 return Err(ErrorCode::MintMismatch.into());
             }
 
-            if token_account.amount < candy_machine.data.price {
+            if token_account.amount < price {
                 return Err(ErrorCode::NotEnoughTokens.into());
             }
 
@@ -72,18 +114,26 @@ pub mod nft_candy_machine {
                 authority: transfer_authority_info.clone(),
                 authority_signer_seeds: &[],
                 token_program: ctx.accounts.token_program.clone(),
-                amount: candy_machine.data.price,
+                amount: price,
             })?;
-        } else {
-            if ctx.accounts.payer.lamports() < candy_machine.data.price {
+        } else if !authority_freeze_mint {
+            if ctx.accounts.payer.lamports() < price {
                 return Err(ErrorCode::NotEnoughSOL.into());
             }
 
+            // The escrow is a bare system account, its first payment has to make it rent exempt.
+            if candy_machine.freeze_settings.is_some()
+                && ctx.accounts.wallet.lamports() == 0
+                && price < ctx.accounts.rent.minimum_balance(0)
+            {
+                return Err(ErrorCode::EscrowNotRentExempt.into());
+            }
+
             invoke(
                 &system_instruction::transfer(
                     &ctx.accounts.payer.key,
                     ctx.accounts.wallet.key,
-                    candy_machine.data.price,
+                    price,
                 ),
                 &[
                     ctx.accounts.payer.clone(),
@@ -93,133 +143,440 @@ pub mod nft_candy_machine {
             )?;
         }
 
-        let config_line = get_config_line(
-            &config.to_account_info(),
-            candy_machine.items_redeemed as usize,
+        // Token payments take the first two remaining accounts, the collection ones follow.
+        let collection_accounts = if token_mint.is_some() {
+            &ctx.remaining_accounts[2..]
+        } else {
+            ctx.remaining_accounts
+        };
+
+        create_nft_from_config(CreateNftParams {
+            candy_machine,
+            config,
+            payer: ctx.accounts.payer.clone(),
+            metadata: ctx.accounts.metadata.clone(),
+            mint: ctx.accounts.mint.clone(),
+            mint_authority: ctx.accounts.mint_authority.clone(),
+            update_authority: ctx.accounts.update_authority.clone(),
+            master_edition: ctx.accounts.master_edition.clone(),
+            token_metadata_program: ctx.accounts.token_metadata_program.clone(),
+            token_program: ctx.accounts.token_program.clone(),
+            system_program: ctx.accounts.system_program.clone(),
+            rent: ctx.accounts.rent.to_account_info(),
+            collection_accounts,
+        })?;
+
+        if ctx.accounts.candy_machine.freeze_settings.is_some() && !authority_freeze_mint {
+            // The buyer's token account, with the candy machine approved as its delegate,
+            // follows the collection accounts.
+            let token_account_offset = if ctx.accounts.candy_machine.collection_mint.is_some() {
+                3
+            } else {
+                0
+            };
+            let token_account_info = match collection_accounts.get(token_account_offset) {
+                Some(val) => val,
+                None => return Err(ErrorCode::FreezeAccountsMissing.into()),
+            };
+
+            // Only the token just minted to the payer may be frozen for this sale.
+            assert_owned_by(token_account_info, &spl_token::id())?;
+            let token_account: Account = assert_initialized(token_account_info)?;
+            if token_account.mint != *ctx.accounts.mint.key {
+                return Err(ErrorCode::MintMismatch.into());
+            }
+            if token_account.owner != *ctx.accounts.payer.key || token_account.amount != 1 {
+                return Err(ErrorCode::NotTokenOwner.into());
+            }
+
+            set_token_frozen(
+                &ctx.accounts.candy_machine,
+                token_account_info,
+                &ctx.accounts.master_edition,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_metadata_program,
+                &ctx.accounts.token_program,
+                true,
+            )?;
+
+            if let Some(freeze) = &mut ctx.accounts.candy_machine.freeze_settings {
+                freeze.items_sold = freeze
+                    .items_sold
+                    .checked_add(1)
+                    .ok_or(ErrorCode::NumericalOverflowError)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_unminted<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawUnminted<'info>>,
+    ) -> ProgramResult {
+        let candy_machine = &mut ctx.accounts.candy_machine;
+        let config = &ctx.accounts.config;
+        let clock = &ctx.accounts.clock;
+
+        if !candy_machine.end_condition_reached(clock.unix_timestamp) {
+            return Err(ErrorCode::CandyMachineNotEnded.into());
+        }
+
+        if candy_machine.items_redeemed >= candy_machine.data.items_available {
+            return Err(ErrorCode::CandyMachineEmpty.into());
+        }
+
+        // Leftovers of a freeze sale stay put until the sale is known to have succeeded.
+        if candy_machine.freeze_settings.is_some()
+            && candy_machine.freeze_sale_succeeded(clock.unix_timestamp) != Some(true)
+        {
+            return Err(ErrorCode::FreezeSaleNotSucceeded.into());
+        }
+
+        // No payment is taken here, the authority is minting its own leftovers.
+        create_nft_from_config(CreateNftParams {
+            candy_machine,
+            config,
+            payer: ctx.accounts.authority.clone(),
+            metadata: ctx.accounts.metadata.clone(),
+            mint: ctx.accounts.mint.clone(),
+            mint_authority: ctx.accounts.mint_authority.clone(),
+            update_authority: ctx.accounts.update_authority.clone(),
+            master_edition: ctx.accounts.master_edition.clone(),
+            token_metadata_program: ctx.accounts.token_metadata_program.clone(),
+            token_program: ctx.accounts.token_program.clone(),
+            system_program: ctx.accounts.system_program.clone(),
+            rent: ctx.accounts.rent.to_account_info(),
+            collection_accounts: ctx.remaining_accounts,
+        })
+    }
+
+    /// Replaces the extra payment options. Each option's wallet is passed, in order, as a
+    /// remaining account so it can be checked against the option's mint.
+    pub fn set_payment_options(
+        ctx: Context<SetPaymentOptions>,
+        payment_options: Vec<PaymentOption>,
+    ) -> ProgramResult {
+        if payment_options.len() > MAX_PAYMENT_OPTIONS
+            || payment_options.len() != ctx.remaining_accounts.len()
+        {
+            return Err(ErrorCode::InvalidPaymentOption.into());
+        }
+
+        if ctx.accounts.candy_machine.freeze_settings.is_some() && !payment_options.is_empty() {
+            return Err(ErrorCode::FreezeRequiresSolPayment.into());
+        }
+
+        for (option, wallet_info) in payment_options.iter().zip(ctx.remaining_accounts) {
+            if option.wallet != *wallet_info.key {
+                return Err(ErrorCode::WalletMismatch.into());
+            }
+
+            match option.token_mint {
+                Some(mint) => {
+                    assert_owned_by(wallet_info, &spl_token::id())?;
+                    let token_account: Account = assert_initialized(wallet_info)?;
+                    if token_account.mint != mint {
+                        return Err(ErrorCode::MintMismatch.into());
+                    }
+                }
+                None => {
+                    if !wallet_info.data_is_empty() || wallet_info.lamports() == 0 {
+                        return Err(ErrorCode::WalletMismatch.into());
+                    }
+                }
+            }
+        }
+
+        ctx.accounts.candy_machine.payment_options = payment_options;
+        Ok(())
+    }
+
+    /// Registers the collection parent that every later mint is verified into. The machine
+    /// must already be the update authority of the parent's metadata so it can sign for it.
+    pub fn set_collection(ctx: Context<SetCollection>) -> ProgramResult {
+        let candy_machine = &mut ctx.accounts.candy_machine;
+        let collection_metadata_info = &ctx.accounts.collection_metadata;
+
+        assert_owned_by(collection_metadata_info, &metaplex_token_metadata::id())?;
+        let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+
+        if collection_metadata.mint != *ctx.accounts.collection_mint.key {
+            return Err(ErrorCode::MintMismatch.into());
+        }
+
+        if collection_metadata.update_authority != candy_machine.key() {
+            return Err(ErrorCode::CollectionAuthorityMismatch.into());
+        }
+
+        candy_machine.collection_mint = Some(*ctx.accounts.collection_mint.key);
+        Ok(())
+    }
+
+    /// Turns on freeze mode. Only allowed before the first mint, and only for machines that
+    /// are paid in SOL at their own price.
+    pub fn set_freeze_settings(
+        ctx: Context<UpdateCandyMachine>,
+        min_items_sold: u64,
+    ) -> ProgramResult {
+        let candy_machine = &mut ctx.accounts.candy_machine;
+
+        if candy_machine.items_redeemed > 0 {
+            return Err(ErrorCode::FreezeAfterMintStarted.into());
+        }
+
+        if candy_machine.token_mint.is_some() || !candy_machine.payment_options.is_empty() {
+            return Err(ErrorCode::FreezeRequiresSolPayment.into());
+        }
+
+        if min_items_sold > candy_machine.data.items_available {
+            return Err(ErrorCode::InvalidFreezeSettings.into());
+        }
+
+        candy_machine.freeze_settings = Some(FreezeSettings {
+            min_items_sold,
+            items_sold: 0,
+            items_thawed: 0,
+            funds_released: false,
+        });
+        Ok(())
+    }
+
+    /// Permissionless crank that thaws a buyer's token once a freeze sale has succeeded.
+    pub fn thaw_nft(ctx: Context<ThawNft>) -> ProgramResult {
+        if ctx
+            .accounts
+            .candy_machine
+            .freeze_sale_succeeded(ctx.accounts.clock.unix_timestamp)
+            != Some(true)
+        {
+            return Err(ErrorCode::FreezeSaleNotSucceeded.into());
+        }
+
+        set_token_frozen(
+            &ctx.accounts.candy_machine,
+            &ctx.accounts.token_account,
+            &ctx.accounts.master_edition,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_metadata_program,
+            &ctx.accounts.token_program,
+            false,
         )?;
 
-        candy_machine.items_redeemed = candy_machine
-            .items_redeemed
-            .checked_add(1)
-            .ok_or(ErrorCode::NumericalOverflowError)?;
+        if let Some(freeze) = &mut ctx.accounts.candy_machine.freeze_settings {
+            freeze.items_thawed = freeze
+                .items_thawed
+                .checked_add(1)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+        }
+        Ok(())
+    }
 
-        let config_key = config.key();
-        let authority_seeds = [
-            PREFIX.as_bytes(),
-            config_key.as_ref(),
-            candy_machine.data.uuid.as_bytes(),
-            &[candy_machine.bump],
-        ];
+    /// Permissionless crank that pays the escrow out to the wallet once a freeze sale has succeeded.
+    pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> ProgramResult {
+        let candy_machine = &mut ctx.accounts.candy_machine;
 
-        let mut creators: Vec<metaplex_token_metadata::state::Creator> =
-            vec![metaplex_token_metadata::state::Creator {
-                address: candy_machine.key(),
-                verified: true,
-                share: 0,
-            }];
-
-        for c in &config.data.creators {
-            creators.push(metaplex_token_metadata::state::Creator {
-                address: c.address,
-                verified: false,
-                share: c.share,
-            });
-        }
-
-        let metadata_infos = vec![
-            ctx.accounts.metadata.clone(),
-            ctx.accounts.mint.clone(),
-            ctx.accounts.mint_authority.clone(),
-            ctx.accounts.payer.clone(),
-            ctx.accounts.token_metadata_program.clone(),
-            ctx.accounts.token_program.clone(),
-            ctx.accounts.system_program.clone(),
-            ctx.accounts.rent.to_account_info().clone(),
-            candy_machine.to_account_info().clone(),
-        ];
+        if candy_machine.freeze_sale_succeeded(ctx.accounts.clock.unix_timestamp) != Some(true) {
+            return Err(ErrorCode::FreezeSaleNotSucceeded.into());
+        }
 
-        let master_edition_infos = vec![
-            ctx.accounts.master_edition.clone(),
-            ctx.accounts.mint.clone(),
-            ctx.accounts.mint_authority.clone(),
-            ctx.accounts.payer.clone(),
-            ctx.accounts.metadata.clone(),
-            ctx.accounts.token_metadata_program.clone(),
-            ctx.accounts.token_program.clone(),
-            ctx.accounts.system_program.clone(),
-            ctx.accounts.rent.to_account_info().clone(),
-            candy_machine.to_account_info().clone(),
+        let (escrow_key, escrow_bump) = get_escrow_address(&candy_machine.key(), ctx.program_id);
+        if *ctx.accounts.escrow.key != escrow_key {
+            return Err(ErrorCode::EscrowMismatch.into());
+        }
+
+        let candy_machine_key = candy_machine.key();
+        let escrow_seeds = [
+            PREFIX.as_bytes(),
+            candy_machine_key.as_ref(),
+            ESCROW.as_bytes(),
+            &[escrow_bump],
         ];
 
         invoke_signed(
-            &create_metadata_accounts(
-                *ctx.accounts.token_metadata_program.key,
-                *ctx.accounts.metadata.key,
-                *ctx.accounts.mint.key,
-                *ctx.accounts.mint_authority.key,
-                *ctx.accounts.payer.key,
-                candy_machine.key(),
-                config_line.name,
-                config.data.symbol.clone(),
-                config_line.uri,
-                Some(creators),
-                config.data.seller_fee_basis_points,
-                true,
-                config.data.is_mutable,
+            &system_instruction::transfer(
+                ctx.accounts.escrow.key,
+                ctx.accounts.wallet.key,
+                ctx.accounts.escrow.lamports(),
             ),
-            metadata_infos.as_slice(),
-            &[&authority_seeds],
+            &[
+                ctx.accounts.escrow.clone(),
+                ctx.accounts.wallet.clone(),
+                ctx.accounts.system_program.clone(),
+            ],
+            &[&escrow_seeds],
         )?;
 
-        invoke_signed(
-            &create_master_edition(
-                *ctx.accounts.token_metadata_program.key,
-                *ctx.accounts.master_edition.key,
-                *ctx.accounts.mint.key,
-                candy_machine.key(),
-                *ctx.accounts.mint_authority.key,
-                *ctx.accounts.metadata.key,
-                *ctx.accounts.payer.key,
-                Some(config.data.max_supply),
-            ),
-            master_edition_infos.as_slice(),
-            &[&authority_seeds],
-        )?;
+        if let Some(freeze) = &mut candy_machine.freeze_settings {
+            freeze.funds_released = true;
+        }
+        Ok(())
+    }
+
+    /// Lets a buyer of a failed freeze sale burn their token and take the price back out of escrow.
+    pub fn refund_nft(ctx: Context<RefundNft>) -> ProgramResult {
+        let candy_machine = &ctx.accounts.candy_machine;
+
+        if candy_machine.freeze_sale_succeeded(ctx.accounts.clock.unix_timestamp) != Some(false) {
+            return Err(ErrorCode::FreezeSaleNotFailed.into());
+        }
 
-        let mut new_update_authority = Some(candy_machine.authority);
+        let (escrow_key, escrow_bump) = get_escrow_address(&candy_machine.key(), ctx.program_id);
+        if *ctx.accounts.escrow.key != escrow_key {
+            return Err(ErrorCode::EscrowMismatch.into());
+        }
+
+        let token_account: Account = assert_initialized(&ctx.accounts.token_account)?;
+        if token_account.mint != *ctx.accounts.mint.key {
+            return Err(ErrorCode::MintMismatch.into());
+        }
+        if token_account.owner != *ctx.accounts.buyer.key || token_account.amount != 1 {
+            return Err(ErrorCode::NotTokenOwner.into());
+        }
 
-        if !ctx.accounts.config.data.retain_authority {
-            new_update_authority = Some(ctx.accounts.update_authority.key());
+        // Only tokens this machine minted are paid back.
+        assert_owned_by(&ctx.accounts.metadata, &metaplex_token_metadata::id())?;
+        let metadata = Metadata::from_account_info(&ctx.accounts.metadata)?;
+        let minted_by_machine = match &metadata.data.creators {
+            Some(creators) => creators
+                .first()
+                .map_or(false, |c| c.address == candy_machine.key() && c.verified),
+            None => false,
+        };
+        if !minted_by_machine || metadata.mint != *ctx.accounts.mint.key {
+            return Err(ErrorCode::MetadataNotFromCandyMachine.into());
         }
 
+        set_token_frozen(
+            candy_machine,
+            &ctx.accounts.token_account,
+            &ctx.accounts.master_edition,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_metadata_program,
+            &ctx.accounts.token_program,
+            false,
+        )?;
+
+        invoke(
+            &spl_token::instruction::burn(
+                ctx.accounts.token_program.key,
+                ctx.accounts.token_account.key,
+                ctx.accounts.mint.key,
+                ctx.accounts.buyer.key,
+                &[],
+                1,
+            )?,
+            &[
+                ctx.accounts.token_account.clone(),
+                ctx.accounts.mint.clone(),
+                ctx.accounts.buyer.clone(),
+                ctx.accounts.token_program.clone(),
+            ],
+        )?;
+
+        let candy_machine_key = candy_machine.key();
+        let escrow_seeds = [
+            PREFIX.as_bytes(),
+            candy_machine_key.as_ref(),
+            ESCROW.as_bytes(),
+            &[escrow_bump],
+        ];
+
         invoke_signed(
-            &update_metadata_accounts(
-                *ctx.accounts.token_metadata_program.key,
-                *ctx.accounts.metadata.key,
-                candy_machine.key(),
-                new_update_authority,
-                None,
-                Some(true),
+            &system_instruction::transfer(
+                ctx.accounts.escrow.key,
+                ctx.accounts.buyer.key,
+                candy_machine.data.price,
             ),
             &[
-                ctx.accounts.token_metadata_program.clone(),
-                ctx.accounts.metadata.clone(),
-                candy_machine.to_account_info().clone(),
+                ctx.accounts.escrow.clone(),
+                ctx.accounts.buyer.clone(),
+                ctx.accounts.system_program.clone(),
             ],
-            &[&authority_seeds],
+            &[&escrow_seeds],
         )?;
 
+        if let Some(freeze) = &mut ctx.accounts.candy_machine.freeze_settings {
+            freeze.items_thawed = freeze
+                .items_thawed
+                .checked_add(1)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+        }
         Ok(())
     }
 
+    pub fn close_candy_machine(ctx: Context<CloseCandyMachine>) -> ProgramResult {
+        let candy_machine_info = &ctx.accounts.candy_machine;
+        let candy_machine =
+            CandyMachine::try_deserialize(&mut &candy_machine_info.data.borrow()[..])?;
+
+        if candy_machine.authority != *ctx.accounts.authority.key {
+            return Err(ErrorCode::AuthorityMismatch.into());
+        }
+
+        if !candy_machine.is_finished(ctx.accounts.clock.unix_timestamp) {
+            return Err(ErrorCode::CandyMachineStillLive.into());
+        }
+
+        // Frozen tokens can only be thawed, and escrow only released, through the machine.
+        if let Some(freeze) = &candy_machine.freeze_settings {
+            let succeeded = candy_machine.freeze_sale_succeeded(ctx.accounts.clock.unix_timestamp);
+            if freeze.items_thawed < freeze.items_sold
+                || (succeeded == Some(true) && !freeze.funds_released)
+            {
+                return Err(ErrorCode::FreezeSaleNotSettled.into());
+            }
+        }
+
+        // Placeholders can only be revealed through the machine.
+        if candy_machine.hidden_settings.is_some()
+            && candy_machine.items_revealed < candy_machine.items_redeemed
+        {
+            return Err(ErrorCode::HiddenItemsNotRevealed.into());
+        }
+
+        let config_info = &ctx.accounts.config;
+        if candy_machine.config != *config_info.key {
+            return Err(ErrorCode::CandyMachineConfigMismatch.into());
+        }
+        let config = Config::try_deserialize(&mut &config_info.data.borrow()[..])?;
+        update_machine_count(config_info, config.data.max_number_of_lines, false)?;
+
+        close_account(candy_machine_info, &ctx.accounts.destination)
+    }
+
+    /// Machines mint and reveal from the config's lines, so it can only be closed once every
+    /// machine created over it has been closed.
+    pub fn close_config(ctx: Context<CloseConfig>) -> ProgramResult {
+        let config_info = &ctx.accounts.config;
+        let config = Config::try_deserialize(&mut &config_info.data.borrow()[..])?;
+
+        if config.authority != *ctx.accounts.authority.key {
+            return Err(ErrorCode::AuthorityMismatch.into());
+        }
+
+        let machine_count =
+            get_machine_count(&config_info.data.borrow(), config.data.max_number_of_lines)
+                .ok_or(ErrorCode::ConfigMissingMachineCount)?;
+        if machine_count > 0 {
+            return Err(ErrorCode::ConfigStillInUse.into());
+        }
+
+        close_account(config_info, &ctx.accounts.destination)
+    }
+
     pub fn update_candy_machine(
         ctx: Context<UpdateCandyMachine>,
         price: Option<u64>,
         go_live_date: Option<i64>,
+        end_settings: Option<EndSettings>,
     ) -> ProgramResult {
         let candy_machine = &mut ctx.accounts.candy_machine;
 
         if let Some(p) = price {
+            // Refunds of a failed freeze sale pay back the price, so it cannot move.
+            if candy_machine.freeze_settings.is_some() {
+                return Err(ErrorCode::FreezePriceLocked.into());
+            }
             candy_machine.data.price = p;
         }
 
@@ -227,6 +584,18 @@ pub mod nft_candy_machine {
             msg!("Go live date changed to {}", go_l);
             candy_machine.data.go_live_date = Some(go_l)
         }
+
+        if let Some(end_s) = end_settings {
+            // Moving the end of a finished or freeze sale would change how it was decided,
+            // so the end can only be set while nothing depends on it yet.
+            if candy_machine.is_finished(ctx.accounts.clock.unix_timestamp)
+                || (candy_machine.freeze_settings.is_some() && candy_machine.items_redeemed > 0)
+            {
+                return Err(ErrorCode::EndSettingsLocked.into());
+            }
+            assert_valid_end_settings(&end_s, candy_machine.data.items_available)?;
+            candy_machine.end_settings = Some(end_s);
+        }
         Ok(())
     }
 
@@ -282,85 +651,87 @@ pub mod nft_candy_machine {
         index: u32,
         config_lines: Vec<ConfigLine>,
     ) -> ProgramResult {
-        let config = &mut ctx.accounts.config;
-        let account = config.to_account_info();
-        let current_count = get_config_count(&account.data.borrow())?;
-        let mut data = account.data.borrow_mut();
+        write_config_lines(&ctx.accounts.config, index, config_lines)
+    }
+
+    pub fn update_config_line(
+        ctx: Context<UpdateConfigLine>,
+        index: u32,
+        config_line: ConfigLine,
+    ) -> ProgramResult {
+        let config = &ctx.accounts.config;
 
-        let mut fixed_config_lines = vec![];
+        let (_, max_items_redeemed) = get_watermarks(
+            &config.to_account_info().data.borrow(),
+            config.data.max_number_of_lines,
+        )
+        .ok_or(ErrorCode::ConfigMissingWatermarks)?;
 
-        if index > config.data.max_number_of_lines - 1 {
-            return Err(ErrorCode::IndexGreaterThanLength.into());
+        if (index as u64) < max_items_redeemed {
+            return Err(ErrorCode::ConfigLineAlreadyMinted.into());
         }
 
-        for line in &config_lines {
-            let mut array_of_zeroes = vec![];
-            while array_of_zeroes.len() < MAX_NAME_LENGTH - line.name.len() {
-                array_of_zeroes.push(0u8);
-            }
-            let name = line.name.clone() + std::str::from_utf8(&array_of_zeroes).unwrap();
+        write_config_lines(config, index, vec![config_line])
+    }
 
-            let mut array_of_zeroes = vec![];
-            while array_of_zeroes.len() < MAX_URI_LENGTH - line.uri.len() {
-                array_of_zeroes.push(0u8);
-            }
-            let uri = line.uri.clone() + std::str::from_utf8(&array_of_zeroes).unwrap();
-            fixed_config_lines.push(ConfigLine { name, uri })
-        }
+    pub fn truncate_config(
+        ctx: Context<TruncateConfig>,
+        new_max_number_of_lines: u32,
+    ) -> ProgramResult {
+        let config = &mut ctx.accounts.config;
+        let old_max_number_of_lines = config.data.max_number_of_lines;
 
-        let as_vec = fixed_config_lines.try_to_vec()?;
-        // remove unneeded u32 because we're just gonna edit the u32 at the front
-        let serialized: &[u8] = &as_vec.as_slice()[4..];
+        if new_max_number_of_lines == 0 || new_max_number_of_lines > old_max_number_of_lines {
+            return Err(ErrorCode::InvalidTruncation.into());
+        }
 
-        let position = CONFIG_ARRAY_START + 4 + (index as usize) * CONFIG_LINE_SIZE;
+        let account = config.to_account_info();
+        let mut data = account.data.borrow_mut();
 
-        let array_slice: &mut [u8] =
-            &mut data[position..position + fixed_config_lines.len() * CONFIG_LINE_SIZE];
-        array_slice.copy_from_slice(serialized);
+        let old_counters_start = get_machine_count_position(old_max_number_of_lines);
+        let (max_items_available, _) = get_watermarks(&data, old_max_number_of_lines)
+            .ok_or(ErrorCode::ConfigMissingWatermarks)?;
 
-        let bit_mask_vec_start = CONFIG_ARRAY_START
-            + 4
-            + (config.data.max_number_of_lines as usize) * CONFIG_LINE_SIZE
-            + 4;
+        if (new_max_number_of_lines as u64) < max_items_available {
+            return Err(ErrorCode::InvalidTruncation.into());
+        }
 
-        let mut new_count = current_count;
-        for i in 0..fixed_config_lines.len() {
-            let position = (index as usize)
-                .checked_add(i)
-                .ok_or(ErrorCode::NumericalOverflowError)?;
-            let my_position_in_vec = bit_mask_vec_start
-                + position
-                    .checked_div(8)
-                    .ok_or(ErrorCode::NumericalOverflowError)?;
-            let position_from_right = 7 - position
-                .checked_rem(8)
-                .ok_or(ErrorCode::NumericalOverflowError)?;
-            let mask = u8::pow(2, position_from_right as u32);
-
-            let old_value_in_vec = data[my_position_in_vec];
-            data[my_position_in_vec] = data[my_position_in_vec] | mask;
-            msg!(
-                "My position in vec is {} my mask is going to be {}, the old value is {}",
-                position,
-                mask,
-                old_value_in_vec
-            );
-            msg!(
-                "My new value is {} and my position from right is {}",
-                data[my_position_in_vec],
-                position_from_right
-            );
-            if old_value_in_vec != data[my_position_in_vec] {
-                msg!("Increasing count");
-                new_count = new_count
-                    .checked_add(1)
-                    .ok_or(ErrorCode::NumericalOverflowError)?;
-            }
+        // The bitmask sits right after the last line, so it moves down with the new length.
+        let old_bit_mask_vec_start =
+            CONFIG_ARRAY_START + 4 + (old_max_number_of_lines as usize) * CONFIG_LINE_SIZE + 4;
+        let new_vec_start =
+            CONFIG_ARRAY_START + 4 + (new_max_number_of_lines as usize) * CONFIG_LINE_SIZE;
+        let new_bit_mask_vec_start = new_vec_start + 4;
+        let bit_mask_bytes = (new_max_number_of_lines as usize)
+            .checked_add(7)
+            .ok_or(ErrorCode::NumericalOverflowError)?
+            / 8;
+
+        data.copy_within(
+            old_bit_mask_vec_start..old_bit_mask_vec_start + bit_mask_bytes,
+            new_bit_mask_vec_start,
+        );
+        data.copy_within(
+            old_counters_start
+                ..old_counters_start + CONFIG_MACHINE_COUNT_SIZE + CONFIG_WATERMARKS_SIZE,
+            get_machine_count_position(new_max_number_of_lines),
+        );
+
+        // Clear the bits of lines past the new end and recount what is left.
+        let leftover_bits = new_max_number_of_lines % 8;
+        if leftover_bits != 0 {
+            data[new_bit_mask_vec_start + bit_mask_bytes - 1] &= 0xFFu8 << (8 - leftover_bits);
         }
+        let mut new_count: u32 = 0;
+        for byte in &data[new_bit_mask_vec_start..new_bit_mask_vec_start + bit_mask_bytes] {
+            new_count += byte.count_ones();
+        }
+
+        let as_bytes = (new_max_number_of_lines / 8).to_le_bytes();
+        data[new_vec_start..new_vec_start + 4].copy_from_slice(&as_bytes);
+        data[CONFIG_ARRAY_START..CONFIG_ARRAY_START + 4].copy_from_slice(&new_count.to_le_bytes());
 
-        // plug in new count.
-        data[CONFIG_ARRAY_START..CONFIG_ARRAY_START + 4]
-            .copy_from_slice(&(new_count as u32).to_le_bytes());
+        config.data.max_number_of_lines = new_max_number_of_lines;
 
         Ok(())
     }
@@ -369,13 +740,28 @@ pub mod nft_candy_machine {
         ctx: Context<InitializeCandyMachine>,
         bump: u8,
         data: CandyMachineData,
+        end_settings: Option<EndSettings>,
+        hidden_settings: Option<HiddenSettings>,
     ) -> ProgramResult {
         let candy_machine = &mut ctx.accounts.candy_machine;
 
         if data.uuid.len() != 6 {
             return Err(ErrorCode::UuidMustBeExactly6Length.into());
         }
+        if let Some(end_s) = &end_settings {
+            assert_valid_end_settings(end_s, data.items_available)?;
+        }
+        if let Some(hidden) = &hidden_settings {
+            assert_valid_hidden_settings(
+                hidden,
+                data.items_available,
+                ctx.accounts.config.data.retain_authority,
+                ctx.accounts.config.data.is_mutable,
+            )?;
+        }
         candy_machine.data = data;
+        candy_machine.end_settings = end_settings;
+        candy_machine.hidden_settings = hidden_settings;
         candy_machine.wallet = *ctx.accounts.wallet.key;
         candy_machine.authority = *ctx.accounts.authority.key;
         candy_machine.config = ctx.accounts.config.key();
@@ -395,72 +781,394 @@ pub mod nft_candy_machine {
             candy_machine.token_mint = Some(*token_mint_info.key);
         }
 
-        if get_config_count(&ctx.accounts.config.to_account_info().data.borrow())?
-            < candy_machine.data.items_available as usize
-        {
-            return Err(ErrorCode::ConfigLineMismatch.into());
+        // With hidden settings the real lines are only uploaded after the sale.
+        if candy_machine.hidden_settings.is_none() {
+            if get_config_count(&ctx.accounts.config.to_account_info().data.borrow())?
+                < candy_machine.data.items_available as usize
+            {
+                return Err(ErrorCode::ConfigLineMismatch.into());
+            }
+
+            let _config_line = match get_config_line(&ctx.accounts.config.to_account_info(), 0) {
+                Ok(val) => val,
+                Err(_) => return Err(ErrorCode::ConfigMustHaveAtleastOneEntry.into()),
+            };
         }
 
-        let _config_line = match get_config_line(&ctx.accounts.config.to_account_info(), 0) {
-            Ok(val) => val,
-            Err(_) => return Err(ErrorCode::ConfigMustHaveAtleastOneEntry.into()),
-        };
+        raise_watermarks(
+            &ctx.accounts.config.to_account_info(),
+            ctx.accounts.config.data.max_number_of_lines,
+            candy_machine.data.items_available,
+            0,
+        )?;
+        update_machine_count(
+            &ctx.accounts.config.to_account_info(),
+            ctx.accounts.config.data.max_number_of_lines,
+            true,
+        )?;
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-#[instruction(bump: u8, data: CandyMachineData)]
-pub struct InitializeCandyMachine<'info> {
-    #[account(init, seeds=[PREFIX.as_bytes(), config.key().as_ref(), data.uuid.as_bytes()], payer=payer, bump=bump, space=8+32+32+33+32+64+64+64+200)]
-    candy_machine: ProgramAccount<'info, CandyMachine>,
-    #[account(constraint= wallet.owner == &spl_token::id() || (wallet.data_is_empty() && wallet.lamports() > 0) )]
-    wallet: AccountInfo<'info>,
-    #[account(has_one=authority)]
-    config: ProgramAccount<'info, Config>,
-    #[account(signer, constraint= authority.data_is_empty() && authority.lamports() > 0)]
-    authority: AccountInfo<'info>,
-    #[account(mut, signer)]
-    payer: AccountInfo<'info>,
-    #[account(address = system_program::ID)]
-    system_program: AccountInfo<'info>,
-    rent: Sysvar<'info, Rent>,
-}
+    pub fn reveal(ctx: Context<Reveal>, index: u32, proof: Vec<[u8; 32]>) -> ProgramResult {
+        let candy_machine = &ctx.accounts.candy_machine;
+        let config = &ctx.accounts.config;
+        let metadata_info = &ctx.accounts.metadata;
 
-#[derive(Accounts)]
-#[instruction(data: ConfigData)]
-pub struct InitializeConfig<'info> {
-    #[account(mut, constraint= config.to_account_info().owner == program_id && config.to_account_info().data_len() >= CONFIG_ARRAY_START+4+(data.max_number_of_lines as usize)*CONFIG_LINE_SIZE + 4 + (data.max_number_of_lines.checked_div(8).ok_or(ErrorCode::NumericalOverflowError)? as usize))]
-    config: AccountInfo<'info>,
-    #[account(constraint= authority.data_is_empty() && authority.lamports() > 0 )]
-    authority: AccountInfo<'info>,
-    #[account(mut, signer)]
-    payer: AccountInfo<'info>,
-    rent: Sysvar<'info, Rent>,
-}
+        let hidden_settings = match &candy_machine.hidden_settings {
+            Some(val) => val,
+            None => return Err(ErrorCode::NoHiddenSettings.into()),
+        };
 
-#[derive(Accounts)]
-pub struct AddConfigLines<'info> {
-    #[account(mut, has_one = authority)]
-    config: ProgramAccount<'info, Config>,
-    #[account(signer)]
-    authority: AccountInfo<'info>,
+        if index as u64 >= candy_machine.items_redeemed {
+            return Err(ErrorCode::IndexGreaterThanLength.into());
+        }
+
+        assert_owned_by(metadata_info, &metaplex_token_metadata::id())?;
+        let metadata = Metadata::from_account_info(metadata_info)?;
+
+        // Only a placeholder minted by this machine at this index may be revealed.
+        let minted_by_machine = match &metadata.data.creators {
+            Some(creators) => creators
+                .first()
+                .map_or(false, |c| c.address == candy_machine.key() && c.verified),
+            None => false,
+        };
+        if !minted_by_machine
+            || metadata.data.name.trim_matches(char::from(0))
+                != hidden_settings.placeholder_name(index as u64)
+        {
+            return Err(ErrorCode::MetadataNotAPlaceholder.into());
+        }
+
+        let config_line = get_config_line(&config.to_account_info(), index as usize)?;
+        let name = config_line.name.trim_matches(char::from(0));
+        let uri = config_line.uri.trim_matches(char::from(0));
+
+        let leaf = hashv(&[&index.to_le_bytes(), name.as_bytes(), uri.as_bytes()]).to_bytes();
+        if !verify_merkle_proof(&proof, hidden_settings.hash, leaf) {
+            return Err(ErrorCode::RevealHashMismatch.into());
+        }
+
+        let config_key = config.key();
+        let authority_seeds = [
+            PREFIX.as_bytes(),
+            config_key.as_ref(),
+            candy_machine.data.uuid.as_bytes(),
+            &[candy_machine.bump],
+        ];
+
+        let mut data = metadata.data;
+        data.name = name.to_string();
+        data.uri = uri.to_string();
+
+        // The placeholder was kept under the machine for this, the authority gets it now.
+        invoke_signed(
+            &update_metadata_accounts(
+                *ctx.accounts.token_metadata_program.key,
+                *metadata_info.key,
+                candy_machine.key(),
+                Some(candy_machine.authority),
+                Some(data),
+                None,
+            ),
+            &[
+                ctx.accounts.token_metadata_program.clone(),
+                metadata_info.clone(),
+                candy_machine.to_account_info().clone(),
+            ],
+            &[&authority_seeds],
+        )?;
+
+        // A placeholder can only be revealed once, its name no longer matches afterwards.
+        let candy_machine = &mut ctx.accounts.candy_machine;
+        candy_machine.items_revealed = candy_machine
+            .items_revealed
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+
+        Ok(())
+    }
+}
+
+pub struct CreateNftParams<'a, 'b> {
+    pub candy_machine: &'b mut ProgramAccount<'a, CandyMachine>,
+    pub config: &'b ProgramAccount<'a, Config>,
+    pub payer: AccountInfo<'a>,
+    pub metadata: AccountInfo<'a>,
+    pub mint: AccountInfo<'a>,
+    pub mint_authority: AccountInfo<'a>,
+    pub update_authority: AccountInfo<'a>,
+    pub master_edition: AccountInfo<'a>,
+    pub token_metadata_program: AccountInfo<'a>,
+    pub token_program: AccountInfo<'a>,
+    pub system_program: AccountInfo<'a>,
+    pub rent: AccountInfo<'a>,
+    /// Collection mint, metadata and master edition, only read when the machine has a collection
+    pub collection_accounts: &'b [AccountInfo<'a>],
+}
+
+/// Creates the metadata and master edition for the next config line and bumps `items_redeemed`.
+/// Payment, if any, is the caller's business.
+pub fn create_nft_from_config(params: CreateNftParams<'_, '_>) -> ProgramResult {
+    let CreateNftParams {
+        candy_machine,
+        config,
+        payer,
+        metadata,
+        mint,
+        mint_authority,
+        update_authority,
+        master_edition,
+        token_metadata_program,
+        token_program,
+        system_program,
+        rent,
+        collection_accounts,
+    } = params;
+
+    let (name, uri) = match &candy_machine.hidden_settings {
+        Some(hidden) => (
+            hidden.placeholder_name(candy_machine.items_redeemed),
+            hidden.uri.clone(),
+        ),
+        None => {
+            let config_line = get_config_line(
+                &config.to_account_info(),
+                candy_machine.items_redeemed as usize,
+            )?;
+            (config_line.name, config_line.uri)
+        }
+    };
+
+    candy_machine.items_redeemed = candy_machine
+        .items_redeemed
+        .checked_add(1)
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+
+    // Placeholder mints are only bound to their lines at reveal time.
+    if candy_machine.hidden_settings.is_none() {
+        raise_watermarks(
+            &config.to_account_info(),
+            config.data.max_number_of_lines,
+            0,
+            candy_machine.items_redeemed,
+        )?;
+    }
+
+    let config_key = config.key();
+    let authority_seeds = [
+        PREFIX.as_bytes(),
+        config_key.as_ref(),
+        candy_machine.data.uuid.as_bytes(),
+        &[candy_machine.bump],
+    ];
+
+    let mut creators: Vec<metaplex_token_metadata::state::Creator> =
+        vec![metaplex_token_metadata::state::Creator {
+            address: candy_machine.key(),
+            verified: true,
+            share: 0,
+        }];
+
+    for c in &config.data.creators {
+        creators.push(metaplex_token_metadata::state::Creator {
+            address: c.address,
+            verified: false,
+            share: c.share,
+        });
+    }
+
+    let metadata_infos = vec![
+        metadata.clone(),
+        mint.clone(),
+        mint_authority.clone(),
+        payer.clone(),
+        token_metadata_program.clone(),
+        token_program.clone(),
+        system_program.clone(),
+        rent.clone(),
+        candy_machine.to_account_info().clone(),
+    ];
+
+    let master_edition_infos = vec![
+        master_edition.clone(),
+        mint.clone(),
+        mint_authority.clone(),
+        payer.clone(),
+        metadata.clone(),
+        token_metadata_program.clone(),
+        token_program.clone(),
+        system_program.clone(),
+        rent.clone(),
+        candy_machine.to_account_info().clone(),
+    ];
+
+    invoke_signed(
+        &create_metadata_accounts(
+            *token_metadata_program.key,
+            *metadata.key,
+            *mint.key,
+            *mint_authority.key,
+            *payer.key,
+            candy_machine.key(),
+            name,
+            config.data.symbol.clone(),
+            uri,
+            Some(creators),
+            config.data.seller_fee_basis_points,
+            true,
+            config.data.is_mutable,
+        ),
+        metadata_infos.as_slice(),
+        &[&authority_seeds],
+    )?;
+
+    invoke_signed(
+        &create_master_edition(
+            *token_metadata_program.key,
+            *master_edition.key,
+            *mint.key,
+            candy_machine.key(),
+            *mint_authority.key,
+            *metadata.key,
+            *payer.key,
+            Some(config.data.max_supply),
+        ),
+        master_edition_infos.as_slice(),
+        &[&authority_seeds],
+    )?;
+
+    if let Some(collection_mint) = candy_machine.collection_mint {
+        if collection_accounts.len() < 3 || *collection_accounts[0].key != collection_mint {
+            return Err(ErrorCode::CollectionAccountsMissing.into());
+        }
+        let collection_mint_info = &collection_accounts[0];
+        let collection_metadata_info = &collection_accounts[1];
+        let collection_master_edition_info = &collection_accounts[2];
+
+        // The machine is update authority of both the new metadata and the collection parent.
+        invoke_signed(
+            &verify_collection(
+                *token_metadata_program.key,
+                *metadata.key,
+                candy_machine.key(),
+                candy_machine.key(),
+                collection_mint,
+                *collection_metadata_info.key,
+                *collection_master_edition_info.key,
+            ),
+            &[
+                token_metadata_program.clone(),
+                metadata.clone(),
+                candy_machine.to_account_info().clone(),
+                collection_mint_info.clone(),
+                collection_metadata_info.clone(),
+                collection_master_edition_info.clone(),
+            ],
+            &[&authority_seeds],
+        )?;
+    }
+
+    let mut new_update_authority = Some(candy_machine.authority);
+
+    if !config.data.retain_authority {
+        new_update_authority = Some(*update_authority.key);
+    }
+
+    // Placeholders stay under the machine so that it can sign the reveal.
+    if candy_machine.hidden_settings.is_some() {
+        new_update_authority = None;
+    }
+
+    invoke_signed(
+        &update_metadata_accounts(
+            *token_metadata_program.key,
+            *metadata.key,
+            candy_machine.key(),
+            new_update_authority,
+            None,
+            Some(true),
+        ),
+        &[
+            token_metadata_program.clone(),
+            metadata.clone(),
+            candy_machine.to_account_info().clone(),
+        ],
+        &[&authority_seeds],
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(bump: u8, data: CandyMachineData)]
+pub struct InitializeCandyMachine<'info> {
+    #[account(init, seeds=[PREFIX.as_bytes(), config.key().as_ref(), data.uuid.as_bytes()], payer=payer, bump=bump, space=8+32+32+33+32+64+64+64+200+4+MAX_PAYMENT_OPTIONS*PAYMENT_OPTION_SIZE)]
+    candy_machine: ProgramAccount<'info, CandyMachine>,
+    #[account(constraint= wallet.owner == &spl_token::id() || (wallet.data_is_empty() && wallet.lamports() > 0) )]
+    wallet: AccountInfo<'info>,
+    #[account(mut, has_one=authority)]
+    config: ProgramAccount<'info, Config>,
+    #[account(signer, constraint= authority.data_is_empty() && authority.lamports() > 0)]
+    authority: AccountInfo<'info>,
+    #[account(mut, signer)]
+    payer: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(data: ConfigData)]
+pub struct InitializeConfig<'info> {
+    #[account(mut, constraint= config.to_account_info().owner == program_id && config.to_account_info().data_len() >= CONFIG_ARRAY_START+4+(data.max_number_of_lines as usize)*CONFIG_LINE_SIZE + 4 + (data.max_number_of_lines.checked_div(8).ok_or(ErrorCode::NumericalOverflowError)? as usize))]
+    config: AccountInfo<'info>,
+    #[account(constraint= authority.data_is_empty() && authority.lamports() > 0 )]
+    authority: AccountInfo<'info>,
+    #[account(mut, signer)]
+    payer: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct AddConfigLines<'info> {
+    #[account(mut, has_one = authority)]
+    config: ProgramAccount<'info, Config>,
+    #[account(signer)]
+    authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfigLine<'info> {
+    #[account(mut, has_one = authority)]
+    config: ProgramAccount<'info, Config>,
+    #[account(signer)]
+    authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TruncateConfig<'info> {
+    #[account(mut, has_one = authority)]
+    config: ProgramAccount<'info, Config>,
+    #[account(signer)]
+    authority: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
 pub struct MintNFT<'info> {
+    #[account(mut)]
     config: ProgramAccount<'info, Config>,
     #[account(
         mut,
         has_one = config,
-        has_one = wallet,
         seeds = [PREFIX.as_bytes(), config.key().as_ref(), candy_machine.data.uuid.as_bytes()],
         bump = candy_machine.bump,
     )]
     candy_machine: ProgramAccount<'info, CandyMachine>,
     #[account(mut, signer)]
     payer: AccountInfo<'info>,
+    // Checked in mint_nft against the wallet of the chosen payment option.
     #[account(mut)]
     wallet: AccountInfo<'info>,
     // With the following accounts we aren't using anchor macros because they are CPI'd
@@ -485,6 +1193,179 @@ pub struct MintNFT<'info> {
     clock: Sysvar<'info, Clock>,
 }
 
+#[derive(Accounts)]
+pub struct WithdrawUnminted<'info> {
+    #[account(mut)]
+    config: ProgramAccount<'info, Config>,
+    #[account(
+        mut,
+        has_one = config,
+        has_one = authority,
+        seeds = [PREFIX.as_bytes(), config.key().as_ref(), candy_machine.data.uuid.as_bytes()],
+        bump = candy_machine.bump,
+    )]
+    candy_machine: ProgramAccount<'info, CandyMachine>,
+    #[account(mut, signer)]
+    authority: AccountInfo<'info>,
+    // With the following accounts we aren't using anchor macros because they are CPI'd
+    // through to token-metadata which will do all the validations we need on them.
+    #[account(mut)]
+    metadata: AccountInfo<'info>,
+    #[account(mut)]
+    mint: AccountInfo<'info>,
+    #[account(signer)]
+    mint_authority: AccountInfo<'info>,
+    #[account(signer)]
+    update_authority: AccountInfo<'info>,
+    #[account(mut)]
+    master_edition: AccountInfo<'info>,
+    #[account(address = metaplex_token_metadata::id())]
+    token_metadata_program: AccountInfo<'info>,
+    #[account(address = spl_token::id())]
+    token_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    system_program: AccountInfo<'info>,
+    rent: Sysvar<'info, Rent>,
+    clock: Sysvar<'info, Clock>,
+}
+
+// The closed accounts are taken as raw AccountInfos because a mutable ProgramAccount
+// would be serialized back over the zeroed data on exit.
+#[derive(Accounts)]
+pub struct SetPaymentOptions<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [PREFIX.as_bytes(), candy_machine.config.key().as_ref(), candy_machine.data.uuid.as_bytes()],
+        bump = candy_machine.bump
+    )]
+    candy_machine: ProgramAccount<'info, CandyMachine>,
+    #[account(signer)]
+    authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCollection<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [PREFIX.as_bytes(), candy_machine.config.key().as_ref(), candy_machine.data.uuid.as_bytes()],
+        bump = candy_machine.bump
+    )]
+    candy_machine: ProgramAccount<'info, CandyMachine>,
+    #[account(signer)]
+    authority: AccountInfo<'info>,
+    collection_mint: AccountInfo<'info>,
+    collection_metadata: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ThawNft<'info> {
+    #[account(
+        mut,
+        seeds = [PREFIX.as_bytes(), candy_machine.config.key().as_ref(), candy_machine.data.uuid.as_bytes()],
+        bump = candy_machine.bump
+    )]
+    candy_machine: ProgramAccount<'info, CandyMachine>,
+    // Validated by token-metadata, which checks the candy machine is the account's delegate.
+    #[account(mut)]
+    token_account: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    master_edition: AccountInfo<'info>,
+    #[account(address = metaplex_token_metadata::id())]
+    token_metadata_program: AccountInfo<'info>,
+    #[account(address = spl_token::id())]
+    token_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseEscrow<'info> {
+    #[account(
+        mut,
+        has_one = wallet,
+        seeds = [PREFIX.as_bytes(), candy_machine.config.key().as_ref(), candy_machine.data.uuid.as_bytes()],
+        bump = candy_machine.bump
+    )]
+    candy_machine: ProgramAccount<'info, CandyMachine>,
+    #[account(mut)]
+    escrow: AccountInfo<'info>,
+    #[account(mut)]
+    wallet: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    system_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct RefundNft<'info> {
+    #[account(
+        mut,
+        seeds = [PREFIX.as_bytes(), candy_machine.config.key().as_ref(), candy_machine.data.uuid.as_bytes()],
+        bump = candy_machine.bump
+    )]
+    candy_machine: ProgramAccount<'info, CandyMachine>,
+    #[account(mut)]
+    escrow: AccountInfo<'info>,
+    #[account(mut, signer)]
+    buyer: AccountInfo<'info>,
+    #[account(mut, constraint= token_account.owner == &spl_token::id())]
+    token_account: AccountInfo<'info>,
+    #[account(mut)]
+    mint: AccountInfo<'info>,
+    master_edition: AccountInfo<'info>,
+    metadata: AccountInfo<'info>,
+    #[account(address = metaplex_token_metadata::id())]
+    token_metadata_program: AccountInfo<'info>,
+    #[account(address = spl_token::id())]
+    token_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    system_program: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCandyMachine<'info> {
+    #[account(mut, constraint= candy_machine.owner == program_id)]
+    candy_machine: AccountInfo<'info>,
+    #[account(mut, constraint= config.owner == program_id)]
+    config: AccountInfo<'info>,
+    #[account(signer)]
+    authority: AccountInfo<'info>,
+    #[account(mut)]
+    destination: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
+}
+
+#[derive(Accounts)]
+pub struct CloseConfig<'info> {
+    #[account(mut, constraint= config.owner == program_id)]
+    config: AccountInfo<'info>,
+    #[account(signer)]
+    authority: AccountInfo<'info>,
+    #[account(mut)]
+    destination: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Reveal<'info> {
+    config: ProgramAccount<'info, Config>,
+    #[account(
+        mut,
+        has_one = config,
+        has_one = authority,
+        seeds = [PREFIX.as_bytes(), config.key().as_ref(), candy_machine.data.uuid.as_bytes()],
+        bump = candy_machine.bump,
+    )]
+    candy_machine: ProgramAccount<'info, CandyMachine>,
+    #[account(signer)]
+    authority: AccountInfo<'info>,
+    #[account(mut)]
+    metadata: AccountInfo<'info>,
+    #[account(address = metaplex_token_metadata::id())]
+    token_metadata_program: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateCandyMachine<'info> {
     #[account(
@@ -496,6 +1377,7 @@ pub struct UpdateCandyMachine<'info> {
     candy_machine: ProgramAccount<'info, CandyMachine>,
     #[account(signer)]
     authority: AccountInfo<'info>,
+    clock: Sysvar<'info, Clock>,
 }
 
 #[account]
@@ -508,6 +1390,48 @@ pub struct CandyMachine {
     pub data: CandyMachineData,
     pub items_redeemed: u64,
     pub bump: u8,
+    // Fields below were added after launch. They live after bump so that existing
+    // machines, whose trailing space is zeroed, still deserialize with them unset.
+    pub end_settings: Option<EndSettings>,
+    pub hidden_settings: Option<HiddenSettings>,
+    pub items_revealed: u64,
+    pub payment_options: Vec<PaymentOption>,
+    pub collection_mint: Option<Pubkey>,
+    pub freeze_settings: Option<FreezeSettings>,
+}
+
+impl CandyMachine {
+    /// True once the public sale is over because of `end_settings`. Running out of
+    /// items is tracked separately through `items_redeemed`.
+    pub fn end_condition_reached(&self, now: i64) -> bool {
+        match &self.end_settings {
+            Some(EndSettings {
+                end_setting_type: EndSettingType::Date,
+                number,
+            }) => now >= *number as i64,
+            Some(EndSettings {
+                end_setting_type: EndSettingType::Amount,
+                number,
+            }) => self.items_redeemed >= *number,
+            None => false,
+        }
+    }
+
+    /// For freeze sales, Some(true) once the machine is finished with enough paid mints and
+    /// Some(false) once it finished short of them. None while selling or without freeze mode.
+    pub fn freeze_sale_succeeded(&self, now: i64) -> Option<bool> {
+        match &self.freeze_settings {
+            Some(freeze) if self.is_finished(now) => {
+                Some(freeze.items_sold >= freeze.min_items_sold)
+            }
+            _ => None,
+        }
+    }
+
+    /// True once the machine is sold out or an end condition has been reached.
+    pub fn is_finished(&self, now: i64) -> bool {
+        self.items_redeemed >= self.data.items_available || self.end_condition_reached(now)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -518,6 +1442,65 @@ pub struct CandyMachineData {
     pub go_live_date: Option<i64>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum EndSettingType {
+    /// Sale ends once the clock passes `number`, a unix timestamp
+    Date,
+    /// Sale ends once `number` items have been redeemed, must not exceed items available
+    Amount,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EndSettings {
+    pub end_setting_type: EndSettingType,
+    pub number: u64,
+}
+
+pub const MAX_PAYMENT_OPTIONS: usize = 4;
+
+pub const PAYMENT_OPTION_SIZE: usize = 1 + 32 + // optional token mint
+8 + // price
+32; // wallet
+
+/// An alternative way to pay for a mint, picked by index in `mint_nft`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PaymentOption {
+    /// Mint of the token to pay with, or None for SOL
+    pub token_mint: Option<Pubkey>,
+    pub price: u64,
+    /// Token account of `token_mint`, or a system account for SOL, that receives the payment
+    pub wallet: Pubkey,
+}
+
+/// Buyers' tokens stay frozen and their payments stay in escrow until the sale is decided.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FreezeSettings {
+    /// Paid mints needed by the end of the sale, otherwise buyers can burn for a refund
+    pub min_items_sold: u64,
+    /// Paid mints so far, the authority's own mints and withdrawals are not counted
+    pub items_sold: u64,
+    /// Tokens thawed by the crank or by a refund
+    pub items_thawed: u64,
+    pub funds_released: bool,
+}
+
+/// Every mint gets the same placeholder until `reveal` swaps in the real config line.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct HiddenSettings {
+    /// Placeholder name, each mint gets " #<mint number>" appended to it
+    pub name: String,
+    /// Placeholder URI shared by every mint
+    pub uri: String,
+    /// Merkle root over hash(index as u32 le, name, uri) of the final config lines
+    pub hash: [u8; 32],
+}
+
+impl HiddenSettings {
+    pub fn placeholder_name(&self, index: u64) -> String {
+        format!("{} #{}", self.name, index + 1)
+    }
+}
+
 pub const CONFIG_ARRAY_START: usize = 32 + // authority
 4 + 6 + // uuid + u32 len
 4 + MAX_SYMBOL_LENGTH + // u32 len + symbol
@@ -537,6 +1520,8 @@ pub struct Config {
     // There is actually lines and lines of data after this but we explicitly never want them deserialized.
     // here there is a borsh vec u32 indicating number of bytes in bitmask array.
     // here there is a number of bytes equal to ceil(max_number_of_lines/8) and it is a bit mask used to figure out when to increment borsh vec u32
+    // here there is a u64 count of the candy machines using the config, see get_machine_count.
+    // here there are two u64 watermarks, see get_watermarks. Configs created before these counters have no room for them.
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -553,6 +1538,264 @@ pub struct ConfigData {
     pub max_number_of_lines: u32,
 }
 
+/// Writes `config_lines` starting at `index`, padding each to its full size and keeping the
+/// line count and bitmask in step.
+pub fn write_config_lines(
+    config: &ProgramAccount<Config>,
+    index: u32,
+    config_lines: Vec<ConfigLine>,
+) -> ProgramResult {
+    let account = config.to_account_info();
+    let current_count = get_config_count(&account.data.borrow())?;
+    let mut data = account.data.borrow_mut();
+
+    let mut fixed_config_lines = vec![];
+
+    if index > config.data.max_number_of_lines - 1 {
+        return Err(ErrorCode::IndexGreaterThanLength.into());
+    }
+
+    for line in &config_lines {
+        let mut array_of_zeroes = vec![];
+        while array_of_zeroes.len() < MAX_NAME_LENGTH - line.name.len() {
+            array_of_zeroes.push(0u8);
+        }
+        let name = line.name.clone() + std::str::from_utf8(&array_of_zeroes).unwrap();
+
+        let mut array_of_zeroes = vec![];
+        while array_of_zeroes.len() < MAX_URI_LENGTH - line.uri.len() {
+            array_of_zeroes.push(0u8);
+        }
+        let uri = line.uri.clone() + std::str::from_utf8(&array_of_zeroes).unwrap();
+        fixed_config_lines.push(ConfigLine { name, uri })
+    }
+
+    let as_vec = fixed_config_lines.try_to_vec()?;
+    // remove unneeded u32 because we're just gonna edit the u32 at the front
+    let serialized: &[u8] = &as_vec.as_slice()[4..];
+
+    let position = CONFIG_ARRAY_START + 4 + (index as usize) * CONFIG_LINE_SIZE;
+
+    let array_slice: &mut [u8] =
+        &mut data[position..position + fixed_config_lines.len() * CONFIG_LINE_SIZE];
+    array_slice.copy_from_slice(serialized);
+
+    let bit_mask_vec_start =
+        CONFIG_ARRAY_START + 4 + (config.data.max_number_of_lines as usize) * CONFIG_LINE_SIZE + 4;
+
+    let mut new_count = current_count;
+    for i in 0..fixed_config_lines.len() {
+        let position = (index as usize)
+            .checked_add(i)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+        let my_position_in_vec = bit_mask_vec_start
+            + position
+                .checked_div(8)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+        let position_from_right = 7 - position
+            .checked_rem(8)
+            .ok_or(ErrorCode::NumericalOverflowError)?;
+        let mask = u8::pow(2, position_from_right as u32);
+
+        let old_value_in_vec = data[my_position_in_vec];
+        data[my_position_in_vec] = data[my_position_in_vec] | mask;
+        msg!(
+            "My position in vec is {} my mask is going to be {}, the old value is {}",
+            position,
+            mask,
+            old_value_in_vec
+        );
+        msg!(
+            "My new value is {} and my position from right is {}",
+            data[my_position_in_vec],
+            position_from_right
+        );
+        if old_value_in_vec != data[my_position_in_vec] {
+            msg!("Increasing count");
+            new_count = new_count
+                .checked_add(1)
+                .ok_or(ErrorCode::NumericalOverflowError)?;
+        }
+    }
+
+    // plug in new count.
+    data[CONFIG_ARRAY_START..CONFIG_ARRAY_START + 4]
+        .copy_from_slice(&(new_count as u32).to_le_bytes());
+
+    Ok(())
+}
+
+pub const CONFIG_MACHINE_COUNT_SIZE: usize = 8; // candy machines using the config
+
+pub const CONFIG_WATERMARKS_SIZE: usize = 8 + // most items available of any candy machine
+8; // most lines minted by any candy machine
+
+pub fn get_machine_count_position(max_number_of_lines: u32) -> usize {
+    let max_number_of_lines = max_number_of_lines as usize;
+    CONFIG_ARRAY_START
+        + 4
+        + max_number_of_lines * CONFIG_LINE_SIZE
+        + 4
+        + (max_number_of_lines + 7) / 8
+}
+
+/// The number of candy machines created over the config and not yet closed. Returns None for
+/// configs created without room for it.
+pub fn get_machine_count(data: &[u8], max_number_of_lines: u32) -> Option<u64> {
+    let position = get_machine_count_position(max_number_of_lines);
+    if data.len() < position + CONFIG_MACHINE_COUNT_SIZE {
+        return None;
+    }
+    Some(u64::from_le_bytes(*array_ref![data, position, 8]))
+}
+
+/// Counts a candy machine in or out of the config. Configs without room for the count are
+/// left alone, they can never be closed.
+pub fn update_machine_count(
+    config_info: &AccountInfo,
+    max_number_of_lines: u32,
+    opened: bool,
+) -> ProgramResult {
+    let mut data = config_info.data.borrow_mut();
+    if let Some(count) = get_machine_count(&data, max_number_of_lines) {
+        let count = if opened {
+            count.checked_add(1)
+        } else {
+            count.checked_sub(1)
+        }
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+        let position = get_machine_count_position(max_number_of_lines);
+        data[position..position + CONFIG_MACHINE_COUNT_SIZE].copy_from_slice(&count.to_le_bytes());
+    }
+    Ok(())
+}
+
+pub fn get_watermarks_position(max_number_of_lines: u32) -> usize {
+    get_machine_count_position(max_number_of_lines) + CONFIG_MACHINE_COUNT_SIZE
+}
+
+/// The config cannot list the candy machines using it, so it keeps the most items any of
+/// them makes available and the most lines any of them has minted. Returns None for configs
+/// created without room for them.
+pub fn get_watermarks(data: &[u8], max_number_of_lines: u32) -> Option<(u64, u64)> {
+    let position = get_watermarks_position(max_number_of_lines);
+    if data.len() < position + CONFIG_WATERMARKS_SIZE {
+        return None;
+    }
+    Some((
+        u64::from_le_bytes(*array_ref![data, position, 8]),
+        u64::from_le_bytes(*array_ref![data, position + 8, 8]),
+    ))
+}
+
+/// Raises the watermarks to at least the given values. Configs without room for them are
+/// left alone, they can never be updated or truncated.
+pub fn raise_watermarks(
+    config_info: &AccountInfo,
+    max_number_of_lines: u32,
+    items_available: u64,
+    items_redeemed: u64,
+) -> ProgramResult {
+    let mut data = config_info.data.borrow_mut();
+    if let Some((max_items_available, max_items_redeemed)) =
+        get_watermarks(&data, max_number_of_lines)
+    {
+        let position = get_watermarks_position(max_number_of_lines);
+        data[position..position + 8]
+            .copy_from_slice(&max_items_available.max(items_available).to_le_bytes());
+        data[position + 8..position + 16]
+            .copy_from_slice(&max_items_redeemed.max(items_redeemed).to_le_bytes());
+    }
+    Ok(())
+}
+
+pub fn get_escrow_address(candy_machine: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), candy_machine.as_ref(), ESCROW.as_bytes()],
+        program_id,
+    )
+}
+
+/// Freezes or thaws a minted token through token-metadata, with the candy machine signing
+/// as the token account's delegate.
+pub fn set_token_frozen<'a>(
+    candy_machine: &ProgramAccount<'a, CandyMachine>,
+    token_account: &AccountInfo<'a>,
+    master_edition: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    token_metadata_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    freeze: bool,
+) -> ProgramResult {
+    let authority_seeds = [
+        PREFIX.as_bytes(),
+        candy_machine.config.as_ref(),
+        candy_machine.data.uuid.as_bytes(),
+        &[candy_machine.bump],
+    ];
+
+    let instruction = if freeze {
+        freeze_delegated_account(
+            *token_metadata_program.key,
+            candy_machine.key(),
+            *token_account.key,
+            *master_edition.key,
+            *mint.key,
+        )
+    } else {
+        thaw_delegated_account(
+            *token_metadata_program.key,
+            candy_machine.key(),
+            *token_account.key,
+            *master_edition.key,
+            *mint.key,
+        )
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            candy_machine.to_account_info(),
+            token_account.clone(),
+            master_edition.clone(),
+            mint.clone(),
+            token_program.clone(),
+            token_metadata_program.clone(),
+        ],
+        &[&authority_seeds],
+    )
+}
+
+pub fn assert_valid_end_settings(
+    end_settings: &EndSettings,
+    items_available: u64,
+) -> ProgramResult {
+    if end_settings.end_setting_type == EndSettingType::Amount
+        && end_settings.number > items_available
+    {
+        return Err(ErrorCode::InvalidEndSettings.into());
+    }
+    Ok(())
+}
+
+pub fn assert_valid_hidden_settings(
+    hidden_settings: &HiddenSettings,
+    items_available: u64,
+    retain_authority: bool,
+    is_mutable: bool,
+) -> ProgramResult {
+    // Reveal rewrites the metadata and then hands it to the candy machine authority,
+    // which is where a retained authority would have gone at mint time.
+    if !retain_authority
+        || !is_mutable
+        || hidden_settings.placeholder_name(items_available).len() > MAX_NAME_LENGTH
+        || hidden_settings.uri.len() > MAX_URI_LENGTH
+    {
+        return Err(ErrorCode::InvalidHiddenSettings.into());
+    }
+    Ok(())
+}
+
 pub fn get_config_count(data: &Ref<&mut [u8]>) -> core::result::Result<usize, ProgramError> {
     return Ok(u32::from_le_bytes(*array_ref![data, CONFIG_ARRAY_START, 4]) as usize);
 }
@@ -624,4 +1867,70 @@ pub enum ErrorCode {
     CandyMachineNotLiveYet,
     #[msg("Number of config lines must be at least number of items available")]
     ConfigLineMismatch,
+    #[msg("Candy machine has reached its end condition!")]
+    CandyMachineEnded,
+    #[msg("Candy machine has not reached an end condition yet")]
+    CandyMachineNotEnded,
+    #[msg("End settings amount cannot exceed items available")]
+    InvalidEndSettings,
+    #[msg("End settings cannot change once the candy machine has ended or a freeze sale has started")]
+    EndSettingsLocked,
+    #[msg("Authority does not match the account's authority")]
+    AuthorityMismatch,
+    #[msg("Candy machine must be sold out or ended before this can be done")]
+    CandyMachineStillLive,
+    #[msg("Candy machine does not use this config")]
+    CandyMachineConfigMismatch,
+    #[msg("Config was created without room for its machine count and cannot be closed")]
+    ConfigMissingMachineCount,
+    #[msg("Config is still used by a candy machine")]
+    ConfigStillInUse,
+    #[msg("Hidden settings need a retained update authority, a mutable config and must fit name and uri limits")]
+    InvalidHiddenSettings,
+    #[msg("Candy machine does not have hidden settings")]
+    NoHiddenSettings,
+    #[msg("Metadata is not a placeholder minted by this candy machine at this index")]
+    MetadataNotAPlaceholder,
+    #[msg("Config line does not match the committed hidden settings hash")]
+    RevealHashMismatch,
+    #[msg("Every hidden item must be revealed before the candy machine or its config is closed")]
+    HiddenItemsNotRevealed,
+    #[msg("Config line has already been minted by a candy machine using this config")]
+    ConfigLineAlreadyMinted,
+    #[msg("Config can only shrink, and not below items available of a candy machine using it")]
+    InvalidTruncation,
+    #[msg("Config was created without room for its watermarks and cannot be edited")]
+    ConfigMissingWatermarks,
+    #[msg("Payment option does not exist or too many payment options were given")]
+    InvalidPaymentOption,
+    #[msg("Wallet does not match the chosen payment option")]
+    WalletMismatch,
+    #[msg("Candy machine must be the update authority of the collection metadata")]
+    CollectionAuthorityMismatch,
+    #[msg("Collection mint, metadata and master edition must be passed for this candy machine")]
+    CollectionAccountsMissing,
+    #[msg("Freeze settings can only be set before the first mint")]
+    FreezeAfterMintStarted,
+    #[msg("Freeze mode only works with SOL payment at the candy machine's own price")]
+    FreezeRequiresSolPayment,
+    #[msg("Minimum items sold cannot exceed items available")]
+    InvalidFreezeSettings,
+    #[msg("Price cannot change while freeze mode is on")]
+    FreezePriceLocked,
+    #[msg("Buyer token account must be passed for freeze mode")]
+    FreezeAccountsMissing,
+    #[msg("Freeze sale has not succeeded")]
+    FreezeSaleNotSucceeded,
+    #[msg("Freeze sale has not failed")]
+    FreezeSaleNotFailed,
+    #[msg("Freeze sale still has frozen tokens or escrowed funds")]
+    FreezeSaleNotSettled,
+    #[msg("Escrow account does not match the candy machine")]
+    EscrowMismatch,
+    #[msg("Buyer does not own exactly one of this token")]
+    NotTokenOwner,
+    #[msg("Metadata was not minted by this candy machine")]
+    MetadataNotFromCandyMachine,
+    #[msg("Price is below the rent exemption of the escrow account")]
+    EscrowNotRentExempt,
 }
@@ -3,6 +3,7 @@ use {
     anchor_lang::{
         prelude::{AccountInfo, ProgramError, ProgramResult, Pubkey},
         solana_program::{
+            hash::hashv,
             program::invoke_signed,
             program_pack::{IsInitialized, Pack},
         },
@@ -20,6 +21,24 @@ pub fn assert_initialized<T: Pack + IsInitialized>(
     }
 }
 
+/// Moves every lamport out of `account` into `destination` and zeroes its data, so the
+/// account cannot be revived with its old contents later in the same transaction.
+pub fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ErrorCode::NumericalOverflowError)?;
+    **account.lamports.borrow_mut() = 0;
+
+    let mut data = account.data.borrow_mut();
+    for byte in data.iter_mut() {
+        *byte = 0;
+    }
+
+    Ok(())
+}
+
 pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
     if account.owner != owner {
         Err(ErrorCode::IncorrectOwner.into())
@@ -69,3 +88,17 @@ pub fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult
 
     result.map_err(|_| ErrorCode::TokenTransferFailed.into())
 }
+
+/// Checks a Merkle proof for `leaf` against `root`. Each pair is hashed in sorted order,
+/// so the proof does not need to say which side each sibling sits on.
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for sibling in proof {
+        computed = if computed <= *sibling {
+            hashv(&[&computed, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &computed]).to_bytes()
+        };
+    }
+    computed == root
+}
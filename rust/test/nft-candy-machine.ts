@@ -1,6 +1,7 @@
 import * as anchor from "@project-serum/anchor";
 
 import assert from "assert";
+import * as crypto from "crypto";
 
 import { AccountLayout, MintLayout, Token } from "@solana/spl-token";
 import {
@@ -58,6 +59,15 @@ function fromUTF8Array(data: number[]) {
 
   return str;
 }
+
+// Name of a metadata account: key, update authority and mint come first, then
+// the u32 length and bytes of the name.
+function metadataName(data: Buffer) {
+  return fromUTF8Array([...data.slice(69, 69 + data.readUInt32LE(65))]).replace(
+    /\0/g,
+    ""
+  );
+}
 export function createAssociatedTokenAccountInstruction(
   associatedTokenAddress: PublicKey,
   payer: PublicKey,
@@ -122,8 +132,39 @@ const configArrayStart =
   1 + // retain authority
   4; // max number of lines;
 const configLineSize = 4 + 32 + 4 + 200;
+const configMachineCountSize = 8; // candy machines using the config
+const configWatermarksSize = 8 + 8; // items available + lines minted
 
 const CANDY_MACHINE = "candy_machine";
+
+const sha256 = (...parts: Buffer[]) =>
+  crypto.createHash("sha256").update(Buffer.concat(parts)).digest();
+
+// Pairs are hashed in sorted order, like verify_merkle_proof on chain.
+const hashPair = (a: Buffer, b: Buffer) =>
+  Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+
+// Returns the root over `leaves` and the proof for the leaf at `index`. A node
+// without a sibling is carried up to the next level as is.
+function merkleProof(leaves: Buffer[], index: number) {
+  let level = leaves;
+  const proof: Buffer[] = [];
+  while (level.length > 1) {
+    if ((index ^ 1) < level.length) {
+      proof.push(level[index ^ 1]);
+    }
+    const next = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(
+        i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i]
+      );
+    }
+    level = next;
+    index = index >> 1;
+  }
+  return { root: level[0], proof };
+}
+
 describe("nft-candy-machine", function () {
   // Configure the client to use the local cluster.
   const idl = JSON.parse(
@@ -273,6 +314,198 @@ describe("nft-candy-machine", function () {
     )[0];
   };
 
+  const configSize = function (lines: number) {
+    return (
+      configArrayStart +
+      4 +
+      lines * configLineSize +
+      4 +
+      Math.ceil(lines / 8) +
+      configMachineCountSize +
+      configWatermarksSize
+    );
+  };
+
+  // Creates a config with `size` lines, all filled, and a SOL candy machine
+  // over it that sells `itemsAvailable` items at `price` lamports.
+  const initCandyMachine = async function (
+    that,
+    size: number,
+    price: number,
+    endSettings,
+    hiddenSettings,
+    itemsAvailable: number = size
+  ) {
+    that.config = anchor.web3.Keypair.generate();
+    const txInstr = await createConfig(that, true, size);
+    const linesInstr = await addConfigLines(that, size);
+    that.candyMachineUuid = anchor.web3.Keypair.generate()
+      .publicKey.toBase58()
+      .slice(0, 6);
+    const [candyMachine, bump] = await getCandyMachine(
+      that.config.publicKey,
+      that.candyMachineUuid
+    );
+    that.candyMachine = candyMachine;
+
+    await program.rpc.initializeCandyMachine(
+      bump,
+      {
+        uuid: that.candyMachineUuid,
+        price: new anchor.BN(price),
+        itemsAvailable: new anchor.BN(itemsAvailable),
+        goLiveDate: null,
+      },
+      endSettings,
+      hiddenSettings,
+      {
+        accounts: {
+          candyMachine,
+          wallet: myWallet.publicKey,
+          config: that.config.publicKey,
+          authority: that.authority.publicKey,
+          payer: myWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [myWallet, that.authority, that.config],
+        instructions: [
+          anchor.web3.SystemProgram.createAccount({
+            fromPubkey: myWallet.publicKey,
+            newAccountPubkey: that.config.publicKey,
+            space: configSize(size),
+            lamports:
+              await provider.connection.getMinimumBalanceForRentExemption(
+                configSize(size)
+              ),
+            programId: programId,
+          }),
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: myWallet.publicKey,
+            toPubkey: that.authority.publicKey,
+            lamports: 5,
+          }),
+          txInstr,
+          ...linesInstr,
+        ],
+      }
+    );
+  };
+
+  // Instructions that create a fresh mint owned by `owner` and mint its one
+  // token into the owner's associated account.
+  const createMintInstructions = async function (
+    mint: PublicKey,
+    token: PublicKey,
+    owner: PublicKey
+  ): Promise<TransactionInstruction[]> {
+    return [
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: myWallet.publicKey,
+        newAccountPubkey: mint,
+        space: MintLayout.span,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(
+          MintLayout.span
+        ),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      Token.createInitMintInstruction(TOKEN_PROGRAM_ID, mint, 0, owner, owner),
+      createAssociatedTokenAccountInstruction(
+        token,
+        myWallet.publicKey,
+        owner,
+        mint
+      ),
+      Token.createMintToInstruction(
+        TOKEN_PROGRAM_ID,
+        mint,
+        token,
+        owner,
+        [],
+        1
+      ),
+    ];
+  };
+
+  // Mints from `that.candyMachine` with `payer` paying `price` lamports into
+  // `wallet`. Extra instructions run after the mint is created and before the
+  // candy machine is called.
+  const mintNft = async function (
+    that,
+    payer: anchor.web3.Keypair,
+    price: number,
+    {
+      mint = anchor.web3.Keypair.generate(),
+      paymentOptionIndex = null,
+      wallet = myWallet.publicKey,
+      remainingAccounts = [],
+      instructions = [],
+      signers = [],
+    }: {
+      mint?: anchor.web3.Keypair;
+      paymentOptionIndex?: number;
+      wallet?: PublicKey;
+      remainingAccounts?: anchor.web3.AccountMeta[];
+      instructions?: TransactionInstruction[];
+      signers?: anchor.web3.Keypair[];
+    } = {}
+  ) {
+    const token = await getTokenWallet(payer.publicKey, mint.publicKey);
+    const metadata = await getMetadata(mint.publicKey);
+    const masterEdition = await getMasterEdition(mint.publicKey);
+
+    await program.rpc.mintNft(paymentOptionIndex, {
+      accounts: {
+        config: that.config.publicKey,
+        candyMachine: that.candyMachine,
+        payer: payer.publicKey,
+        wallet,
+        mint: mint.publicKey,
+        metadata,
+        masterEdition,
+        mintAuthority: payer.publicKey,
+        updateAuthority: payer.publicKey,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+      },
+      remainingAccounts,
+      signers: [mint, payer, myWallet, ...signers],
+      instructions: [
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: myWallet.publicKey,
+          toPubkey: payer.publicKey,
+          lamports: price + 10000000, // add minting fees in there
+        }),
+        ...(await createMintInstructions(
+          mint.publicKey,
+          token,
+          payer.publicKey
+        )),
+        ...instructions,
+      ],
+    });
+
+    return { mint: mint.publicKey, token, metadata, masterEdition };
+  };
+
+  // Asserts that `promise` fails with the program error `name`.
+  const assertProgramError = async function (
+    promise: Promise<any>,
+    name: string
+  ) {
+    const code = idl.errors.find((e) => e.name == name).code;
+    try {
+      await promise;
+    } catch (e) {
+      assert.equal(e.code, code);
+      return;
+    }
+    assert.fail(`expected ${name}`);
+  };
+
   describe("sol only", function () {
     beforeEach(async function () {
       const config = await anchor.web3.Keypair.generate();
@@ -296,6 +529,8 @@ describe("nft-candy-machine", function () {
             itemsAvailable: new anchor.BN(10),
             goLiveDate: null,
           },
+          null,
+          null,
           {
             accounts: {
               candyMachine,
@@ -394,7 +629,7 @@ describe("nft-candy-machine", function () {
           this.candyMachineUuid
         );
         try {
-          const tx = await program.rpc.mintNft({
+          const tx = await program.rpc.mintNft(null, {
             accounts: {
               config: this.config.publicKey,
               candyMachine: candyMachine,
@@ -483,7 +718,7 @@ describe("nft-candy-machine", function () {
       );
 
       try {
-        const tx = await program.rpc.mintNft({
+        const tx = await program.rpc.mintNft(null, {
           accounts: {
             config: this.config.publicKey,
             candyMachine,
@@ -502,12 +737,18 @@ describe("nft-candy-machine", function () {
           },
           signers: [mint, this.authority, myWallet],
           instructions: [
-            program.instruction.updateCandyMachine(null, new anchor.BN(500), {
-              accounts: {
-                candyMachine,
-                authority: this.authority.publicKey,
-              },
-            }),
+            program.instruction.updateCandyMachine(
+              null,
+              new anchor.BN(500),
+              null,
+              {
+                accounts: {
+                  candyMachine,
+                  authority: this.authority.publicKey,
+                  clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+                },
+              }
+            ),
             anchor.web3.SystemProgram.createAccount({
               fromPubkey: myWallet.publicKey,
               newAccountPubkey: mint.publicKey,
@@ -576,7 +817,7 @@ describe("nft-candy-machine", function () {
         this.candyMachineUuid
       );
       try {
-        const tx = await program.rpc.mintNft({
+        const tx = await program.rpc.mintNft(null, {
           accounts: {
             config: this.config.publicKey,
             candyMachine: candyMachine,
@@ -686,6 +927,8 @@ describe("nft-candy-machine", function () {
             itemsAvailable: new anchor.BN(5),
             goLiveDate: null,
           },
+          null,
+          null,
           {
             accounts: {
               candyMachine,
@@ -800,7 +1043,7 @@ describe("nft-candy-machine", function () {
         this.candyMachineUuid
       );
       try {
-        const tx = await program.rpc.mintNft({
+        const tx = await program.rpc.mintNft(null, {
           accounts: {
             config: this.config.publicKey,
             candyMachine: candyMachine,
@@ -920,4 +1163,586 @@ describe("nft-candy-machine", function () {
       assert.equal(payingTokenBalance.value.uiAmount, 0);
     });
   });
+
+  describe("withdraw unminted", function () {
+    beforeEach(async function () {
+      await initCandyMachine(
+        this,
+        5,
+        1000000,
+        { endSettingType: { amount: {} }, number: new anchor.BN(1) },
+        null
+      );
+    });
+
+    it("lets the authority mint the leftovers once the sale has ended", async function () {
+      await mintNft(this, this.authority, 1000000);
+      await assertProgramError(
+        mintNft(this, this.authority, 1000000),
+        "CandyMachineEnded"
+      );
+
+      const mint = anchor.web3.Keypair.generate();
+      const token = await getTokenWallet(
+        this.authority.publicKey,
+        mint.publicKey
+      );
+      const metadata = await getMetadata(mint.publicKey);
+      const masterEdition = await getMasterEdition(mint.publicKey);
+      await program.rpc.withdrawUnminted({
+        accounts: {
+          config: this.config.publicKey,
+          candyMachine: this.candyMachine,
+          authority: this.authority.publicKey,
+          metadata,
+          mint: mint.publicKey,
+          mintAuthority: this.authority.publicKey,
+          updateAuthority: this.authority.publicKey,
+          masterEdition,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        },
+        signers: [mint, this.authority, myWallet],
+        instructions: [
+          anchor.web3.SystemProgram.transfer({
+            fromPubkey: myWallet.publicKey,
+            toPubkey: this.authority.publicKey,
+            lamports: 10000000,
+          }),
+          ...(await createMintInstructions(
+            mint.publicKey,
+            token,
+            this.authority.publicKey
+          )),
+        ],
+      });
+
+      const machine: CandyMachine = await program.account.candyMachine.fetch(
+        this.candyMachine
+      );
+      assert.equal(machine.itemsRedeemed.toNumber(), 2);
+      const metadataAccount = await connection.getAccountInfo(metadata);
+      assert.ok(metadataAccount.data.length > 0);
+    });
+
+    it("refuses to move the end of a sale that has ended", async function () {
+      await mintNft(this, this.authority, 1000000);
+
+      await assertProgramError(
+        program.rpc.updateCandyMachine(
+          null,
+          null,
+          { endSettingType: { amount: {} }, number: new anchor.BN(5) },
+          {
+            accounts: {
+              candyMachine: this.candyMachine,
+              authority: this.authority.publicKey,
+              clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            },
+            signers: [this.authority],
+          }
+        ),
+        "EndSettingsLocked"
+      );
+    });
+  });
+
+  describe("close", function () {
+    beforeEach(async function () {
+      await initCandyMachine(
+        this,
+        5,
+        1000000,
+        { endSettingType: { amount: {} }, number: new anchor.BN(1) },
+        null
+      );
+      this.destination = anchor.web3.Keypair.generate().publicKey;
+    });
+
+    const closeCandyMachine = function (that) {
+      return program.rpc.closeCandyMachine({
+        accounts: {
+          candyMachine: that.candyMachine,
+          config: that.config.publicKey,
+          authority: that.authority.publicKey,
+          destination: that.destination,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        },
+        signers: [that.authority],
+      });
+    };
+
+    const closeConfig = function (that) {
+      return program.rpc.closeConfig({
+        accounts: {
+          config: that.config.publicKey,
+          authority: that.authority.publicKey,
+          destination: that.destination,
+        },
+        signers: [that.authority],
+      });
+    };
+
+    it("refuses to close a live candy machine or its config", async function () {
+      await assertProgramError(closeConfig(this), "ConfigStillInUse");
+      await assertProgramError(
+        closeCandyMachine(this),
+        "CandyMachineStillLive"
+      );
+    });
+
+    it("closes the candy machine once ended and then its config", async function () {
+      await mintNft(this, this.authority, 1000000);
+
+      const configLamports = await connection.getBalance(
+        this.config.publicKey
+      );
+      const machineLamports = await connection.getBalance(this.candyMachine);
+
+      await assertProgramError(closeConfig(this), "ConfigStillInUse");
+      await closeCandyMachine(this);
+      await closeConfig(this);
+
+      assert.equal(
+        await connection.getAccountInfo(this.config.publicKey),
+        null
+      );
+      assert.equal(await connection.getAccountInfo(this.candyMachine), null);
+      assert.equal(
+        await connection.getBalance(this.destination),
+        configLamports + machineLamports
+      );
+    });
+  });
+
+  describe("hidden settings", function () {
+    beforeEach(async function () {
+      // Leaves over the lines addConfigLines uploads.
+      this.leaves = [];
+      for (let i = 0; i < 5; i++) {
+        const index = Buffer.alloc(4);
+        index.writeUInt32LE(i);
+        this.leaves.push(
+          sha256(index, Buffer.from(`Sample ${i}`), Buffer.from("www.aol.com"))
+        );
+      }
+      await initCandyMachine(
+        this,
+        5,
+        1000000,
+        { endSettingType: { amount: {} }, number: new anchor.BN(1) },
+        {
+          name: "Hidden",
+          uri: "www.hidden.com",
+          hash: [...merkleProof(this.leaves, 0).root],
+        }
+      );
+      this.destination = anchor.web3.Keypair.generate().publicKey;
+    });
+
+    const reveal = function (that, index: number, proof: Buffer[], metadata) {
+      return program.rpc.reveal(
+        index,
+        proof.map((p) => [...p]),
+        {
+          accounts: {
+            config: that.config.publicKey,
+            candyMachine: that.candyMachine,
+            authority: that.authority.publicKey,
+            metadata,
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          },
+          signers: [that.authority],
+        }
+      );
+    };
+
+    it("mints a placeholder and reveals it", async function () {
+      const { metadata } = await mintNft(this, this.authority, 1000000);
+
+      let metadataAccount = await connection.getAccountInfo(metadata);
+      assert.equal(metadataName(metadataAccount.data), "Hidden #1");
+      assert.ok(
+        new PublicKey(metadataAccount.data.slice(1, 33)).equals(
+          this.candyMachine
+        )
+      );
+
+      await assertProgramError(
+        reveal(this, 0, merkleProof(this.leaves, 1).proof, metadata),
+        "RevealHashMismatch"
+      );
+
+      await reveal(this, 0, merkleProof(this.leaves, 0).proof, metadata);
+
+      metadataAccount = await connection.getAccountInfo(metadata);
+      assert.equal(metadataName(metadataAccount.data), "Sample 0");
+      assert.ok(
+        new PublicKey(metadataAccount.data.slice(1, 33)).equals(
+          this.authority.publicKey
+        )
+      );
+    });
+
+    it("refuses to close the candy machine until every placeholder is revealed", async function () {
+      const { metadata } = await mintNft(this, this.authority, 1000000);
+
+      const closeCandyMachine = () =>
+        program.rpc.closeCandyMachine({
+          accounts: {
+            candyMachine: this.candyMachine,
+            config: this.config.publicKey,
+            authority: this.authority.publicKey,
+            destination: this.destination,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+          signers: [this.authority],
+        });
+
+      await assertProgramError(closeCandyMachine(), "HiddenItemsNotRevealed");
+
+      await reveal(this, 0, merkleProof(this.leaves, 0).proof, metadata);
+      await closeCandyMachine();
+
+      assert.equal(await connection.getAccountInfo(this.candyMachine), null);
+    });
+  });
+
+  describe("update and truncate config", function () {
+    beforeEach(async function () {
+      await initCandyMachine(this, 10, 1000000, null, null, 5);
+    });
+
+    const updateConfigLine = function (that, index: number, name: string) {
+      return program.rpc.updateConfigLine(
+        index,
+        { name, uri: "www.aol.com" },
+        {
+          accounts: {
+            config: that.config.publicKey,
+            authority: that.authority.publicKey,
+          },
+          signers: [that.authority],
+        }
+      );
+    };
+
+    const truncateConfig = function (that, newMaxNumberOfLines: number) {
+      return program.rpc.truncateConfig(newMaxNumberOfLines, {
+        accounts: {
+          config: that.config.publicKey,
+          authority: that.authority.publicKey,
+        },
+        signers: [that.authority],
+      });
+    };
+
+    it("only updates lines that have not been minted", async function () {
+      await mintNft(this, this.authority, 1000000);
+      await mintNft(this, this.authority, 1000000);
+
+      await assertProgramError(
+        updateConfigLine(this, 1, "Updated"),
+        "ConfigLineAlreadyMinted"
+      );
+      await updateConfigLine(this, 2, "Updated");
+
+      const { metadata } = await mintNft(this, this.authority, 1000000);
+      const metadataAccount = await connection.getAccountInfo(metadata);
+      assert.equal(metadataName(metadataAccount.data), "Updated");
+    });
+
+    it("truncates down to the items available", async function () {
+      await mintNft(this, this.authority, 1000000);
+
+      await assertProgramError(truncateConfig(this, 4), "InvalidTruncation");
+      await truncateConfig(this, 6);
+
+      const config = await connection.getAccountInfo(this.config.publicKey);
+      assert.equal(config.data.readUInt32LE(configArrayStart), 6);
+      const configData: Config = await program.account.config.fetch(
+        this.config.publicKey
+      );
+      assert.equal(configData.data.maxNumberOfLines, 6);
+
+      // The machine count and watermarks moved down with the bitmask.
+      await assertProgramError(
+        updateConfigLine(this, 0, "Updated"),
+        "ConfigLineAlreadyMinted"
+      );
+      await updateConfigLine(this, 1, "Updated");
+    });
+  });
+
+  describe("payment options", function () {
+    beforeEach(async function () {
+      await initCandyMachine(this, 5, 1000000, null, null);
+      this.optionWallet = anchor.web3.Keypair.generate().publicKey;
+      await program.rpc.setPaymentOptions(
+        [
+          {
+            tokenMint: null,
+            price: new anchor.BN(2000000),
+            wallet: this.optionWallet,
+          },
+        ],
+        {
+          accounts: {
+            candyMachine: this.candyMachine,
+            authority: this.authority.publicKey,
+          },
+          remainingAccounts: [
+            { pubkey: this.optionWallet, isWritable: false, isSigner: false },
+          ],
+          signers: [this.authority],
+          instructions: [
+            anchor.web3.SystemProgram.transfer({
+              fromPubkey: myWallet.publicKey,
+              toPubkey: this.optionWallet,
+              lamports: 1000000,
+            }),
+          ],
+        }
+      );
+    });
+
+    it("pays the chosen option's price into its wallet", async function () {
+      await mintNft(this, this.authority, 2000000, {
+        paymentOptionIndex: 0,
+        wallet: this.optionWallet,
+      });
+
+      assert.equal(await connection.getBalance(this.optionWallet), 3000000);
+    });
+
+    it("rejects unknown options and the wrong wallet", async function () {
+      await assertProgramError(
+        mintNft(this, this.authority, 2000000, {
+          paymentOptionIndex: 1,
+          wallet: this.optionWallet,
+        }),
+        "InvalidPaymentOption"
+      );
+      await assertProgramError(
+        mintNft(this, this.authority, 2000000, { paymentOptionIndex: 0 }),
+        "WalletMismatch"
+      );
+    });
+  });
+
+  describe("freeze", function () {
+    const price = 1000000;
+
+    // Sells out after `sold` mints, and succeeds once `minItemsSold` are sold.
+    // The sale is live so that `that.buyer` can mint as well as the authority.
+    const initFreezeSale = async function (
+      that,
+      sold: number,
+      minItemsSold: number,
+      salePrice: number = price
+    ) {
+      await initCandyMachine(
+        that,
+        5,
+        salePrice,
+        { endSettingType: { amount: {} }, number: new anchor.BN(sold) },
+        null
+      );
+      await program.rpc.updateCandyMachine(null, new anchor.BN(500), null, {
+        accounts: {
+          candyMachine: that.candyMachine,
+          authority: that.authority.publicKey,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        },
+        signers: [that.authority],
+      });
+      that.buyer = anchor.web3.Keypair.generate();
+      that.escrow = (
+        await PublicKey.findProgramAddress(
+          [
+            Buffer.from(CANDY_MACHINE),
+            that.candyMachine.toBuffer(),
+            Buffer.from("escrow"),
+          ],
+          programId
+        )
+      )[0];
+      await program.rpc.setFreezeSettings(new anchor.BN(minItemsSold), {
+        accounts: {
+          candyMachine: that.candyMachine,
+          authority: that.authority.publicKey,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        },
+        signers: [that.authority],
+      });
+    };
+
+    // Mints into the payer's token account with the candy machine approved as
+    // its delegate so that it can be frozen.
+    const mintFrozen = async function (
+      that,
+      payer: anchor.web3.Keypair = that.buyer,
+      salePrice: number = price
+    ) {
+      const mint = anchor.web3.Keypair.generate();
+      const token = await getTokenWallet(payer.publicKey, mint.publicKey);
+      return await mintNft(that, payer, salePrice, {
+        mint,
+        wallet: that.escrow,
+        remainingAccounts: [
+          { pubkey: token, isWritable: true, isSigner: false },
+        ],
+        instructions: [
+          Token.createApproveInstruction(
+            TOKEN_PROGRAM_ID,
+            token,
+            that.candyMachine,
+            payer.publicKey,
+            [],
+            1
+          ),
+        ],
+      });
+    };
+
+    // spl-token account state: 1 is initialized, 2 is frozen.
+    const tokenState = async function (token: PublicKey) {
+      const account = await connection.getAccountInfo(token);
+      return AccountLayout.decode(account.data).state;
+    };
+
+    it("refuses to freeze a token account the payer does not own", async function () {
+      await initFreezeSale(this, 2, 2);
+
+      // An empty account of the new mint that belongs to someone else.
+      const mint = anchor.web3.Keypair.generate();
+      const otherToken = await getTokenWallet(
+        myWallet.publicKey,
+        mint.publicKey
+      );
+      await assertProgramError(
+        mintNft(this, this.buyer, price, {
+          mint,
+          wallet: this.escrow,
+          remainingAccounts: [
+            { pubkey: otherToken, isWritable: true, isSigner: false },
+          ],
+          instructions: [
+            createAssociatedTokenAccountInstruction(
+              otherToken,
+              myWallet.publicKey,
+              myWallet.publicKey,
+              mint.publicKey
+            ),
+          ],
+        }),
+        "NotTokenOwner"
+      );
+    });
+
+    it("thaws and releases the escrow once the sale succeeds", async function () {
+      await initFreezeSale(this, 2, 2);
+
+      const minted = [await mintFrozen(this), await mintFrozen(this)];
+      for (const { token } of minted) {
+        assert.equal(await tokenState(token), 2);
+      }
+
+      for (const { mint, token, masterEdition } of minted) {
+        await program.rpc.thawNft({
+          accounts: {
+            candyMachine: this.candyMachine,
+            tokenAccount: token,
+            mint,
+            masterEdition,
+            tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+        });
+        assert.equal(await tokenState(token), 1);
+      }
+
+      assert.equal(await connection.getBalance(this.escrow), 2 * price);
+      const walletLamports = await connection.getBalance(myWallet.publicKey);
+      await program.rpc.releaseEscrow({
+        accounts: {
+          candyMachine: this.candyMachine,
+          escrow: this.escrow,
+          wallet: myWallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        },
+      });
+      assert.equal(await connection.getBalance(this.escrow), 0);
+      // less the transaction fee, which the wallet pays as well
+      const newWalletLamports = await connection.getBalance(myWallet.publicKey);
+      assert.ok(newWalletLamports - walletLamports > 2 * price - 10000);
+    });
+
+    it("refunds the buyer once the sale falls short", async function () {
+      await initFreezeSale(this, 1, 2);
+
+      const { mint, token, metadata, masterEdition } = await mintFrozen(this);
+      const buyerLamports = await connection.getBalance(this.buyer.publicKey);
+
+      await program.rpc.refundNft({
+        accounts: {
+          candyMachine: this.candyMachine,
+          escrow: this.escrow,
+          buyer: this.buyer.publicKey,
+          tokenAccount: token,
+          mint,
+          masterEdition,
+          metadata,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        },
+        signers: [this.buyer],
+      });
+
+      const tokenBalance = await connection.getTokenAccountBalance(token);
+      assert.equal(tokenBalance.value.uiAmount, 0);
+      assert.equal(
+        await connection.getBalance(this.buyer.publicKey),
+        buyerLamports + price
+      );
+      assert.equal(await connection.getBalance(this.escrow), 0);
+    });
+
+    it("does not count the authority's own mints towards the sale", async function () {
+      await initFreezeSale(this, 1, 1);
+
+      const { token } = await mintFrozen(this, this.authority);
+      assert.equal(await tokenState(token), 1);
+      assert.equal(await connection.getBalance(this.escrow), 0);
+
+      await assertProgramError(
+        program.rpc.releaseEscrow({
+          accounts: {
+            candyMachine: this.candyMachine,
+            escrow: this.escrow,
+            wallet: myWallet.publicKey,
+            systemProgram: SystemProgram.programId,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          },
+        }),
+        "FreezeSaleNotSucceeded"
+      );
+    });
+
+    it("refuses a first escrow payment below its rent exemption", async function () {
+      await initFreezeSale(this, 2, 2, 1000);
+
+      await assertProgramError(
+        mintFrozen(this, this.buyer, 1000),
+        "EscrowNotRentExempt"
+      );
+    });
+  });
 });
@@ -306,6 +306,10 @@ pub enum MetadataError {
     // In the legacy system the reservation needs to be of size one for cpu limit reasons
     #[error("In the legacy system the reservation needs to be of size one for cpu limit reasons")]
     ReservationArrayShouldBeSizeOne,
+
+    /// Signer is not the delegate on this token account
+    #[error("Signer is not the delegate on this token account")]
+    InvalidDelegate,
 }
 
 impl PrintProgramError for MetadataError {
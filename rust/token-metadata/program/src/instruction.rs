@@ -242,6 +242,33 @@ pub enum MetadataInstruction {
     /// so that it can be found using offset searches by the RPC to make client lookups cheaper.
     ///   0. `[writable]` Metadata account
     PuffMetadata,
+
+    /// Mark a metadata as a verified member of a collection. Both the metadata's update authority
+    /// and the collection parent's update authority must sign, so membership cannot be forged.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority of the metadata
+    ///   2. `[signer]` Update authority of the collection parent's metadata
+    ///   3. `[]` Mint of the collection parent
+    ///   4. `[]` Metadata of the collection parent (pda of ['metadata', program id, collection mint id])
+    ///   5. `[]` Master edition of the collection parent (pda of ['metadata', program id, collection mint id, 'edition'])
+    VerifyCollection,
+
+    /// Freeze a token account of a master edition mint on behalf of its delegate. The master edition
+    /// is the mint's freeze authority, so it signs the actual freeze.
+    ///   0. `[signer]` Delegate on the token account
+    ///   1. `[writable]` Token account to freeze
+    ///   2. `[]` Master edition (pda of ['metadata', program id, mint id, 'edition'])
+    ///   3. `[]` Mint of the token
+    ///   4. `[]` Token program
+    FreezeDelegatedAccount,
+
+    /// Thaw a token account frozen by FreezeDelegatedAccount, signed by the same delegate.
+    ///   0. `[signer]` Delegate on the token account
+    ///   1. `[writable]` Token account to thaw
+    ///   2. `[]` Master edition (pda of ['metadata', program id, mint id, 'edition'])
+    ///   3. `[]` Mint of the token
+    ///   4. `[]` Token program
+    ThawDelegatedAccount,
 }
 
 /// Creates an CreateMetadataAccounts instruction
@@ -518,3 +545,74 @@ pub fn mint_edition_from_master_edition_via_vault_proxy(
         .unwrap(),
     }
 }
+
+/// creates a verify_collection instruction
+#[allow(clippy::too_many_arguments)]
+pub fn verify_collection(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    collection_authority: Pubkey,
+    collection_mint: Pubkey,
+    collection: Pubkey,
+    collection_master_edition_account: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(collection_authority, true),
+            AccountMeta::new_readonly(collection_mint, false),
+            AccountMeta::new_readonly(collection, false),
+            AccountMeta::new_readonly(collection_master_edition_account, false),
+        ],
+        data: MetadataInstruction::VerifyCollection.try_to_vec().unwrap(),
+    }
+}
+
+/// creates a freeze_delegated_account instruction
+pub fn freeze_delegated_account(
+    program_id: Pubkey,
+    delegate: Pubkey,
+    token_account: Pubkey,
+    edition: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(delegate, true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetadataInstruction::FreezeDelegatedAccount
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a thaw_delegated_account instruction
+pub fn thaw_delegated_account(
+    program_id: Pubkey,
+    delegate: Pubkey,
+    token_account: Pubkey,
+    edition: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(delegate, true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetadataInstruction::ThawDelegatedAccount
+            .try_to_vec()
+            .unwrap(),
+    }
+}
@@ -10,8 +10,8 @@ use {
         error::MetadataError,
         instruction::MetadataInstruction,
         state::{
-            Data, Key, MasterEditionV1, MasterEditionV2, Metadata, EDITION, MAX_MASTER_EDITION_LEN,
-            PREFIX,
+            Collection, Data, Key, MasterEditionV1, MasterEditionV2, Metadata, EDITION,
+            MAX_MASTER_EDITION_LEN, PREFIX,
         },
         utils::{
             assert_data_valid, assert_derivation, assert_initialized,
@@ -30,7 +30,9 @@ use {
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         msg,
+        program::invoke_signed,
         program_error::ProgramError,
+        program_option::COption,
         pubkey::Pubkey,
     },
     spl_token::state::{Account, Mint},
@@ -134,6 +136,18 @@ pub fn process_instruction<'a>(
             msg!("Instruction: Puff Metadata");
             process_puff_metadata_account(program_id, accounts)
         }
+        MetadataInstruction::VerifyCollection => {
+            msg!("Instruction: Verify Collection");
+            process_verify_collection(program_id, accounts)
+        }
+        MetadataInstruction::FreezeDelegatedAccount => {
+            msg!("Instruction: Freeze Delegated Account");
+            process_toggle_delegated_account_freeze(program_id, accounts, true)
+        }
+        MetadataInstruction::ThawDelegatedAccount => {
+            msg!("Instruction: Thaw Delegated Account");
+            process_toggle_delegated_account_freeze(program_id, accounts, false)
+        }
     }
 }
 
@@ -608,3 +622,131 @@ pub fn process_puff_metadata_account(
     metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
     Ok(())
 }
+
+pub fn process_verify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let collection_authority_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+    let collection_master_edition_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_account_info, program_id)?;
+    assert_owned_by(collection_metadata_info, program_id)?;
+    assert_owned_by(collection_master_edition_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_account_info)?;
+    let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    assert_update_authority_is_correct(&collection_metadata, collection_authority_info)?;
+
+    if collection_metadata.mint != *collection_mint_info.key
+        || metadata.mint == *collection_mint_info.key
+    {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // The collection parent has to be a master edition, not just any mint with metadata.
+    assert_derivation(
+        program_id,
+        collection_master_edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            collection_mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+    let edition_key = collection_master_edition_info.data.borrow()[0];
+    if edition_key != Key::MasterEditionV1 as u8 && edition_key != Key::MasterEditionV2 as u8 {
+        return Err(MetadataError::DataTypeMismatch.into());
+    }
+
+    metadata.collection = Some(Collection {
+        verified: true,
+        key: *collection_mint_info.key,
+    });
+
+    metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
+    Ok(())
+}
+
+pub fn process_toggle_delegated_account_freeze(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    freeze: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let delegate_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(delegate_info)?;
+    assert_token_program_matches_package(token_program_info)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_owned_by(edition_info, program_id)?;
+
+    let token_account: Account = assert_initialized(token_account_info)?;
+    if token_account.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    if token_account.delegate != COption::Some(*delegate_info.key) {
+        return Err(MetadataError::InvalidDelegate.into());
+    }
+
+    let bump = assert_derivation(
+        program_id,
+        edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+    let edition_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+        &[bump],
+    ];
+
+    let instruction = if freeze {
+        spl_token::instruction::freeze_account(
+            token_program_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )?
+    } else {
+        spl_token::instruction::thaw_account(
+            token_program_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )?
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            token_account_info.clone(),
+            mint_info.clone(),
+            edition_info.clone(),
+            token_program_info.clone(),
+        ],
+        &[edition_seeds],
+    )?;
+
+    Ok(())
+}
@@ -20,7 +20,7 @@ pub const MAX_SYMBOL_LENGTH: usize = 10;
 
 pub const MAX_URI_LENGTH: usize = 200;
 
-pub const MAX_METADATA_LEN: usize = 1 + 32 + 32 + MAX_DATA_SIZE + 1 + 1 + 9 + 172;
+pub const MAX_METADATA_LEN: usize = 1 + 32 + 32 + MAX_DATA_SIZE + 1 + 1 + 9 + 34 + 138;
 
 pub const MAX_DATA_SIZE: usize = 4
     + MAX_NAME_LENGTH
@@ -96,6 +96,8 @@ pub struct Metadata {
     pub is_mutable: bool,
     /// nonce for easy calculation of editions, if present
     pub edition_nonce: Option<u8>,
+    /// Collection this metadata belongs to, if present
+    pub collection: Option<Collection>,
 }
 
 impl Metadata {
@@ -107,6 +109,15 @@ impl Metadata {
     }
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct Collection {
+    /// Only set by VerifyCollection, which needs the collection parent's update authority
+    pub verified: bool,
+    /// Mint of the collection parent
+    pub key: Pubkey,
+}
+
 pub trait MasterEdition {
     fn key(&self) -> Key;
     fn supply(&self) -> u64;
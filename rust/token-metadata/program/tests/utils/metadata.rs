@@ -143,4 +143,29 @@ impl Metadata {
 
         Ok(context.banks_client.process_transaction(tx).await?)
     }
+
+    pub async fn verify_collection(
+        &self,
+        context: &mut ProgramTestContext,
+        collection: &Metadata,
+        collection_master_edition: &MasterEditionV2,
+        collection_authority: &Keypair,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::verify_collection(
+                id(),
+                self.pubkey,
+                context.payer.pubkey(),
+                collection_authority.pubkey(),
+                collection.mint.pubkey(),
+                collection.pubkey,
+                collection_master_edition.pubkey,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, collection_authority],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
 }
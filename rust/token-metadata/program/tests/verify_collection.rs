@@ -0,0 +1,115 @@
+mod utils;
+
+use metaplex_token_metadata::error::MetadataError;
+use metaplex_token_metadata::state::Collection;
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+    transport::TransportError,
+};
+use utils::*;
+
+async fn create_collection_parent(context: &mut ProgramTestContext) -> (Metadata, MasterEditionV2) {
+    let collection = Metadata::new();
+    let collection_master_edition = MasterEditionV2::new(&collection);
+
+    collection
+        .create(
+            context,
+            "Collection".to_string(),
+            "COL".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    collection_master_edition
+        .create(context, Some(0))
+        .await
+        .unwrap();
+
+    (collection, collection_master_edition)
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let (collection, collection_master_edition) = create_collection_parent(&mut context).await;
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    test_metadata
+        .verify_collection(
+            &mut context,
+            &collection,
+            &collection_master_edition,
+            &payer,
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+
+    assert_eq!(
+        metadata.collection,
+        Some(Collection {
+            verified: true,
+            key: collection.mint.pubkey(),
+        })
+    );
+}
+
+#[tokio::test]
+async fn fail_invalid_collection_authority() {
+    let mut context = program_test().start_with_context().await;
+    let (collection, collection_master_edition) = create_collection_parent(&mut context).await;
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    let fake_collection_authority = Keypair::new();
+    let result = test_metadata
+        .verify_collection(
+            &mut context,
+            &collection,
+            &collection_master_edition,
+            &fake_collection_authority,
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::UpdateAuthorityIncorrect);
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.collection, None);
+}